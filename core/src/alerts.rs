@@ -0,0 +1,565 @@
+use crate::imports::*;
+use crate::market::MarketDataMap;
+use kaspa_metrics_core::{Metric, MetricsSnapshot};
+use kaspa_wallet_core::storage::local::storage::Storage;
+
+/// Maximum number of triggered alerts retained in the alert history.
+pub const ALERT_HISTORY_LIMIT: usize = 512;
+
+/// User-defined alert rule. Price rules are evaluated by the
+/// [`MarketMonitorService`](crate::runtime::services::MarketMonitorService) on each price update, account rules
+/// are evaluated when the wallet posts account balance updates and node
+/// rules are evaluated by the [`MetricsService`](crate::runtime::services::MetricsService)
+/// on each metrics snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum AlertRule {
+    /// Price rises above the threshold in the given currency
+    PriceAbove { currency: String, threshold: f64 },
+    /// Price drops below the threshold in the given currency
+    PriceBelow { currency: String, threshold: f64 },
+    /// Account (or any account if `None`) receives funds
+    FundsReceived { account: Option<String> },
+    /// Account (or any account if `None`) balance drops below the threshold (in SOMPI)
+    BalanceBelow {
+        account: Option<String>,
+        threshold: u64,
+    },
+    /// Node metric stays above the threshold for at least `duration` seconds
+    MetricAbove {
+        metric: Metric,
+        threshold: f64,
+        duration: u64,
+    },
+    /// Node metric stays below the threshold for at least `duration` seconds
+    MetricBelow {
+        metric: Metric,
+        threshold: f64,
+        duration: u64,
+    },
+    /// Virtual DAA score does not advance for at least `duration` seconds
+    SyncStalled { duration: u64 },
+}
+
+impl AlertRule {
+    pub fn currency(&self) -> Option<&str> {
+        match self {
+            AlertRule::PriceAbove { currency, .. } | AlertRule::PriceBelow { currency, .. } => {
+                Some(currency.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_metric_rule(&self) -> bool {
+        matches!(
+            self,
+            AlertRule::MetricAbove { .. }
+                | AlertRule::MetricBelow { .. }
+                | AlertRule::SyncStalled { .. }
+        )
+    }
+
+    /// Time in seconds the rule condition must hold before the alert triggers.
+    pub fn duration(&self) -> u64 {
+        match self {
+            AlertRule::MetricAbove { duration, .. }
+            | AlertRule::MetricBelow { duration, .. }
+            | AlertRule::SyncStalled { duration } => *duration,
+            _ => 0,
+        }
+    }
+
+    fn matches_account(account: &Option<String>, account_id: &AccountId) -> bool {
+        account
+            .as_ref()
+            .map(|id| *id == account_id.to_hex())
+            .unwrap_or(true)
+    }
+
+    /// Render a short human-readable description of the rule.
+    pub fn describe(
+        &self,
+        network_type: &NetworkType,
+        account_name: impl Fn(&str) -> String,
+    ) -> String {
+        let account_label = |account: &Option<String>| {
+            account
+                .as_deref()
+                .map(&account_name)
+                .unwrap_or_else(|| i18n("any account").to_string())
+        };
+
+        match self {
+            AlertRule::PriceAbove {
+                currency,
+                threshold,
+            } => format!(
+                "{} {}",
+                i18n("Price above"),
+                format_currency_with_symbol(*threshold, precision_from_symbol(currency), currency)
+            ),
+            AlertRule::PriceBelow {
+                currency,
+                threshold,
+            } => format!(
+                "{} {}",
+                i18n("Price below"),
+                format_currency_with_symbol(*threshold, precision_from_symbol(currency), currency)
+            ),
+            AlertRule::FundsReceived { account } => {
+                format!("{} {}", i18n("Funds received by"), account_label(account))
+            }
+            AlertRule::BalanceBelow { account, threshold } => format!(
+                "{} {} {}",
+                account_label(account),
+                i18n("balance below"),
+                sompi_to_kaspa_string_with_suffix(*threshold, network_type)
+            ),
+            AlertRule::MetricAbove {
+                metric,
+                threshold,
+                duration,
+            } => format!(
+                "{} {} {}{}",
+                i18n(metric.title().0),
+                i18n("above"),
+                metric.format(*threshold, true, false),
+                describe_duration(*duration)
+            ),
+            AlertRule::MetricBelow {
+                metric,
+                threshold,
+                duration,
+            } => format!(
+                "{} {} {}{}",
+                i18n(metric.title().0),
+                i18n("below"),
+                metric.format(*threshold, true, false),
+                describe_duration(*duration)
+            ),
+            AlertRule::SyncStalled { duration } => {
+                format!(
+                    "{}{}",
+                    i18n("Node sync stalled"),
+                    describe_duration(*duration)
+                )
+            }
+        }
+    }
+}
+
+fn describe_duration(seconds: u64) -> String {
+    if seconds == 0 {
+        String::new()
+    } else if seconds % 60 == 0 {
+        format!(" {} {} {}", i18n("for"), seconds / 60, i18n("min"))
+    } else {
+        format!(" {} {} {}", i18n("for"), seconds, i18n("sec"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertSettings {
+    pub enable: bool,
+    pub os_notifications: bool,
+    pub rules: Vec<AlertRule>,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            os_notifications: true,
+            rules: vec![],
+        }
+    }
+}
+
+impl AlertSettings {
+    pub fn is_active(&self) -> bool {
+        self.enable && !self.rules.is_empty()
+    }
+
+    /// Currencies referenced by price alerts; these need to be
+    /// monitored in addition to the user-selected currencies.
+    pub fn currencies(&self) -> Vec<String> {
+        if !self.enable {
+            return vec![];
+        }
+
+        self.rules
+            .iter()
+            .filter_map(|rule| rule.currency().map(|currency| currency.to_lowercase()))
+            .collect()
+    }
+
+    /// Evaluate price rules against the previous and the current market
+    /// price lists. Rules trigger only when the price crosses the threshold.
+    pub fn evaluate_price(&self, previous: &MarketDataMap, current: &MarketDataMap) -> Vec<String> {
+        if !self.is_active() {
+            return vec![];
+        }
+
+        self.rules
+            .iter()
+            .filter_map(|rule| {
+                let currency = rule.currency()?.to_lowercase();
+                let previous = previous.get(&currency)?.price;
+                let current = current.get(&currency)?.price;
+                let precision = precision_from_symbol(currency.as_str());
+                match rule {
+                    AlertRule::PriceAbove { threshold, .. }
+                        if previous < *threshold && current >= *threshold =>
+                    {
+                        Some(format!(
+                            "{} {}",
+                            i18n("KAS price rose above"),
+                            format_currency_with_symbol(*threshold, precision, &currency)
+                        ))
+                    }
+                    AlertRule::PriceBelow { threshold, .. }
+                        if previous > *threshold && current <= *threshold =>
+                    {
+                        Some(format!(
+                            "{} {}",
+                            i18n("KAS price dropped below"),
+                            format_currency_with_symbol(*threshold, precision, &currency)
+                        ))
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Evaluate account rules against the previous and the current
+    /// account balance. The previous balance is `None` when the
+    /// account balance is reported for the first time.
+    pub fn evaluate_balance(
+        &self,
+        account: &Account,
+        previous: Option<&Balance>,
+        current: Option<&Balance>,
+        network_type: &NetworkType,
+    ) -> Vec<String> {
+        if !self.is_active() {
+            return vec![];
+        }
+
+        let Some(current) = current else {
+            return vec![];
+        };
+
+        let account_id = account.id();
+        let current_total = current.mature + current.pending;
+        let previous_total = previous.map(|balance| balance.mature + balance.pending);
+
+        self.rules
+            .iter()
+            .filter_map(|rule| match rule {
+                AlertRule::FundsReceived {
+                    account: rule_account,
+                } if AlertRule::matches_account(rule_account, &account_id) => {
+                    let previous_total = previous_total?;
+                    (current_total > previous_total).then(|| {
+                        format!(
+                            "{} {} {}",
+                            account.name_or_id(),
+                            i18n("received"),
+                            sompi_to_kaspa_string_with_suffix(
+                                current_total - previous_total,
+                                network_type
+                            )
+                        )
+                    })
+                }
+                AlertRule::BalanceBelow {
+                    account: rule_account,
+                    threshold,
+                } if AlertRule::matches_account(rule_account, &account_id) => {
+                    // the first balance report only seeds the previous balance
+                    let crossed = current_total < *threshold
+                        && previous_total
+                            .map(|previous_total| previous_total >= *threshold)
+                            .unwrap_or(false);
+                    crossed.then(|| {
+                        format!(
+                            "{} {} {}",
+                            account.name_or_id(),
+                            i18n("balance is below"),
+                            sompi_to_kaspa_string_with_suffix(*threshold, network_type)
+                        )
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+struct MetricRuleState {
+    /// Unixtime (in milliseconds) at which the rule condition started to hold
+    since: Option<f64>,
+    /// Set once the alert is dispatched; cleared when the condition stops holding
+    triggered: bool,
+}
+
+/// Tracks node metric rule conditions across consecutive metrics
+/// snapshots. An alert triggers once when its condition has held
+/// for the rule duration and re-arms when the condition clears.
+#[derive(Default)]
+pub struct MetricAlertMonitor {
+    rules: Vec<(AlertRule, MetricRuleState)>,
+    /// Last observed virtual DAA score and the time it was first observed
+    daa_score: Option<(f64, f64)>,
+}
+
+impl MetricAlertMonitor {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn evaluate(
+        &mut self,
+        settings: &AlertSettings,
+        snapshot: &MetricsSnapshot,
+    ) -> Vec<String> {
+        let now = snapshot.unixtime_millis;
+
+        let daa_score = snapshot.get(&Metric::NetworkVirtualDaaScore);
+        let stalled_since = match self.daa_score {
+            Some((score, since)) if score == daa_score => since,
+            _ => {
+                self.daa_score = Some((daa_score, now));
+                now
+            }
+        };
+
+        if !settings.is_active() {
+            self.rules.clear();
+            return vec![];
+        }
+
+        // retain the state of rules that are still present
+        let mut previous = std::mem::take(&mut self.rules);
+        self.rules = settings
+            .rules
+            .iter()
+            .filter(|rule| rule.is_metric_rule())
+            .map(|rule| {
+                let state = previous
+                    .iter()
+                    .position(|(previous_rule, _)| previous_rule == rule)
+                    .map(|index| previous.swap_remove(index).1)
+                    .unwrap_or_default();
+                (rule.clone(), state)
+            })
+            .collect();
+
+        let mut alerts = vec![];
+        for (rule, state) in self.rules.iter_mut() {
+            let since = match rule {
+                AlertRule::MetricAbove {
+                    metric, threshold, ..
+                } => (snapshot.get(metric) > *threshold).then_some(now),
+                AlertRule::MetricBelow {
+                    metric, threshold, ..
+                } => (snapshot.get(metric) < *threshold).then_some(now),
+                AlertRule::SyncStalled { .. } => (stalled_since < now).then_some(stalled_since),
+                _ => None,
+            };
+
+            let Some(since) = since else {
+                *state = MetricRuleState::default();
+                continue;
+            };
+
+            let since = *state.since.get_or_insert(since);
+            if !state.triggered && now - since >= rule.duration() as f64 * 1000.0 {
+                state.triggered = true;
+                alerts.push(Self::message(rule, snapshot));
+            }
+        }
+
+        alerts
+    }
+
+    fn message(rule: &AlertRule, snapshot: &MetricsSnapshot) -> String {
+        match rule {
+            AlertRule::MetricAbove {
+                metric, threshold, ..
+            } => format!(
+                "{} {} {} ({})",
+                i18n(metric.title().0),
+                i18n("is above"),
+                metric.format(*threshold, true, false),
+                metric.format(snapshot.get(metric), true, false)
+            ),
+            AlertRule::MetricBelow {
+                metric, threshold, ..
+            } => format!(
+                "{} {} {} ({})",
+                i18n(metric.title().0),
+                i18n("is below"),
+                metric.format(*threshold, true, false),
+                metric.format(snapshot.get(metric), true, false)
+            ),
+            AlertRule::SyncStalled { duration } => format!(
+                "{}{}",
+                i18n("Node DAA score has not advanced"),
+                describe_duration(*duration)
+            ),
+            _ => unreachable!("metric alert monitor only tracks metric rules"),
+        }
+    }
+}
+
+/// Triggered alert recorded in the alert history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    /// Unixtime in milliseconds
+    pub timestamp: f64,
+    pub message: String,
+}
+
+/// Bounded history of triggered alerts, oldest first, persisted
+/// in the application storage folder.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AlertHistory {
+    pub events: VecDeque<AlertEvent>,
+}
+
+fn alert_history_storage() -> Result<Storage> {
+    Ok(Storage::try_new("kaspa-ng.alerts")?)
+}
+
+impl AlertHistory {
+    pub async fn load() -> Self {
+        match alert_history_storage() {
+            Ok(storage) if storage.exists().await.unwrap_or(false) => {
+                workflow_store::fs::read_json::<Self>(storage.filename())
+                    .await
+                    .unwrap_or_else(|err| {
+                        log_warn!("AlertHistory::load() error: {}", err);
+                        Self::default()
+                    })
+            }
+            _ => Self::default(),
+        }
+    }
+
+    pub async fn store(&self) -> Result<()> {
+        let storage = alert_history_storage()?;
+        storage.ensure_dir().await?;
+        workflow_store::fs::write_json(storage.filename(), self).await?;
+        Ok(())
+    }
+
+    /// Record triggered alerts, discarding the oldest entries
+    /// once the history exceeds [`ALERT_HISTORY_LIMIT`].
+    pub fn record(&mut self, alerts: &[String]) {
+        let timestamp = unixtime_as_millis_f64();
+        self.events.extend(alerts.iter().map(|message| AlertEvent {
+            timestamp,
+            message: message.clone(),
+        }));
+        self.truncate();
+    }
+
+    /// Prepend previously recorded events (loaded from storage) to the history.
+    pub fn merge(&mut self, previous: AlertHistory) {
+        let mut events = previous.events;
+        events.append(&mut self.events);
+        self.events = events;
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        if self.events.len() > ALERT_HISTORY_LIMIT {
+            self.events
+                .drain(0..self.events.len() - ALERT_HISTORY_LIMIT);
+        }
+    }
+}
+
+/// Post triggered alerts to the application event channel. The
+/// application records them in the alert history and displays them
+/// as toast and, if enabled, desktop (OS) notifications.
+pub fn dispatch_alerts(application_events: &ApplicationEventsChannel, alerts: Vec<String>) {
+    if alerts.is_empty() {
+        return;
+    }
+
+    application_events
+        .sender
+        .try_send(Events::Alerts { alerts })
+        .ok();
+}
+
+cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        /// Display a desktop notification using the facilities
+        /// available on the host platform. Failures are ignored.
+        pub fn os_notify(title: &str, body: &str) {
+            use std::process::{Command, Stdio};
+
+            let mut command = if cfg!(target_os = "macos") {
+                let script = format!(
+                    "display notification {} with title {}",
+                    applescript_string(body),
+                    applescript_string(title)
+                );
+                let mut command = Command::new("osascript");
+                command.arg("-e").arg(script);
+                command
+            } else if cfg!(target_os = "windows") {
+                // title and body are passed through the environment and
+                // inserted as XML text nodes, so they need no escaping
+                let script = "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null; \
+                     $template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+                     $text = $template.GetElementsByTagName('text'); \
+                     $text.Item(0).AppendChild($template.CreateTextNode($env:KASPA_NG_NOTIFICATION_TITLE)) | Out-Null; \
+                     $text.Item(1).AppendChild($template.CreateTextNode($env:KASPA_NG_NOTIFICATION_BODY)) | Out-Null; \
+                     $toast = [Windows.UI.Notifications.ToastNotification]::new($template); \
+                     [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe').Show($toast)";
+                let mut command = Command::new("powershell");
+                command
+                    .args(["-NoProfile", "-NonInteractive", "-WindowStyle", "Hidden", "-Command", script])
+                    .env("KASPA_NG_NOTIFICATION_TITLE", title)
+                    .env("KASPA_NG_NOTIFICATION_BODY", body);
+                command
+            } else {
+                let mut command = Command::new("notify-send");
+                command.args(["--app-name", title, "--", title, body]);
+                command
+            };
+
+            match command
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+            {
+                Ok(mut child) => {
+                    // reap the helper process once it exits
+                    std::thread::spawn(move || {
+                        child.wait().ok();
+                    });
+                }
+                Err(err) => {
+                    log_warn!("Unable to display OS notification: {}", err);
+                }
+            }
+        }
+
+        /// Quote a string as an AppleScript string literal.
+        fn applescript_string(s: &str) -> String {
+            format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+    } else {
+        pub fn os_notify(_title: &str, _body: &str) {}
+    }
+}
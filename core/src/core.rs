@@ -273,6 +273,26 @@ impl Core {
         self.settings.user_interface.balance_padding
     }
 
+    /// Returns the KAS price in the user-selected fiat currency
+    /// if the market monitor is enabled and the price is available.
+    pub fn fiat_price(&self) -> Option<FiatPrice> {
+        if !self.settings.market_monitor
+            || (self.settings.node.network != Network::Mainnet
+                && !self.settings.developer.market_monitor_on_testnet)
+        {
+            return None;
+        }
+
+        let fiat_currency = self.settings.market.fiat_currency.as_str();
+        self.market
+            .as_ref()?
+            .price
+            .as_ref()?
+            .get(fiat_currency)
+            .filter(|data| data.price > 0.0)
+            .map(|data| FiatPrice::new(fiat_currency, data))
+    }
+
     pub fn has_stack(&self) -> bool {
         !self.stack.is_empty()
     }
@@ -390,6 +410,16 @@ impl Core {
                 .update_services(&self.settings.node, None);
         }
     }
+
+    pub fn apply_connection_profile(&mut self, profile: &ConnectionProfile) {
+        profile.apply(&mut self.settings.node);
+        self.get_mut::<modules::Settings>()
+            .change_node_settings(&self.settings.node);
+        self.store_settings();
+        self.runtime
+            .kaspa_service()
+            .update_services(&self.settings.node, None);
+    }
 }
 
 impl eframe::App for Core {
@@ -676,11 +706,33 @@ impl Core {
                     module.network_change(self, network);
                 });
             }
+            Events::ApplyConnectionProfile(profile) => {
+                self.apply_connection_profile(&profile);
+            }
             Events::UpdateStorage(_options) => {
                 #[cfg(not(target_arch = "wasm32"))]
                 self.storage
                     .update(Some(_options.with_network(self.settings.node.network)));
             }
+            #[allow(unused_variables)]
+            Events::StorageRelocated { target } => {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let storage_folder = target.to_string_lossy().to_string();
+                    self.settings.node.kaspad_daemon_storage_folder_enable = true;
+                    self.settings.node.kaspad_daemon_storage_folder = storage_folder.clone();
+                    self.settings.store_sync()?;
+                    self.get_mut::<modules::Settings>()
+                        .change_storage_folder(storage_folder.as_str());
+                    self.storage
+                        .track_storage_root(Some(storage_folder.as_str()));
+                    runtime().notify(UserNotification::success(format!(
+                        "{} {}",
+                        i18n("Data folder moved to"),
+                        target.display()
+                    )));
+                }
+            }
             Events::VisibilityChange(state) => match state {
                 VisibilityState::Visible => {
                     self.module.clone().show(self);
@@ -765,6 +817,18 @@ impl Core {
                     self.notifications.push(notification);
                 }
             }
+            Events::Alerts { alerts } => {
+                self.runtime.metrics_service().record_alerts(&alerts);
+                for message in alerts {
+                    if self.settings.alerts.os_notifications {
+                        crate::alerts::os_notify("Kaspa NG", message.as_str());
+                    }
+                    UserNotification::info(message)
+                        .duration(Duration::from_secs(10))
+                        .as_toast()
+                        .toast(&mut self.toasts);
+                }
+            }
             Events::Close { .. } => {}
             Events::UnlockSuccess => {}
             Events::UnlockFailure { .. } => {}
@@ -949,6 +1013,9 @@ impl Core {
                         self.account_collection = None;
                         self.wallet_descriptor = None;
                         self.prv_key_data_map = None;
+                        self.runtime
+                            .mempool_monitor_service()
+                            .clear_wallet_transactions();
 
                         self.modules.clone().into_iter().for_each(|(_, module)| {
                             module.reset(self);
@@ -1021,6 +1088,9 @@ impl Core {
                     }
                     CoreWallet::Pending { record } => match record.binding().clone() {
                         Binding::Account(id) => {
+                            self.runtime
+                                .mempool_monitor_service()
+                                .register_wallet_transaction(*record.id());
                             self.account_collection
                                 .as_ref()
                                 .and_then(|account_collection| {
@@ -1054,6 +1124,17 @@ impl Core {
                     CoreWallet::Balance { balance, id } => {
                         if let Some(account_collection) = &self.account_collection {
                             if let Some(account) = account_collection.get(&id.into()) {
+                                let previous = account.balance();
+                                let alerts = self.settings.alerts.evaluate_balance(
+                                    account,
+                                    previous.as_ref(),
+                                    balance.as_ref(),
+                                    &NetworkType::from(self.network()),
+                                );
+                                crate::alerts::dispatch_alerts(
+                                    &self.application_events_channel,
+                                    alerts,
+                                );
                                 account.update_balance(balance)?;
                             } else {
                                 log_error!("unable to find account {}", id);
@@ -86,6 +86,9 @@ pub enum Error {
     #[error("Http error: {0}")]
     HttpError(#[from] workflow_http::error::Error),
 
+    #[error("Http error: {0}")]
+    Reqwest(#[from] reqwest::Error),
+
     #[error("Invalid JSON: {0}")]
     SerdeJson(#[from] serde_json::Error),
 
@@ -103,6 +106,9 @@ pub enum Error {
 
     #[error("ParseInt")]
     ParseInt(#[from] std::num::ParseIntError),
+
+    #[error("ParseFloat")]
+    ParseFloat(#[from] std::num::ParseFloatError),
 }
 
 impl Error {
@@ -11,7 +11,11 @@ pub type ApplicationEventsChannel = crate::runtime::channel::Channel<Events>;
 pub enum Events {
     ChangeSection(TypeId),
     NetworkChange(Network),
+    ApplyConnectionProfile(Box<ConnectionProfile>),
     UpdateStorage(StorageUpdateOptions),
+    StorageRelocated {
+        target: PathBuf,
+    },
     VisibilityChange(VisibilityState),
     VersionUpdate(Release),
     ThemeChange,
@@ -42,6 +46,9 @@ pub enum Events {
     Notify {
         user_notification: UserNotification,
     },
+    Alerts {
+        alerts: Vec<String>,
+    },
     Close,
     Exit,
 }
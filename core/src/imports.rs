@@ -61,6 +61,7 @@ pub use egui::epaint::{
 pub use egui::*;
 pub use egui_plot::{PlotPoint, PlotPoints};
 
+pub use crate::alerts::{AlertRule, AlertSettings};
 pub use crate::collection::Collection;
 pub use crate::core::Core;
 pub use crate::device::{Device, Orientation};
@@ -69,7 +70,7 @@ pub use crate::error::Error;
 pub use crate::events::{ApplicationEventsChannel, Events};
 pub use crate::extensions::*;
 pub use crate::interop;
-pub use crate::market::MarketData;
+pub use crate::market::{FiatPrice, MarketData};
 pub use crate::menu::Menu;
 pub use crate::modules;
 pub use crate::modules::{Module, ModuleCaps, ModuleStyle, ModuleT};
@@ -83,8 +84,11 @@ pub use crate::result::Result;
 pub use crate::runtime::{runtime, spawn, spawn_with_result, Payload, Runtime, Service};
 pub use crate::servers::{load_public_servers, public_servers, Server};
 pub use crate::settings::{
-    KaspadNodeKind, NetworkInterfaceConfig, NetworkInterfaceKind, NodeConnectionConfigKind,
-    NodeMemoryScale, NodeSettings, RpcConfig, RpcOptions, Settings, UserInterfaceSettings,
+    ConnectionProfile, JsonMarketDataProviderSettings, KaspadConfig, KaspadNodeKind,
+    MarketDataProvider, MarketSettings, MetricsDashboard, MetricsHistoryRange, MetricsPanel,
+    MetricsPanelRange, NetworkInterfaceConfig, NetworkInterfaceKind, NodeConnectionConfigKind,
+    NodeLogLevel, NodeMemoryScale, NodeSettings, PeerSettings, PrometheusSettings, RpcConfig,
+    RpcKind, RpcOptions, ServerMonitorSettings, Settings, UserInterfaceSettings,
 };
 pub use crate::state::State;
 pub use crate::status::Status;
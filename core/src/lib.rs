@@ -5,6 +5,7 @@ extern crate self as kaspa_ng_core;
 mod core;
 pub use core::Core;
 
+pub mod alerts;
 pub mod app;
 pub mod collection;
 pub mod device;
@@ -27,6 +28,8 @@ pub mod result;
 pub mod runtime;
 pub mod servers;
 pub mod settings;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod snapshot;
 pub mod state;
 pub mod status;
 pub mod storage;
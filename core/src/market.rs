@@ -24,9 +24,168 @@ impl MarketData {
 
 pub type MarketDataMap = AHashMap<String, MarketData>;
 
-#[derive(Default, Debug)]
-pub struct Ohlc {}
+/// Fiat currencies that can be selected as the preferred
+/// currency for balance display and amount entry.
+pub const FIAT_CURRENCIES: &[&str] = &[
+    "usd", "eur", "gbp", "jpy", "cny", "krw", "inr", "rub", "try", "cad", "aud", "chf", "brl",
+    "hkd", "sgd", "nzd", "sek", "nok", "pln", "uah", "idr", "thb", "vnd", "php", "aed", "zar",
+];
+
+/// Price of KAS in the user-selected fiat currency.
+#[derive(Debug, Clone)]
+pub struct FiatPrice {
+    pub symbol: String,
+    pub price: f64,
+    pub precision: usize,
+}
+
+impl FiatPrice {
+    pub fn new(symbol: &str, data: &MarketData) -> Self {
+        Self {
+            symbol: symbol.to_uppercase(),
+            price: data.price,
+            precision: data.precision,
+        }
+    }
+
+    /// Format the fiat value of the given sompi amount.
+    pub fn format_sompi(&self, sompi: u64) -> String {
+        let amount = sompi_to_kaspa(sompi) * self.price;
+        format_currency_with_symbol(amount, self.precision, self.symbol.as_str())
+    }
+
+    /// Convert a fiat amount to sompi using the current price.
+    pub fn fiat_to_sompi(&self, amount: f64) -> Option<u64> {
+        (self.price > 0.0).then(|| kaspa_to_sompi(amount / self.price))
+    }
+
+    /// Parse a user-supplied fiat amount and convert it to sompi.
+    pub fn try_fiat_str_to_sompi(&self, text: &str) -> Result<Option<u64>> {
+        let amount = text.trim().replace(',', "");
+        if amount.is_empty() {
+            return Ok(None);
+        }
+
+        let amount = amount.parse::<f64>()?;
+        if amount < 0.0 {
+            return Err(Error::custom(i18n("Amount can not be negative")));
+        }
+
+        self.fiat_to_sompi(amount)
+            .map(Some)
+            .ok_or(Error::custom(i18n("Market price is not available")))
+    }
+}
+
+/// Time range of the OHLC market history.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OhlcRange {
+    #[default]
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl OhlcRange {
+    pub fn iter() -> impl Iterator<Item = &'static OhlcRange> {
+        [
+            OhlcRange::Day,
+            OhlcRange::Week,
+            OhlcRange::Month,
+            OhlcRange::Year,
+        ]
+        .iter()
+    }
+
+    pub fn days(&self) -> u32 {
+        match self {
+            OhlcRange::Day => 1,
+            OhlcRange::Week => 7,
+            OhlcRange::Month => 30,
+            OhlcRange::Year => 365,
+        }
+    }
+
+    /// Period after which the cached OHLC data is considered stale.
+    /// This roughly follows the candle granularity of the providers.
+    pub fn cache_ttl(&self) -> Duration {
+        match self {
+            OhlcRange::Day => Duration::from_secs(30 * 60),
+            OhlcRange::Week => Duration::from_secs(4 * 60 * 60),
+            OhlcRange::Month => Duration::from_secs(4 * 60 * 60),
+            OhlcRange::Year => Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+impl std::fmt::Display for OhlcRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OhlcRange::Day => write!(f, "1D"),
+            OhlcRange::Week => write!(f, "7D"),
+            OhlcRange::Month => write!(f, "30D"),
+            OhlcRange::Year => write!(f, "1Y"),
+        }
+    }
+}
+
+/// Single OHLC candle; `timestamp` is the candle close time in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OhlcPoint {
+    pub timestamp: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl From<[f64; 5]> for OhlcPoint {
+    fn from([timestamp, open, high, low, close]: [f64; 5]) -> Self {
+        Self {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Ohlc {
+    pub currency: String,
+    pub range: OhlcRange,
+    /// Time the data was fetched (unixtime in milliseconds)
+    pub timestamp: f64,
+    pub points: Vec<OhlcPoint>,
+}
+
+impl Ohlc {
+    pub fn new(currency: &str, range: OhlcRange, points: Vec<OhlcPoint>) -> Self {
+        Self {
+            currency: currency.to_lowercase(),
+            range,
+            timestamp: unixtime_as_millis_f64(),
+            points,
+        }
+    }
+
+    pub fn is_stale(&self) -> bool {
+        unixtime_as_millis_f64() - self.timestamp > self.range.cache_ttl().as_millis() as f64
+    }
+
+    pub fn key(&self) -> String {
+        ohlc_key(self.currency.as_str(), self.range)
+    }
+}
+
+pub fn ohlc_key(currency: &str, range: OhlcRange) -> String {
+    format!("{}:{}", currency.to_lowercase(), range)
+}
 
+/// OHLC data keyed by the currency symbol.
 pub type OhlcMap = AHashMap<String, Ohlc>;
 
 #[derive(Default, Debug)]
@@ -395,6 +395,18 @@ impl<'core> Menu<'core> {
             }
         }
 
+        ui.separator();
+        if ui.button(i18n("Mempool")).clicked() {
+            self.select::<modules::Mempool>();
+            ui.close_menu();
+        }
+
+        ui.separator();
+        if ui.button(i18n("Tools")).clicked() {
+            self.select::<modules::Tools>();
+            ui.close_menu();
+        }
+
         #[cfg(not(target_arch = "wasm32"))]
         {
             if self.core.settings.node.node_kind.is_local() {
@@ -1,4 +1,11 @@
 use crate::imports::*;
+use kaspa_bip32::DerivationPath;
+
+/// Number of receive addresses displayed when previewing
+/// the derivation selected during the account creation.
+const PREVIEW_ADDRESS_COUNT: u32 = 5;
+/// BIP-44 coin type registered for Kaspa.
+const KASPA_COIN_TYPE: u32 = 111111;
 
 #[derive(Clone)]
 pub enum CreateAccountKind {
@@ -18,8 +25,10 @@ pub enum State {
     PrivateKeyCreate,
     PrivateKeyConfirm,
     AccountName,
+    AccountDerivation,
     WalletSecret,
     PaymentSecret,
+    AccountPreview,
     CreateAccount,
     AccountError(Arc<Error>),
     PresentMnemonic(Arc<CreationData>),
@@ -48,10 +57,45 @@ enum Focus {
     #[default]
     None,
     AccountName,
+    AccountIndex,
+    CoinType,
+    DerivationPath,
     WalletSecret,
     PaymentSecret,
 }
 
+/// Account derivation selected by the user.
+#[derive(Clone)]
+enum AccountDerivation {
+    /// `m/44'/<coin type>'/<account index>'`, the next available
+    /// account index is used if the account index is not specified.
+    Bip44 {
+        coin_type: u32,
+        account_index: Option<u64>,
+    },
+    /// Custom account derivation path
+    Custom(DerivationPath),
+}
+
+impl AccountDerivation {
+    /// Kaspa wallet accounts can only be created for the
+    /// standard Kaspa `m/44'/111111'/<index>'` derivation path.
+    fn is_standard(&self) -> bool {
+        match self {
+            AccountDerivation::Bip44 { coin_type, .. } => *coin_type == KASPA_COIN_TYPE,
+            AccountDerivation::Custom(path) => standard_account_index(path).is_some(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            AccountDerivation::Bip44 { coin_type, account_index : Some(account_index) } => format!("m/44'/{coin_type}'/{account_index}'"),
+            AccountDerivation::Bip44 { coin_type, account_index : None } => format!("m/44'/{coin_type}'/<{}>'", i18n("next")),
+            AccountDerivation::Custom(path) => path.to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Default)]
 // #[derive(Default)]
 struct Context {
@@ -59,10 +103,109 @@ struct Context {
     account_kind: Option<CreateAccountKind>,
     _create_private_key: bool,
     account_name: String,
+    account_index: String,
+    coin_type: String,
+    enable_custom_derivation_path: bool,
+    derivation_path: String,
     // enable_payment_secret: bool,
     wallet_secret : String,
     payment_secret: String,
     // payment_secret_confirm: String,
+    preview_path: Option<DerivationPath>,
+    preview_addresses: Option<Vec<String>>,
+    preview_error: Option<String>,
+}
+
+impl Context {
+    fn derivation(&self) -> Result<AccountDerivation> {
+        if self.enable_custom_derivation_path {
+            Ok(AccountDerivation::Custom(self.derivation_path.trim().parse::<DerivationPath>()?))
+        } else {
+            Ok(AccountDerivation::Bip44 {
+                coin_type : self.coin_type()?,
+                account_index : self.account_index()?,
+            })
+        }
+    }
+
+    /// Account index entered by the user, `None` selects
+    /// the next available account index.
+    fn account_index(&self) -> Result<Option<u64>> {
+        let account_index = self.account_index.trim();
+        if account_index.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(account_index.parse::<u64>()?))
+        }
+    }
+
+    fn coin_type(&self) -> Result<u32> {
+        let coin_type = self.coin_type.trim();
+        if coin_type.is_empty() {
+            Ok(KASPA_COIN_TYPE)
+        } else {
+            Ok(coin_type.parse::<u32>()?)
+        }
+    }
+
+    fn reset_preview(&mut self) {
+        self.preview_path = None;
+        self.preview_addresses = None;
+        self.preview_error = None;
+    }
+}
+
+/// BIP-44 account derivation path (without the
+/// address type and the address index).
+fn bip44_derivation_path(coin_type: u32, account_index: u64) -> Result<DerivationPath> {
+    Ok(format!("m/44'/{coin_type}'/{account_index}'").parse::<DerivationPath>()?)
+}
+
+/// Returns the BIP-32 account index if the derivation path matches
+/// the standard Kaspa `m/44'/111111'/<index>'` path.
+fn standard_account_index(path: &DerivationPath) -> Option<u64> {
+    let path = path.to_string();
+    let index = path.strip_prefix(format!("m/44'/{KASPA_COIN_TYPE}'/").as_str())?;
+    index.strip_suffix('\'')?.parse::<u64>().ok()
+}
+
+/// Resolves the account derivation path (using the next available account
+/// index if none is specified, matching the wallet account creation) and
+/// derives the first receive addresses of the account.
+async fn derive_preview_addresses(
+    wallet: Arc<dyn WalletApi>,
+    prv_key_data_id: PrvKeyDataId,
+    wallet_secret: Secret,
+    payment_secret: Option<Secret>,
+    derivation: AccountDerivation,
+    network: Network,
+) -> Result<(DerivationPath, Vec<String>)> {
+    let account_path = match derivation {
+        AccountDerivation::Bip44 { coin_type, account_index : Some(account_index) } => {
+            bip44_derivation_path(coin_type, account_index)?
+        }
+        AccountDerivation::Bip44 { coin_type, account_index : None } => {
+            let account_index = wallet.clone().accounts_enumerate().await?
+                .iter()
+                .filter(|descriptor| (&descriptor.prv_key_data_ids).into_iter().any(|id| id == prv_key_data_id))
+                .count() as u64;
+            bip44_derivation_path(coin_type, account_index)?
+        }
+        AccountDerivation::Custom(path) => path,
+    };
+
+    let prv_key_data = wallet.prv_key_data_get(prv_key_data_id, wallet_secret).await?;
+    let xprv = prv_key_data.get_xprv(payment_secret.as_ref())?;
+    let prefix = kaspa_addresses::Prefix::from(NetworkType::from(network));
+
+    let addresses = (0..PREVIEW_ADDRESS_COUNT).map(|index| {
+        let path = format!("{account_path}/0/{index}").parse::<DerivationPath>()?;
+        let public_key = xprv.clone().derive_path(path)?.public_key().to_bytes();
+        let address = Address::new(prefix, kaspa_addresses::Version::PubKey, &public_key[1..]);
+        Ok(address.to_string())
+    }).collect::<Result<Vec<_>>>()?;
+
+    Ok((account_path, addresses))
 }
 
 pub struct AccountCreate {
@@ -178,14 +321,113 @@ impl ModuleT for AccountCreate {
                                     .vertical_align(Align::Center))
                             },
                         ).submit(|_,focus| {
-                            this.state = State::WalletSecret;
-                            focus.next(Focus::WalletSecret);
+                            this.state = State::AccountDerivation;
+                            focus.next(Focus::AccountIndex);
                         })
                         .build(ui);
                 
                     })
                     .with_footer(|this,ui| {
                         if ui.large_button(i18n("Continue")).clicked() {
+                            this.state = State::AccountDerivation;
+                            this.focus.next(Focus::AccountIndex);
+                        }
+                    })
+                    .render(ui);
+            }
+
+            State::AccountDerivation => {
+
+                Panel::new(self)
+                    .with_caption(i18n("Account Derivation"))
+                    .with_back(|this| {
+                        this.state = State::AccountName;
+                    })
+                    .with_close_enabled(false, |_|{
+                    })
+                    .with_header(|_ctx,ui| {
+                        ui.label(i18n("Leave the defaults to create the next account in your wallet. To recover funds created by another wallet, please specify the account index, the coin type or a custom derivation path used by that wallet."));
+                    })
+                    .with_body(|this,ui| {
+
+                        ui.add_space(8.);
+                        ui.checkbox(&mut this.context.enable_custom_derivation_path, i18n("Use custom derivation path"));
+                        ui.add_space(8.);
+
+                        if this.context.enable_custom_derivation_path {
+                            if this.context.derivation_path.is_empty() {
+                                let coin_type = this.context.coin_type().unwrap_or(KASPA_COIN_TYPE);
+                                let account_index = this.context.account_index().ok().flatten().unwrap_or_default();
+                                if let Ok(path) = bip44_derivation_path(coin_type, account_index) {
+                                    this.context.derivation_path = path.to_string();
+                                }
+                            }
+
+                            TextEditor::new(
+                                &mut this.context.derivation_path,
+                                &mut this.focus,
+                                Focus::DerivationPath,
+                                |ui, text| {
+                                    ui.label(RichText::new(i18n("Enter account derivation path (e.g. m/44'/111111'/0')")).size(12.).raised());
+                                    ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(text)
+                                        .vertical_align(Align::Center))
+                                },
+                            ).submit(|_,focus| {
+                                focus.clear();
+                            })
+                            .build(ui);
+                        } else {
+                            TextEditor::new(
+                                &mut this.context.account_index,
+                                &mut this.focus,
+                                Focus::AccountIndex,
+                                |ui, text| {
+                                    ui.label(RichText::new(i18n("Enter account index (optional)")).size(12.).raised());
+                                    ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(text)
+                                        .hint_text(i18n("Next available account"))
+                                        .vertical_align(Align::Center))
+                                },
+                            ).submit(|_,focus| {
+                                focus.next(Focus::CoinType);
+                            })
+                            .build(ui);
+
+                            ui.add_space(8.);
+
+                            TextEditor::new(
+                                &mut this.context.coin_type,
+                                &mut this.focus,
+                                Focus::CoinType,
+                                |ui, text| {
+                                    ui.label(RichText::new(i18n("Enter coin type (optional)")).size(12.).raised());
+                                    ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(text)
+                                        .hint_text(KASPA_COIN_TYPE.to_string())
+                                        .vertical_align(Align::Center))
+                                },
+                            ).submit(|_,focus| {
+                                focus.clear();
+                            })
+                            .build(ui);
+                        }
+
+                        ui.add_space(8.);
+
+                        match this.context.derivation() {
+                            Ok(derivation) => {
+                                ui.label(format!("{} {}", i18n("Derivation path:"), derivation.describe()));
+                                if !derivation.is_standard() {
+                                    ui.label(" ");
+                                    ui.label(RichText::new(i18n("Wallet accounts can only be created for the standard Kaspa derivation path. You can preview addresses for this derivation, but the account can not be created.")).color(theme_color().warning_color));
+                                }
+                            }
+                            Err(err) => {
+                                ui.label(RichText::new(err.to_string()).color(theme_color().error_color));
+                            }
+                        }
+                    })
+                    .with_footer(|this,ui| {
+                        let enabled = this.context.derivation().is_ok();
+                        if ui.large_button_enabled(enabled, i18n("Continue")).clicked() {
                             this.state = State::WalletSecret;
                             this.focus.next(Focus::WalletSecret);
                         }
@@ -201,7 +443,7 @@ impl ModuleT for AccountCreate {
                 Panel::new(self)
                     .with_caption(i18n("Wallet Secret"))
                     .with_back(|this| {
-                        this.state = State::AccountName;
+                        this.state = State::AccountDerivation;
                     })
                     .with_close_enabled(false, |_|{
                     })
@@ -239,7 +481,8 @@ impl ModuleT for AccountCreate {
                         self.state = State::PaymentSecret;
                         self.focus.next(Focus::PaymentSecret);
                     } else {
-                        self.state = State::CreateAccount;
+                        self.context.reset_preview();
+                        self.state = State::AccountPreview;
                     }
                 }
             }
@@ -268,7 +511,8 @@ impl ModuleT for AccountCreate {
                             },
                         ).submit(|text,focus| {
                             if !text.is_empty() {
-                                this.state = State::CreateAccount;
+                                this.context.reset_preview();
+                                this.state = State::AccountPreview;
                                 focus.clear()
                             }
                         })
@@ -277,7 +521,85 @@ impl ModuleT for AccountCreate {
                     .with_footer(|this,ui| {
                         let enabled = !this.context.payment_secret.is_empty();
                         if ui.large_button_enabled(enabled,i18n("Continue")).clicked() {
-                            this.state = State::CreateAccount;
+                            this.context.reset_preview();
+                            this.state = State::AccountPreview;
+                        }
+                    })
+                    .render(ui);
+            }
+
+            State::AccountPreview => {
+
+                let preview_result = Payload::<Result<(DerivationPath, Vec<String>)>>::new("account_create_preview_result");
+                if self.context.preview_addresses.is_none() && self.context.preview_error.is_none() && !preview_result.is_pending() {
+                    let wallet = self.runtime.wallet().clone();
+                    let network = core.network();
+                    let prv_key_data_info = self.context.prv_key_data_info.clone();
+                    let derivation = self.context.derivation();
+                    let wallet_secret = Secret::from(self.context.wallet_secret.clone());
+                    let payment_secret = prv_key_data_info.as_ref().and_then(|info| {
+                        info.requires_bip39_passphrase().then_some(Secret::from(self.context.payment_secret.clone()))
+                    });
+
+                    spawn_with_result(&preview_result, async move {
+                        let prv_key_data_id = *prv_key_data_info.as_ref().ok_or(Error::custom("No private key data available"))?.id();
+                        derive_preview_addresses(wallet, prv_key_data_id, wallet_secret, payment_secret, derivation?, network).await
+                    });
+                }
+
+                if let Some(result) = preview_result.take() {
+                    match result {
+                        Ok((path, addresses)) => {
+                            self.context.preview_path = Some(path);
+                            self.context.preview_addresses = Some(addresses);
+                        }
+                        Err(err) => { self.context.preview_error = Some(err.to_string()); }
+                    }
+                }
+
+                Panel::new(self)
+                    .with_caption(i18n("Confirm Addresses"))
+                    .with_back(|this| {
+                        this.context.reset_preview();
+                        this.state = State::AccountDerivation;
+                    })
+                    .with_close_enabled(false, |_|{
+                    })
+                    .with_header(|this,ui| {
+                        ui.label(i18n("Please make sure that the following addresses match the addresses of the account you are recovering."));
+                        ui.label(" ");
+                        if let Some(path) = this.context.preview_path.as_ref() {
+                            ui.label(format!("{} {path}", i18n("Derivation path:")));
+                            if standard_account_index(path).is_none() {
+                                ui.label(" ");
+                                ui.label(RichText::new(i18n("Wallet accounts can only be created for the standard Kaspa derivation path.")).color(theme_color().warning_color));
+                            }
+                        }
+                    })
+                    .with_body(|this,ui| {
+                        if let Some(addresses) = this.context.preview_addresses.as_ref() {
+                            for (index, address) in addresses.iter().enumerate() {
+                                ui.label(RichText::new(format!("#{index}")).size(12.).raised());
+                                ui.label(RichText::new(format_address_string(address.as_str(), Some(12))).font(FontId::monospace(14.)));
+                                ui.add_space(4.);
+                            }
+                        } else if let Some(error) = this.context.preview_error.as_ref() {
+                            ui.label(RichText::new(error).color(theme_color().error_color));
+                        } else {
+                            ui.add_space(32.);
+                            ui.add(egui::Spinner::new().size(48.));
+                        }
+                    })
+                    .with_footer(|this,ui| {
+                        let is_standard = this.context.preview_path.as_ref().map(|path| standard_account_index(path).is_some()).unwrap_or(true);
+                        if is_standard {
+                            let enabled = this.context.preview_addresses.is_some();
+                            if ui.large_button_enabled(enabled, i18n("Create Account")).clicked() {
+                                this.state = State::CreateAccount;
+                            }
+                        } else if ui.large_button(i18n("Change Derivation")).clicked() {
+                            this.context.reset_preview();
+                            this.state = State::AccountDerivation;
                         }
                     })
                     .render(ui);
@@ -320,9 +642,12 @@ impl ModuleT for AccountCreate {
                         });
 
                         let prv_key_data_id = *args.prv_key_data_info.as_ref().unwrap().id();
+                        // create the account for the derivation path whose addresses were confirmed by the user
+                        let account_path = args.preview_path.as_ref().ok_or(Error::custom(i18n("Account derivation path is not resolved")))?;
+                        let account_index = Some(standard_account_index(account_path).ok_or(Error::custom(i18n("Wallet accounts can only be created for the standard Kaspa derivation path.")))?);
 
                         let prv_key_data_args = PrvKeyDataArgs { prv_key_data_id, payment_secret };
-                        let account_args = AccountCreateArgsBip32 { account_name, account_index: None };
+                        let account_args = AccountCreateArgsBip32 { account_name, account_index };
                         let account_create_args = AccountCreateArgs::Bip32 { prv_key_data_args, account_args };
 
                         let account_descriptor = wallet.accounts_create(wallet_secret, account_create_args).await?;
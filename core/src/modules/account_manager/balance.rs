@@ -31,43 +31,50 @@ impl<'context> BalancePane<'context> {
                 );
             }
 
-            if core.settings.market_monitor && (core.settings.node.network == Network::Mainnet || core.settings.developer.market_monitor_on_testnet) {
-                if let Some(market) = core.market.as_ref() {
-                    if let Some(price_list) = market.price.as_ref() {
-                        let mut symbols = price_list.keys().collect::<Vec<_>>();
-                        symbols.sort();
-                        ui.vertical_centered(|ui| {
-                            let text = symbols.into_iter().filter_map(|symbol| {
-                                    price_list.get(symbol).map(|data| {
-                                        let symbol = symbol.to_uppercase();
-                                        let MarketData { price,  precision, .. } = data;
-                                        // let text = 
-                                        let amount = sompi_to_kaspa(balance.mature) * (*price);
-                                        format_currency_with_symbol(amount, *precision, symbol.as_str())
-                                    })
-                            }).collect::<Vec<_>>().join("  ");
-                            ui.label(RichText::new(text).font(FontId::proportional(14.)));
-                        });
-                    }
+            let fiat_price = core.fiat_price();
+
+            if let Some(fiat_price) = fiat_price.as_ref() {
+                ui.label(RichText::new(fiat_price.format_sompi(balance.mature)).font(FontId::proportional(18.)));
+
+                if let Some(price_list) = core.market.as_ref().and_then(|market| market.price.as_ref()) {
+                    let mut symbols = price_list.keys().filter(|symbol| symbol.to_uppercase() != fiat_price.symbol).collect::<Vec<_>>();
+                    symbols.sort();
+                    ui.vertical_centered(|ui| {
+                        let text = symbols.into_iter().filter_map(|symbol| {
+                                price_list.get(symbol).map(|data| {
+                                    let symbol = symbol.to_uppercase();
+                                    let MarketData { price,  precision, .. } = data;
+                                    let amount = sompi_to_kaspa(balance.mature) * (*price);
+                                    format_currency_with_symbol(amount, *precision, symbol.as_str())
+                                })
+                        }).collect::<Vec<_>>().join("  ");
+                        ui.label(RichText::new(text).font(FontId::proportional(14.)));
+                    });
                 }
             }
             
+            let fiat_suffix = |sompi : u64| {
+                fiat_price.as_ref().map(|fiat_price| format!(" ({})", fiat_price.format_sompi(sompi))).unwrap_or_default()
+            };
+
             if balance.pending != 0 {
                 ui.label(format!(
-                    "Pending: {}",
+                    "Pending: {}{}",
                     sompi_to_kaspa_string_with_suffix(
                         balance.pending,
                         network_type
-                    )
+                    ),
+                    fiat_suffix(balance.pending)
                 ));
             }
             if balance.outgoing != 0 {
                 ui.label(format!(
-                    "Sending: {}",
+                    "Sending: {}{}",
                     sompi_to_kaspa_string_with_suffix(
                         balance.outgoing,
                         network_type
-                    )
+                    ),
+                    fiat_suffix(balance.outgoing)
                 ));
             }
 
@@ -20,6 +20,12 @@ impl<'context> Estimator<'context> {
         let mut request_send = false;
         let mut request_estimate = self.context.request_estimate.take().unwrap_or_default();
 
+        let fiat_price = core.fiat_price();
+        if fiat_price.is_none() && self.context.send_amount_in_fiat {
+            self.context.send_amount_in_fiat = false;
+            request_estimate = true;
+        }
+
         match self.context.transaction_kind.as_ref().unwrap() {
             TransactionKind::Send => {
                 Destination::new(self.context).render(core, ui, rc);
@@ -31,13 +37,29 @@ impl<'context> Estimator<'context> {
             }
         }
 
+        if let Some(fiat_price) = fiat_price.as_ref() {
+            ui.add_space(8.);
+            let text = format!("{} {}", i18n("Enter amount in"), fiat_price.symbol);
+            if ui.checkbox(&mut self.context.send_amount_in_fiat, text).changed() {
+                self.context.send_amount_text.clear();
+                self.context.focus.next(Focus::Amount);
+                request_estimate = true;
+            }
+        }
+
+        let amount_suffix = if self.context.send_amount_in_fiat {
+            fiat_price.as_ref().map(|fiat_price| fiat_price.symbol.as_str()).unwrap_or_default()
+        } else {
+            kaspa_suffix(network_type)
+        };
+
         let response = TextEditor::new(
             &mut self.context.send_amount_text,
             &mut self.context.focus,
             Focus::Amount,
             |ui, text| {
                 ui.add_space(8.);
-                ui.label(RichText::new(format!("{} {} {}", i18n("Enter"), kaspa_suffix(network_type), i18n("amount to send"))).size(12.).raised());
+                ui.label(RichText::new(format!("{} {} {}", i18n("Enter"), amount_suffix, i18n("amount to send"))).size(12.).raised());
                 ui.add_sized(Overview::editor_size(ui), TextEdit::singleline(text)
                     .vertical_align(Align::Center))
             },
@@ -47,10 +69,24 @@ impl<'context> Estimator<'context> {
         })
         .build(ui);
 
+        if let Some(fiat_price) = fiat_price.as_ref() {
+            let conversion = if self.context.send_amount_in_fiat {
+                fiat_price.try_fiat_str_to_sompi(self.context.send_amount_text.as_str()).ok().flatten()
+                    .map(|sompi| sompi_to_kaspa_string_with_suffix(sompi, network_type))
+            } else {
+                try_kaspa_str_to_sompi(self.context.send_amount_text.as_str()).ok().flatten()
+                    .map(|sompi| fiat_price.format_sompi(sompi))
+            };
+
+            if let Some(conversion) = conversion {
+                ui.label(RichText::new(format!("≈ {conversion}")).size(12.).raised());
+            }
+        }
+
         if response.text_edit_submit(ui) {
             if self.context.enable_priority_fees {
                 self.context.focus.next(Focus::Fees);
-            } else if self.update_user_args() {
+            } else if self.update_user_args(fiat_price.as_ref()) {
                 request_send = true;
             }
         }
@@ -104,15 +140,20 @@ impl<'context> Estimator<'context> {
         ui.add_space(8.);
         let ready_to_send = match &*self.context.estimate.lock().unwrap() {
             EstimatorStatus::GeneratorSummary(estimate) => {
+                let fiat_suffix = |sompi : u64| {
+                    fiat_price.as_ref().map(|fiat_price| format!(" ({})", fiat_price.format_sompi(sompi))).unwrap_or_default()
+                };
+
                 if let Some(final_transaction_amount) = estimate.final_transaction_amount {
-                    ui.label(format!("{} {}",i18n("Final Amount:"), sompi_to_kaspa_string_with_suffix(final_transaction_amount + estimate.aggregated_fees, network_type)));
+                    let final_amount = final_transaction_amount + estimate.aggregated_fees;
+                    ui.label(format!("{} {}{}",i18n("Final Amount:"), sompi_to_kaspa_string_with_suffix(final_amount, network_type), fiat_suffix(final_amount)));
                 }
                 let fee_title = if self.context.priority_fees_sompi != 0 {
                     i18n("Network and Priority Fees:")
                 } else {
                     i18n("Network Fees:")
                 };
-                ui.label(format!("{} {}", fee_title, sompi_to_kaspa_string_with_suffix(estimate.aggregated_fees, network_type)));
+                ui.label(format!("{} {}{}", fee_title, sompi_to_kaspa_string_with_suffix(estimate.aggregated_fees, network_type), fiat_suffix(estimate.aggregated_fees)));
                 ui.label(format!("{} {} {} {}",i18n("Transactions:"), estimate.number_of_generated_transactions, i18n("UTXOs:"), estimate.aggregated_utxos));
                 
                 self.context.address_status == AddressStatus::Valid || (self.context.transaction_kind == Some(TransactionKind::Transfer) && self.context.transfer_to_account.is_some())
@@ -154,7 +195,7 @@ impl<'context> Estimator<'context> {
 
         });
 
-        self.update_user_args() 
+        self.update_user_args(fiat_price.as_ref()) 
             && request_estimate 
             && matches!(self.context.action,Action::Estimating)
 
@@ -162,10 +203,17 @@ impl<'context> Estimator<'context> {
 
 
 
-    fn update_user_args(&mut self) -> bool {
+    fn update_user_args(&mut self, fiat_price : Option<&FiatPrice>) -> bool {
         let mut valid = true;
 
-        match try_kaspa_str_to_sompi(self.context.send_amount_text.as_str()) {
+        let send_amount_sompi = match fiat_price {
+            Some(fiat_price) if self.context.send_amount_in_fiat => {
+                fiat_price.try_fiat_str_to_sompi(self.context.send_amount_text.as_str())
+            }
+            _ => try_kaspa_str_to_sompi(self.context.send_amount_text.as_str()).map_err(Error::from),
+        };
+
+        match send_amount_sompi {
             Ok(Some(sompi)) => {
                 self.context.send_amount_sompi = sompi;
             }
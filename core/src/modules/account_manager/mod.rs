@@ -117,6 +117,7 @@ pub struct ManagerContext {
     destination_address_string : String,
     send_amount_text: String,
     send_amount_sompi : u64,
+    send_amount_in_fiat : bool,
     enable_priority_fees : bool,
     priority_fees_text : String,
     priority_fees_sompi : u64,
@@ -152,6 +153,7 @@ impl Zeroize for ManagerContext {
         self.destination_address_string = String::default();
         self.send_amount_text = String::default();
         self.send_amount_sompi = 0;
+        self.send_amount_in_fiat = false;
         self.enable_priority_fees = false;
         self.priority_fees_text = String::default();
         self.priority_fees_sompi = 0;
@@ -8,8 +8,9 @@ impl Transactions {
         Self { }
     }
 
-    pub fn render(&mut self, ui: &mut Ui, _core : &mut Core, rc : &RenderContext) {
+    pub fn render(&mut self, ui: &mut Ui, core : &mut Core, rc : &RenderContext) {
         let RenderContext { account, network_type, current_daa_score, .. } = rc;
+        let fiat_price = core.fiat_price();
 
         egui::ScrollArea::vertical().auto_shrink([false,false]).show(ui, |ui| {
             let transactions = account.transactions();
@@ -21,7 +22,7 @@ impl Transactions {
             } else {
                 let total: u64 = transactions.iter().map(|transaction|transaction.aggregate_input_value()).sum();
                 transactions.iter().for_each(|transaction| {
-                    transaction.render(ui, *network_type, *current_daa_score, true, Some(total));
+                    transaction.render(ui, *network_type, *current_daa_score, true, Some(total), fiat_price.as_ref());
                 });
             }
         });
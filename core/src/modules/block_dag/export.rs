@@ -0,0 +1,213 @@
+use crate::imports::*;
+use kaspa_rpc_core::RpcBlock;
+use egui_plot::PlotBounds;
+use std::fmt::Write as _;
+
+/// Horizontal figure resolution (SVG units per DAA score)
+const UNITS_PER_DAA : f64 = 48.0;
+/// Vertical to horizontal unit ratio, matches the plot data aspect
+const DATA_ASPECT : f64 = 0.2;
+const STROKE_SCALE : f64 = 1.5;
+/// Maximum width or height of an exported PNG image
+const MAX_PNG_DIMENSION : f64 = 16_384.0;
+/// Maximum number of pixels of an exported PNG image (256 MiB of RGBA data)
+const MAX_PNG_PIXELS : f64 = 64.0 * 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Svg,
+    Png,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Svg => "svg",
+            ExportFormat::Png => "png",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Svg => "SVG",
+            ExportFormat::Png => "PNG",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportRange {
+    Viewport,
+    DaaRange,
+}
+
+pub struct ExportSettings {
+    pub range : ExportRange,
+    pub format : ExportFormat,
+    pub scale : f32,
+    pub daa_from : String,
+    pub daa_to : String,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            range : ExportRange::Viewport,
+            format : ExportFormat::Svg,
+            scale : 4.0,
+            daa_from : String::new(),
+            daa_to : String::new(),
+        }
+    }
+}
+
+/// Snapshot of the BlockDAG graph rendered into a static figure.
+pub struct DagFigure {
+    pub blocks : Vec<(Arc<RpcBlock>, PlotPoint, bool)>,
+    pub bounds : PlotBounds,
+    pub block_scale : f64,
+    pub parent_levels : usize,
+    pub parent_threshold : usize,
+    pub bezier : bool,
+    pub show_vspc : bool,
+    pub background : Color32,
+}
+
+impl DagFigure {
+
+    pub fn size(&self) -> (f64, f64) {
+        (self.bounds.width() * UNITS_PER_DAA, self.bounds.height() * UNITS_PER_DAA * DATA_ASPECT)
+    }
+
+    fn transform(&self, PlotPoint { x, y } : PlotPoint) -> (f64, f64) {
+        ((x - self.bounds.min()[0]) * UNITS_PER_DAA, (self.bounds.max()[1] - y) * UNITS_PER_DAA * DATA_ASPECT)
+    }
+
+    pub fn to_svg(&self) -> String {
+        let theme_color = theme_color();
+        let (width, height) = self.size();
+
+        let mut svg = String::new();
+        writeln!(svg, r#"<?xml version="1.0" encoding="UTF-8"?>"#).ok();
+        writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.2} {height:.2}">"#).ok();
+        writeln!(svg, r#"<rect width="100%" height="100%" {}/>"#, fill(self.background)).ok();
+
+        let parent_levels = self.parent_levels.max(1);
+        let block_map : AHashMap<KaspaHash,(PlotPoint,bool)> = self.blocks.iter().map(|(block, point, vspc)|(block.header.hash,(*point,*vspc))).collect();
+
+        let mut edges_parent = String::new();
+        let mut edges_vspc = String::new();
+        for (block, point, vspc) in self.blocks.iter() {
+            for (level, parent_level) in block.header.parents_by_level.iter().enumerate() {
+                if level >= parent_levels {
+                    break;
+                }
+                for parent_hash in parent_level.iter() {
+                    if let Some((parent_point, parent_vspc)) = block_map.get(parent_hash) {
+                        if (point.x - parent_point.x).abs() > self.parent_threshold as f64 {
+                            continue;
+                        }
+
+                        let path = self.edge(*point, *parent_point);
+                        if self.show_vspc && level == 0 && *vspc && *parent_vspc {
+                            writeln!(edges_vspc, r#"<path d="{path}"/>"#).ok();
+                        } else {
+                            writeln!(edges_parent, r#"<path d="{path}"/>"#).ok();
+                        }
+                    }
+                }
+            }
+        }
+
+        writeln!(svg, r#"<g fill="none" {}>"#, stroke(theme_color.block_dag_parent_connect_color, STROKE_SCALE)).ok();
+        svg.push_str(&edges_parent);
+        writeln!(svg, "</g>").ok();
+        writeln!(svg, r#"<g fill="none" stroke-linecap="round" {}>"#, stroke(theme_color.block_dag_vspc_connect_color, 3.0 * STROKE_SCALE)).ok();
+        svg.push_str(&edges_vspc);
+        writeln!(svg, "</g>").ok();
+
+        let d = 1.5 * self.block_scale;
+        let block_width = 0.4 * d * UNITS_PER_DAA;
+        let block_height = 2.0 * d * UNITS_PER_DAA * DATA_ASPECT;
+        writeln!(svg, r#"<g {} {}>"#, fill(theme_color.block_dag_block_fill_color), stroke(theme_color.block_dag_block_stroke_color, STROKE_SCALE)).ok();
+        for (block, point, _) in self.blocks.iter() {
+            let (x, y) = self.transform(*point);
+            writeln!(svg, r#"<rect x="{:.2}" y="{:.2}" width="{block_width:.2}" height="{block_height:.2}"><title>{}</title></rect>"#,
+                x - block_width * 0.5,
+                y - block_height * 0.5,
+                block.header.hash,
+            ).ok();
+        }
+        writeln!(svg, "</g>").ok();
+
+        writeln!(svg, "</svg>").ok();
+        svg
+    }
+
+    fn edge(&self, from : PlotPoint, to : PlotPoint) -> String {
+        let (x1, y1) = self.transform(from);
+        let (x2, y2) = self.transform(to);
+        if self.bezier {
+            // same control points as the on-screen bezier curves
+            let offset = (x2 - x1) * 0.6;
+            format!("M{x1:.2} {y1:.2}C{:.2} {y1:.2} {:.2} {y2:.2} {x2:.2} {y2:.2}", x1 + offset, x2 - offset)
+        } else {
+            format!("M{x1:.2} {y1:.2}L{x2:.2} {y2:.2}")
+        }
+    }
+
+    /// Largest PNG rasterization scale not exceeding the requested one
+    /// that keeps the image within the PNG dimension and pixel limits.
+    pub fn png_scale(&self, scale : f32) -> f32 {
+        let (width, height) = self.size();
+        let (width, height) = (width.max(1.0), height.max(1.0));
+        let max_scale = (MAX_PNG_DIMENSION / width)
+            .min(MAX_PNG_DIMENSION / height)
+            .min((MAX_PNG_PIXELS / (width * height)).sqrt());
+        scale.min(max_scale as f32)
+    }
+
+    /// Save the figure as SVG or as a PNG rasterized at the given scale,
+    /// returning the scale applied after clamping the PNG dimensions.
+    pub fn save(&self, path : &Path, format : ExportFormat, scale : f32) -> Result<f32> {
+        let svg = self.to_svg();
+        match format {
+            ExportFormat::Svg => {
+                std::fs::write(path, svg)?;
+                Ok(scale)
+            }
+            ExportFormat::Png => {
+                let scale = self.png_scale(scale);
+                let image = load_svg_bytes_with_size(svg.as_bytes(), FitTo::Zoom(scale)).map_err(Error::custom)?;
+                image::save_buffer(
+                    path,
+                    image.as_raw(),
+                    image.width() as u32,
+                    image.height() as u32,
+                    image::ColorType::Rgba8,
+                ).map_err(|err| Error::custom(err.to_string()))?;
+                Ok(scale)
+            }
+        }
+    }
+}
+
+/// SVG color and opacity of a (premultiplied) egui color
+fn svg_color(color : Color32) -> (String, f32) {
+    let alpha = color.a();
+    let unmultiply = |c : u8| if alpha == 0 { 0 } else { (c as u32 * 255 / alpha as u32).min(255) as u8 };
+    (
+        format!("#{:02x}{:02x}{:02x}", unmultiply(color.r()), unmultiply(color.g()), unmultiply(color.b())),
+        alpha as f32 / 255.0,
+    )
+}
+
+fn fill(color : Color32) -> String {
+    let (color, opacity) = svg_color(color);
+    format!(r#"fill="{color}" fill-opacity="{opacity:.3}""#)
+}
+
+fn stroke(color : Color32, width : f64) -> String {
+    let (color, opacity) = svg_color(color);
+    format!(r#"stroke="{color}" stroke-opacity="{opacity:.3}" stroke-width="{width:.2}""#)
+}
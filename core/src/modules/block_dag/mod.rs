@@ -0,0 +1,1112 @@
+use crate::imports::*;
+use chrono::DateTime;
+use kaspa_rpc_core::RpcBlock;
+
+cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        mod export;
+        use export::*;
+    }
+}
+
+use egui_plot::{
+    LineStyle,
+    Plot,
+    PlotPoints, Polygon, uniform_grid_spacer, Line, PlotBounds,
+    // Legend,
+    // Corner
+};
+
+#[derive(Clone)]
+pub struct Preset {
+    name : &'static str,
+    daa_range : f64,
+    daa_offset : f64,
+    spread : f64,
+    noise : f64,
+    block_scale : f64,
+}
+
+const PRESETS: &[Preset] = &[
+    Preset {
+        name : "Large (1 BPS)",
+        daa_range : 36.0,
+        daa_offset : 10.0,
+        spread : 10.0,
+        noise : 0.0,
+        block_scale : 1.0,
+    },
+    Preset {
+        name : "Medium Wide",
+        daa_range : 100.0,
+        daa_offset : 16.0,
+        spread : 36.0,
+        noise : 0.0,
+        block_scale : 1.0,
+    },
+    Preset {
+        name : "Medium Narrow",
+        daa_range : 80.0,
+        daa_offset : 16.0,
+        spread : 22.0,
+        noise : 0.0,
+        block_scale : 1.2,
+    },
+    Preset {
+        name : "Small (10 BPS)",
+        daa_range : 180.0,
+        daa_offset : 32.0,
+        spread : 36.0,
+        noise : 1.0,
+        block_scale : 1.4,
+    },
+];
+
+impl From<Network> for Preset {
+    fn from(network: Network) -> Self {
+        match network {
+            Network::Mainnet => PRESETS[0].clone(),
+            Network::Testnet10 => PRESETS[0].clone(),
+            Network::Testnet11 => PRESETS[3].clone(),
+        }
+    }
+}
+
+struct BlockInspector {
+    hash : KaspaHash,
+    block : Option<Arc<RpcBlock>>,
+    vspc : Option<bool>,
+    error : Option<String>,
+}
+
+pub struct BlockDag {
+    #[allow(dead_code)]
+    runtime: Runtime,
+    daa_cursor: f64,
+    last_daa_score : u64,
+    running : bool,
+    plot_bounds : PlotBounds,
+    bezier : bool,
+    parent_levels : usize,
+    parent_threshold : usize,
+    daa_offset : f64,
+    daa_range : f64,
+    block_scale : f64,
+    last_repaint : Instant,
+    settings: BlockDagGraphSettings,
+    background : Arc<AtomicBool>,
+    network : Network,
+    inspector : Option<BlockInspector>,
+    search : String,
+    center_on : Option<PlotPoint>,
+    replaying : bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    export : ExportSettings,
+}
+
+impl BlockDag {
+    pub fn new(runtime: Runtime) -> Self {
+
+        let preset = Preset::from(Network::Mainnet);
+        let settings = BlockDagGraphSettings::new(preset.spread);
+        runtime.block_dag_monitor_service().update_settings(settings.clone());
+
+        Self { 
+            runtime, 
+            daa_cursor : 0.0, 
+            last_daa_score : 0, 
+            running : false, 
+            plot_bounds : PlotBounds::NOTHING, 
+            bezier : true, 
+            daa_offset : preset.daa_offset,
+            daa_range : preset.daa_range,
+            block_scale : preset.block_scale,
+            last_repaint : Instant::now(),
+            parent_levels : 1,
+            parent_threshold : 200,
+            settings,
+            background : Arc::new(AtomicBool::new(false)),
+            network : Network::Mainnet,
+            inspector : None,
+            search : String::new(),
+            center_on : None,
+            replaying : false,
+            #[cfg(not(target_arch = "wasm32"))]
+            export : ExportSettings::default(),
+        }
+    }
+
+    pub fn background(&self) -> bool {
+        self.background.load(Ordering::SeqCst)
+    }
+
+    pub fn background_state(&self) -> Arc<AtomicBool> {
+        self.background.clone()
+    }
+
+    pub fn load_preset(&mut self, preset : &Preset) {
+        self.daa_range = preset.daa_range;
+        self.daa_offset = preset.daa_offset;
+        self.block_scale = preset.block_scale;
+        self.settings.y_dist = preset.spread;
+        self.settings.noise = preset.noise;
+    }
+
+    fn reset_state(&mut self) {
+        self.running = false;
+        self.daa_cursor = 0.0; 
+        self.last_daa_score = 0; 
+    }
+
+    /// Open the block in the inspector panel and fetch
+    /// its transactions and verbose data from the node.
+    fn inspect(&mut self, hash : KaspaHash, block : Option<Arc<RpcBlock>>, vspc : Option<bool>) {
+        self.inspector = Some(BlockInspector { hash, block, vspc, error : None });
+
+        let block_dag_monitor_service = self.runtime.block_dag_monitor_service().clone();
+        let block_result = Payload::<Result<Arc<RpcBlock>>>::new("block_dag_block_result");
+        spawn_with_result(&block_result, async move {
+            block_dag_monitor_service.fetch_block(hash).await
+        });
+    }
+
+    fn search(&mut self) {
+        let text = self.search.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        match KaspaHash::from_str(text) {
+            Ok(hash) => {
+                if let Some((block, point, vspc)) = self.runtime.block_dag_monitor_service().find_block(&hash) {
+                    self.center_on = Some(point);
+                    self.inspect(hash, Some(block), Some(vspc));
+                } else {
+                    runtime().notify(UserNotification::info(i18n("The block is not present in the visualized DAG")).short());
+                    self.inspect(hash, None, None);
+                }
+            }
+            Err(_) => {
+                runtime().error(i18n("Invalid block hash"));
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_recording_menu(&mut self, ui : &mut Ui) {
+        use crate::runtime::services::blockdag_monitor::RECORDING_FILE_EXTENSION;
+
+        let block_dag_monitor_service = self.runtime.block_dag_monitor_service().clone();
+
+        PopupPanel::new(PopupPanel::id(ui,"block_dag_recording"),|ui|{ ui.add(Label::new(format!("{} ⏷", i18n("Recording"))).sense(Sense::click())) }, |ui, close| {
+            ui.vertical(|ui| {
+                if block_dag_monitor_service.is_recording() {
+                    if ui.button(i18n("Stop Recording")).clicked() {
+                        match block_dag_monitor_service.stop_recording() {
+                            Ok(Some((path, records))) => {
+                                runtime().notify(UserNotification::success(format!("{} {} ({records} {})", i18n("Recording saved to"), path.display(), i18n("events"))).short());
+                            }
+                            Ok(None) => { }
+                            Err(err) => {
+                                runtime().error(err.to_string());
+                            }
+                        }
+                        *close = true;
+                    }
+                } else if ui.add_enabled(!self.replaying, Button::new(i18n("Start Recording..."))).clicked() {
+                    let path = rfd::FileDialog::new()
+                        .add_filter(i18n("BlockDAG Recording"), &[RECORDING_FILE_EXTENSION])
+                        .set_file_name(format!("blockdag-{}.{RECORDING_FILE_EXTENSION}", self.network))
+                        .save_file();
+                    if let Some(path) = path {
+                        if let Err(err) = block_dag_monitor_service.start_recording(&path) {
+                            runtime().error(err.to_string());
+                        }
+                    }
+                    *close = true;
+                }
+
+                ui.space();
+
+                if ui.add_enabled(!block_dag_monitor_service.is_recording(), Button::new(i18n("Replay Recording..."))).clicked() {
+                    let path = rfd::FileDialog::new()
+                        .add_filter(i18n("BlockDAG Recording"), &[RECORDING_FILE_EXTENSION])
+                        .pick_file();
+                    if let Some(path) = path {
+                        block_dag_monitor_service.start_replay(path, self.network);
+                    }
+                    *close = true;
+                }
+            });
+        })
+        .with_min_width(160.)
+        .with_caption(i18n("Recording"))
+        .with_close_button(true)
+        .build(ui);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_export_menu(&mut self, ui : &mut Ui) {
+        let background = ui.visuals().extreme_bg_color;
+
+        let export_result = Payload::<Result<(PathBuf, f32, f32)>>::new("block_dag_export_result");
+        if let Some(result) = export_result.take() {
+            match result {
+                Ok((path, requested_scale, scale)) => {
+                    if scale < requested_scale {
+                        runtime().notify(UserNotification::warning(format!("{} {:.2}x {} {}", i18n("BlockDAG image downscaled to"), scale, i18n("and exported to"), path.display())));
+                    } else {
+                        runtime().notify(UserNotification::success(format!("{} {}", i18n("BlockDAG exported to"), path.display())).short());
+                    }
+                }
+                Err(err) => {
+                    runtime().error(err.to_string());
+                }
+            }
+        }
+        let is_exporting = export_result.is_pending();
+
+        PopupPanel::new(PopupPanel::id(ui,"block_dag_export"),|ui|{ ui.add(Label::new(format!("{} ⏷", i18n("Export"))).sense(Sense::click())) }, |ui, close| {
+
+            if self.export.daa_from.is_empty() && self.export.daa_to.is_empty() && self.plot_bounds.is_valid() {
+                self.export.daa_from = (self.plot_bounds.min()[0].max(0.0) as u64).to_string();
+                self.export.daa_to = (self.plot_bounds.max()[0].max(0.0) as u64).to_string();
+            }
+
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.export.range, ExportRange::Viewport, i18n("Current View"));
+                ui.radio_value(&mut self.export.range, ExportRange::DaaRange, i18n("DAA Range"));
+            });
+
+            if self.export.range == ExportRange::DaaRange {
+                ui.horizontal(|ui| {
+                    ui.add(TextEdit::singleline(&mut self.export.daa_from).hint_text(i18n("From")).desired_width(100.));
+                    ui.label("-");
+                    ui.add(TextEdit::singleline(&mut self.export.daa_to).hint_text(i18n("To")).desired_width(100.));
+                });
+            }
+
+            ui.space();
+            ui.horizontal(|ui| {
+                for format in [ExportFormat::Svg, ExportFormat::Png] {
+                    ui.radio_value(&mut self.export.format, format, format.name());
+                }
+            });
+
+            if self.export.format == ExportFormat::Png {
+                ui.add(
+                    Slider::new(&mut self.export.scale, 1.0..=8.0)
+                        .clamp_to_range(true)
+                        .step_by(0.5)
+                        .suffix("x")
+                        .text(i18n("Resolution"))
+                );
+            }
+
+            ui.space();
+            if is_exporting {
+                ui.horizontal(|ui| {
+                    ui.add(egui::Spinner::new());
+                    ui.label(i18n("Exporting..."));
+                });
+            } else if ui.button(i18n("Export...")).clicked() {
+                if let Err(err) = self.export_figure(background, &export_result) {
+                    runtime().error(err.to_string());
+                }
+                *close = true;
+            }
+        })
+        .with_min_width(200.)
+        .with_caption(i18n("Export"))
+        .with_close_button(true)
+        .build(ui);
+    }
+
+    /// Export the current viewport or the selected DAA range. The figure
+    /// is rendered and saved on the blocking thread pool; the destination
+    /// file path, the requested and the applied scale are posted to
+    /// `export_result` once the export is complete.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_figure(&self, background : Color32, export_result : &Payload<Result<(PathBuf, f32, f32)>>) -> Result<()> {
+        let (daa_min, daa_max) = match self.export.range {
+            ExportRange::Viewport => {
+                if !self.plot_bounds.is_valid() {
+                    return Err(Error::custom(i18n("Nothing to export")));
+                }
+                (self.plot_bounds.min()[0], self.plot_bounds.max()[0])
+            }
+            ExportRange::DaaRange => {
+                let from = self.export.daa_from.trim().parse::<u64>()?;
+                let to = self.export.daa_to.trim().parse::<u64>()?;
+                if from >= to {
+                    return Err(Error::custom(i18n("Invalid DAA range")));
+                }
+                (from as f64, to as f64)
+            }
+        };
+
+        // include blocks just outside of the range to retain their edges
+        let daa_margin = (daa_max - daa_min).min(128.0).max(32.0);
+        let blocks = self.runtime.block_dag_monitor_service().settled_blocks(
+            ((daa_min - daa_margin).max(0.0) as u64)..=((daa_max + daa_margin).max(0.0) as u64)
+        );
+
+        let bounds = match self.export.range {
+            ExportRange::Viewport => self.plot_bounds,
+            ExportRange::DaaRange => {
+                let (y_min, y_max) = blocks.iter()
+                    .filter(|(_, point, _)| point.x >= daa_min && point.x <= daa_max)
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, point, _)| (min.min(point.y), max.max(point.y)));
+                if y_min > y_max {
+                    return Err(Error::custom(i18n("No blocks within the DAA range")));
+                }
+                let padding = 1.5 * self.block_scale + self.settings.y_dist;
+                PlotBounds::from_min_max([daa_min - 1.0, y_min - padding], [daa_max + 1.0, y_max + padding])
+            }
+        };
+
+        let figure = DagFigure {
+            blocks,
+            bounds,
+            block_scale : self.block_scale,
+            parent_levels : self.parent_levels,
+            parent_threshold : self.parent_threshold,
+            bezier : self.bezier,
+            show_vspc : self.settings.show_vspc,
+            background,
+        };
+
+        let format = self.export.format;
+        let extension = format.extension();
+        let Some(mut path) = rfd::FileDialog::new()
+            .add_filter(format.name(), &[extension])
+            .set_file_name(format!("blockdag-{}.{extension}", daa_min.max(0.0) as u64))
+            .save_file() else {
+            return Ok(());
+        };
+        path.set_extension(extension);
+
+        let requested_scale = self.export.scale;
+        spawn_with_result(export_result, async move {
+            tokio::task::spawn_blocking(move || {
+                let scale = figure.save(&path, format, requested_scale)?;
+                Ok((path, requested_scale, scale))
+            })
+            .await
+            .map_err(|err| Error::custom(err.to_string()))?
+        });
+
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_replay_controls(&mut self, ui : &mut Ui) {
+        let block_dag_monitor_service = self.runtime.block_dag_monitor_service().clone();
+        let replay = block_dag_monitor_service.replay();
+
+        let format_time = |millis : u64| {
+            let seconds = millis / 1000;
+            format!("{:02}:{:02}", seconds / 60, seconds % 60)
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(i18n("Replay")).color(theme_color().warning_color));
+            ui.space();
+
+            if replay.is_finished() {
+                ui.label(i18n("Finished"));
+            } else if replay.is_paused() {
+                if ui.button(i18n("Resume")).clicked() {
+                    replay.set_paused(false);
+                }
+            } else if ui.button(i18n("Pause")).clicked() {
+                replay.set_paused(true);
+            }
+
+            let mut speed = replay.speed();
+            ui.add(
+                Slider::new(&mut speed, 0.1..=20.0)
+                    .clamp_to_range(true)
+                    .logarithmic(true)
+                    .suffix("x")
+                    .text(i18n("Speed"))
+            );
+            if speed != replay.speed() {
+                replay.set_speed(speed);
+            }
+
+            let (position, duration) = replay.progress();
+            ui.label(format!("{} / {}", format_time(position), format_time(duration)));
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(i18n("Stop Replay")).clicked() {
+                    block_dag_monitor_service.stop_replay();
+                }
+            });
+        });
+        ui.separator();
+    }
+
+    fn render_inspector(&mut self, core: &mut Core, ui : &mut Ui) {
+        let block_result = Payload::<Result<Arc<RpcBlock>>>::new("block_dag_block_result");
+        if let Some(result) = block_result.take() {
+            if let Some(inspector) = self.inspector.as_mut() {
+                match result {
+                    Ok(block) => {
+                        if block.header.hash == inspector.hash {
+                            inspector.block = Some(block);
+                        }
+                    }
+                    Err(err) => {
+                        inspector.error = Some(err.to_string());
+                    }
+                }
+            }
+        }
+
+        let Some(inspector) = self.inspector.as_ref() else {
+            return;
+        };
+
+        let network_type = NetworkType::from(core.network());
+        let mut close = false;
+        let mut inspect = None;
+
+        SidePanel::right("block_dag_inspector")
+            .resizable(true)
+            .default_width(360.)
+            .min_width(280.)
+            .show_inside(ui, |ui| {
+
+                ui.horizontal(|ui| {
+                    ui.heading(i18n("Block"));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("✕").clicked() {
+                            close = true;
+                        }
+                    });
+                });
+                ui.separator();
+
+                let hash = inspector.hash.to_string();
+                let response = ui.add(Label::new(RichText::new(hash.as_str()).font(FontId::monospace(12.))).sense(Sense::click()))
+                    .on_hover_text_at_pointer(i18n("Click to copy the block hash to clipboard"));
+                if response.clicked() {
+                    ui.output_mut(|o| o.copied_text = hash.clone());
+                    runtime().notify_clipboard(i18n("Copied to clipboard"));
+                }
+                ui.space();
+
+                let Some(block) = inspector.block.as_ref() else {
+                    if let Some(err) = inspector.error.as_ref() {
+                        ui.label(RichText::new(err).color(theme_color().error_color));
+                    } else {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(i18n("Loading..."));
+                        });
+                    }
+                    return;
+                };
+
+                let header = &block.header;
+                let verbose_data = block.verbose_data.as_ref();
+                let vspc = verbose_data.map(|data| data.is_chain_block).or(inspector.vspc);
+
+                let mut hash_link = |ui : &mut Ui, hash : &KaspaHash| {
+                    if ui.link(RichText::new(format_partial_string(hash.to_string(), Some(12))).font(FontId::monospace(12.))).clicked() {
+                        inspect = Some(*hash);
+                    }
+                };
+
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+
+                        Grid::new("block_dag_inspector_header")
+                            .num_columns(2)
+                            .spacing([16.0, 4.0])
+                            .show(ui, |ui| {
+                                let timestamp = DateTime::<chrono::Utc>::from_timestamp((header.timestamp / 1000) as i64, 0)
+                                    .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                                    .unwrap_or_default();
+
+                                ui.label(i18n("VSPC"));
+                                match vspc {
+                                    Some(true) => ui.label(RichText::new(i18n("Yes")).color(theme_color().strong_color)),
+                                    Some(false) => ui.label(i18n("No")),
+                                    None => ui.label(i18n("Unknown")),
+                                };
+                                ui.end_row();
+                                ui.label(i18n("DAA Score"));
+                                ui.label(header.daa_score.separated_string());
+                                ui.end_row();
+                                ui.label(i18n("Blue Score"));
+                                ui.label(header.blue_score.separated_string());
+                                ui.end_row();
+                                ui.label(i18n("Timestamp"));
+                                ui.label(format!("{timestamp} ({} ms)", header.timestamp));
+                                ui.end_row();
+                                ui.label(i18n("Bits"));
+                                ui.label(format!("0x{:08x}", header.bits));
+                                ui.end_row();
+                                if let Some(verbose_data) = verbose_data {
+                                    ui.label(i18n("Difficulty"));
+                                    ui.label(format!("{:.2}", verbose_data.difficulty));
+                                    ui.end_row();
+                                }
+                                ui.label(i18n("Version"));
+                                ui.label(header.version.to_string());
+                                ui.end_row();
+                                ui.label(i18n("Nonce"));
+                                ui.label(header.nonce.to_string());
+                                ui.end_row();
+                                if let Some(verbose_data) = verbose_data {
+                                    ui.label(i18n("Selected Parent"));
+                                    hash_link(ui, &verbose_data.selected_parent_hash);
+                                    ui.end_row();
+                                }
+                                ui.label(i18n("Pruning Point"));
+                                hash_link(ui, &header.pruning_point);
+                                ui.end_row();
+                                ui.label(i18n("Merkle Root"));
+                                ui.label(RichText::new(format_partial_string(header.hash_merkle_root.to_string(), Some(12))).font(FontId::monospace(12.)));
+                                ui.end_row();
+                                ui.label(i18n("Accepted ID Merkle Root"));
+                                ui.label(RichText::new(format_partial_string(header.accepted_id_merkle_root.to_string(), Some(12))).font(FontId::monospace(12.)));
+                                ui.end_row();
+                                ui.label(i18n("UTXO Commitment"));
+                                ui.label(RichText::new(format_partial_string(header.utxo_commitment.to_string(), Some(12))).font(FontId::monospace(12.)));
+                                ui.end_row();
+                            });
+
+                        ui.space();
+
+                        CollapsingHeader::new(i18n("Parents by Level"))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for (level, parents) in header.parents_by_level.iter().enumerate() {
+                                    CollapsingHeader::new(format!("{} {level} ({})", i18n("Level"), parents.len()))
+                                        .id_source(("block_dag_inspector_parents", level))
+                                        .default_open(level == 0)
+                                        .show(ui, |ui| {
+                                            for parent in parents.iter() {
+                                                hash_link(ui, parent);
+                                            }
+                                        });
+                                }
+                            });
+
+                        if let Some(verbose_data) = verbose_data {
+                            CollapsingHeader::new(i18n("Merge Set"))
+                                .default_open(true)
+                                .show(ui, |ui| {
+                                    ui.label(format!("{} ({})", i18n("Blues"), verbose_data.merge_set_blues_hashes.len()));
+                                    for hash in verbose_data.merge_set_blues_hashes.iter() {
+                                        hash_link(ui, hash);
+                                    }
+                                    ui.space();
+                                    ui.label(format!("{} ({})", i18n("Reds"), verbose_data.merge_set_reds_hashes.len()));
+                                    for hash in verbose_data.merge_set_reds_hashes.iter() {
+                                        hash_link(ui, hash);
+                                    }
+                                });
+
+                            CollapsingHeader::new(format!("{} ({})", i18n("Children"), verbose_data.children_hashes.len()))
+                                .id_source("block_dag_inspector_children")
+                                .show(ui, |ui| {
+                                    for hash in verbose_data.children_hashes.iter() {
+                                        hash_link(ui, hash);
+                                    }
+                                });
+                        }
+
+                        CollapsingHeader::new(format!("{} ({})", i18n("Transactions"), block.transactions.len()))
+                            .id_source("block_dag_inspector_transactions")
+                            .show(ui, |ui| {
+                                for (index, transaction) in block.transactions.iter().enumerate() {
+                                    let id = transaction.verbose_data.as_ref().map(|data| data.transaction_id)
+                                        .or_else(|| verbose_data.and_then(|data| data.transaction_ids.get(index).cloned()))
+                                        .map(|id| format_partial_string(id.to_string(), Some(12)))
+                                        .unwrap_or_else(|| format!("#{index}"));
+                                    let total = transaction.outputs.iter().map(|output| output.value).sum::<u64>();
+
+                                    CollapsingHeader::new(RichText::new(id).font(FontId::monospace(12.)))
+                                        .id_source(("block_dag_inspector_transaction", index))
+                                        .show(ui, |ui| {
+                                            ui.label(format!("{}: {}  {}: {}", i18n("Inputs"), transaction.inputs.len(), i18n("Outputs"), transaction.outputs.len()));
+                                            ui.label(format!("{}: {}", i18n("Total Output"), sompi_to_kaspa_string_with_suffix(total, &network_type)));
+                                            for output in transaction.outputs.iter() {
+                                                let address = output.verbose_data.as_ref()
+                                                    .map(|data| format_address(&data.script_public_key_address, Some(8)))
+                                                    .unwrap_or_else(|| i18n("Unknown").to_string());
+                                                ui.label(RichText::new(format!("{address} {}", sompi_to_kaspa_string_with_suffix(output.value, &network_type))).font(FontId::monospace(12.)));
+                                            }
+                                        });
+                                }
+                            });
+                    });
+            });
+
+        if close {
+            self.inspector = None;
+        } else if let Some(hash) = inspect {
+            let (block, vspc) = self.runtime.block_dag_monitor_service().find_block(&hash)
+                .map(|(block, point, vspc)| {
+                    self.center_on = Some(point);
+                    (Some(block), Some(vspc))
+                })
+                .unwrap_or((None, None));
+            self.inspect(hash, block, vspc);
+        }
+    }
+
+}
+
+impl ModuleT for BlockDag {
+
+    fn style(&self) -> ModuleStyle {
+        ModuleStyle::Default
+    }
+
+    fn status_bar(&self, core: &mut Core, ui : &mut Ui) {
+        ui.separator();
+        if self.replaying {
+            ui.label(RichText::new(i18n("Replaying BlockDAG recording...")).color(theme_color().warning_color));
+        } else if !core.state().is_connected() {
+            ui.label(RichText::new(i18n("You must be connected to a node...")).color(theme_color().error_color));
+        } else if !core.state().is_synced() {
+            ui.label(RichText::new(i18n("Please wait for the node to sync...")).color(theme_color().warning_color));
+        } else {
+            ui.label(i18n("Click on a block to inspect it, double click on the graph to re-center..."));
+        }
+    }
+
+    fn render(
+        &mut self,
+        core: &mut Core,
+        _ctx: &egui::Context,
+        _frame: &mut eframe::Frame,
+        ui: &mut egui::Ui,
+    ) {
+        let theme_color = theme_color();
+
+        let y_dist = self.settings.y_dist;
+        let noise = self.settings.noise;
+        let vspc_center = self.settings.center_vspc;
+
+        if core.settings.node.network != self.network {
+            self.network = core.settings.node.network;
+            self.load_preset(&self.network.into());
+            runtime().block_dag_monitor_service().update_settings(self.settings.clone());
+        }
+
+        let replaying = self.runtime.block_dag_monitor_service().is_replaying();
+        if replaying != self.replaying {
+            self.replaying = replaying;
+            self.reset_state();
+            self.inspector = None;
+        }
+
+        ui.horizontal(|ui| {
+            ui.heading(i18n("Block DAG"));
+
+            ui.space();
+            let response = ui.add(TextEdit::singleline(&mut self.search)
+                .hint_text(i18n("Search by block hash..."))
+                .font(FontId::monospace(12.))
+                .desired_width(320.)
+            );
+            if (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))) || ui.button(i18n("Search")).clicked() {
+                self.search();
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                PopupPanel::new(PopupPanel::id(ui,"block_dag_settings"),|ui|{ ui.add(Label::new("Settings ⏷").sense(Sense::click())) }, |ui, _| {
+
+                    CollapsingHeader::new(i18n("Dimensions"))
+                        .open(Some(true))
+                        // .default_open(true)
+                        .show(ui, |ui| {
+                            ui.space();
+
+                            ui.space();
+                            ui.add(
+                                Slider::new(&mut self.daa_range, 1.0..=self.settings.graph_length_daa as f64)
+                                    .clamp_to_range(true)
+                                    .logarithmic(true)
+                                    .text(i18n("DAA Range"))
+                            );
+                            ui.space();
+                            ui.add(
+                                Slider::new(&mut self.daa_offset, 1.0..=50.0)
+                                    .clamp_to_range(true)
+                                    .text(i18n("DAA Offset"))
+                                    // .step_by(1.0)
+                            );
+                            ui.space();
+                            ui.add(
+                                Slider::new(&mut self.settings.y_dist, 1.0..=100.0)
+                                    .clamp_to_range(true)
+                                    .text(i18n("Spread"))
+                            );
+                            ui.space();
+                            ui.add(
+                                Slider::new(&mut self.settings.noise, 0.0..=10.0)
+                                    .clamp_to_range(true)
+                                    .text(i18n("Noise"))
+                            );
+                            ui.space();
+                            ui.add(
+                                Slider::new(&mut self.block_scale, 0.1..=2.5)
+                                    .clamp_to_range(true)
+                                    .logarithmic(true)
+                                    .text(i18n("Block Scale"))
+                            );
+                            ui.space();
+                        });
+
+                    CollapsingHeader::new(i18n("Parents"))
+                        .open(Some(true))
+                        // .default_open(true)
+                        .show(ui, |ui| {
+
+                            ui.space();
+                            
+                            ui.add(
+                                Slider::new(&mut self.parent_levels, 1..=50)
+                                    .clamp_to_range(true)
+                                    .text(i18n("Levels"))
+                                    .step_by(1.0)
+                            );
+                            ui.space();
+                            ui.add(
+                                Slider::new(&mut self.parent_threshold, 200..=1000)
+                                    .clamp_to_range(true)
+                                    .logarithmic(true)
+                                    .text(i18n("Threshold"))
+                            );
+                            ui.space();
+                        });
+
+                    ui.separator();
+                    ui.space();
+                    ui.horizontal_wrapped(|ui| {
+                        ui.checkbox(&mut self.settings.center_vspc, i18n("Center VSPC"));
+                        ui.space();
+                        ui.checkbox(&mut self.settings.show_vspc, i18n("Show VSPC"));
+                        ui.space();
+                        // ui.checkbox(&mut self.settings.reset_vspc, i18n("Reset VSPC"));
+                        // ui.space();
+                        ui.checkbox(&mut self.settings.show_grid, i18n("Show Grid"));
+                        ui.space();
+                        ui.checkbox(&mut self.settings.show_daa, i18n("Show DAA"));
+                        ui.space();
+                        ui.checkbox(&mut self.bezier, i18n("Bezier Curves"));
+                        ui.space();
+
+                        if core.settings.node.node_kind.is_local() {
+                            let background_flag = self.background.load(Ordering::SeqCst);
+                            let mut background_state = background_flag;
+                            ui.checkbox(&mut background_state, i18n("Track in the background"));
+                            if background_state != background_flag {
+                                self.background.store(background_state, Ordering::SeqCst);
+                            }
+                        }
+                    });
+                })
+                .with_min_width(240.)
+                .with_caption(i18n("Settings"))
+                .with_close_button(true)
+                .build(ui);
+
+                let response = ui
+                        .add(Label::new(RichText::new(format!("{} ⏷", i18n("Presets")))).sense(Sense::click()));
+                PopupPanel::new(
+                    PopupPanel::id(ui,"network_selector_popup"),
+                    |_ui| response,
+                    |ui, close| {
+                        set_menu_style(ui.style_mut());
+                        for preset in PRESETS {
+                            if ui.button(i18n(preset.name)).clicked() {
+                                self.load_preset(preset);
+                                *close = true;
+                            }
+                        }
+                })
+                .with_min_width(100.0)
+                .build(ui);
+
+                #[cfg(not(target_arch = "wasm32"))]
+                self.render_recording_menu(ui);
+
+                #[cfg(not(target_arch = "wasm32"))]
+                self.render_export_menu(ui);
+
+            });
+        });
+        ui.separator();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.replaying {
+            self.render_replay_controls(ui);
+        }
+
+        if y_dist != self.settings.y_dist || noise != self.settings.noise || vspc_center != self.settings.center_vspc {
+            runtime().block_dag_monitor_service().update_settings(self.settings.clone());
+        }
+
+
+        self.render_inspector(core, ui);
+
+        let mut reset_plot = false;
+        let current_daa_score = if self.replaying {
+            self.runtime.block_dag_monitor_service().replay_daa_score()
+        } else {
+            core.state().current_daa_score()
+        }.unwrap_or_default();
+        if self.last_daa_score != current_daa_score {
+
+            if !self.running {
+                self.running = true;
+                reset_plot = true;
+                self.daa_cursor = current_daa_score as f64 - 1.0;
+            }
+
+            self.last_daa_score = current_daa_score;
+        }
+
+        let delta = 0.025;
+        let daa_diff = current_daa_score as f64 - self.daa_cursor;
+        let step = daa_diff * delta;
+        let step = (1.0 + step).powf(2.0) - 1.0;
+        self.daa_cursor += step;
+        
+        let graph_width = ui.available_width();
+        let graph_height = ui.available_height();
+        let default_daa_max = self.daa_cursor + self.daa_offset;
+        let default_daa_min = default_daa_max - self.daa_range;
+        let default_daa_range = default_daa_max - default_daa_min;
+        let pixels_per_daa = graph_width as f64 / default_daa_range;
+
+        let mut plot = Plot::new("block_dag")
+            .width(graph_width)
+            .height(graph_height)
+            .include_x(default_daa_max)
+            .include_x(default_daa_min)
+            .include_y(15.)
+            .include_y(-15.)
+            .data_aspect(0.2)
+            .y_axis_width(0)
+            .show_axes([self.settings.show_daa, false])
+            .show_grid(self.settings.show_grid)
+            .allow_drag([true, true])
+            .allow_scroll(true)
+            .allow_double_click_reset(true)
+            .x_axis_formatter(move |x, _size, _range| {
+                format!("{} DAA", x.trunc().separated_string())
+            })
+            .x_grid_spacer(
+                uniform_grid_spacer(move |input| {
+                    let (start,stop) = input.bounds;
+                    let d = (stop - start) / 5.;
+                    let mut v = 10.;
+                    while v < d {
+                        v *= 2.;
+                    }
+                    [v,v*10.,v*100.]
+                })
+            )
+            .label_formatter(move |name, point| {
+                let PlotPoint { x, y: _ } = point;
+                format!("{name}\n{} DAA", x.trunc().separated_string())
+            })                        
+            ;
+
+        // kick it into gear when starting up
+        if reset_plot {
+            plot = plot.auto_bounds_x().auto_bounds_y();
+            plot = plot.reset();
+        }
+
+        let mut graph_settled = true;
+        let mut lines_parent = Vec::new();
+        let mut lines_vspc = Vec::new();
+
+        let daa_range = self.plot_bounds.max()[0] - self.plot_bounds.min()[0];
+        let daa_margin = daa_range.min(128.0).max(32.0);
+        let daa_min = (self.plot_bounds.min()[0] - daa_margin).max(0.0) as u64;
+        let daa_max = (self.plot_bounds.max()[0] + daa_margin).max(0.0) as u64;
+        
+        let blocks = if let Ok(mut daa_buckets) = self.runtime.block_dag_monitor_service().chain.lock() {
+            daa_buckets.iter_mut().filter_map(|(daa_score,bucket)| {
+                (*daa_score > daa_min && *daa_score < daa_max).then_some(bucket)
+            }).flat_map(DaaBucket::render).collect::<Vec<_>>()
+        } else {
+            return;
+        };
+
+        // let separators = if let Ok(separators) = self.runtime.block_dag_monitor_service().separators.lock() {
+        //     separators.iter().filter_map(|daa_score| {
+        //         (*daa_score > daa_min || *daa_score < daa_max).then_some(*daa_score)
+        //     }).collect::<Vec<_>>()
+        // } else {
+        //     return;
+        // };
+
+        let parent_levels = self.parent_levels.max(1);
+        let block_map : AHashMap<KaspaHash,(PlotPoint,bool)> = blocks.clone().into_iter().map(|(block, plot_point,vspc, _)|(block.header.hash,(plot_point,vspc))).collect();
+        let new_blocks = self.runtime.block_dag_monitor_service().new_blocks().clone();
+        let selected = self.inspector.as_ref().map(|inspector| inspector.hash);
+        let block_size = 1.5 * self.block_scale;
+        let polygons = blocks.iter().map(|(block, point, current_vspc, block_settled)| {
+            if !block_settled {
+                graph_settled = false;
+            }
+
+            let PlotPoint { x, y } = *point;
+
+            for (level,parent_level) in block.header.parents_by_level.iter().enumerate() {
+                if level >= parent_levels {
+                    break;
+                }
+                // for parent_hash in block.header.parents_by_level.iter().flatten() {
+                for parent_hash in parent_level.iter() {
+                    if let Some(parent_point) = block_map.get(parent_hash) {
+                        let (PlotPoint { x: parent_x, y: parent_y }, parent_vspc) = *parent_point;
+                        let x_len = (x - parent_x).abs();
+                        
+                        if x_len > self.parent_threshold as f64 {
+                            continue;
+                        }
+
+                        let points = if self.bezier {
+                            // x dist is sufficient... (let's save some cycles)
+                            let line_steps = (x_len * pixels_per_daa * 0.3) as usize;
+                            bezier(x,y,parent_x,parent_y,line_steps,0.6) 
+                        } else {
+                            [
+                                [x,y],
+                                [parent_x, parent_y],
+                            ].into_iter().map(|pt|pt.into()).collect::<Vec<_>>()
+                        };
+                        if self.settings.show_vspc && level == 0 && *current_vspc && parent_vspc {
+                            lines_vspc.push(Line::new(PlotPoints::Owned(points)).color(theme_color.block_dag_vspc_connect_color).style(LineStyle::Solid).width(3.0));
+                        } else {
+                            lines_parent.push(Line::new(PlotPoints::Owned(points)).color(theme_color.block_dag_parent_connect_color).style(LineStyle::Solid));
+                        }
+                    }
+                }
+            }
+
+            let d = block_size;
+            let points: PlotPoints = [
+                [x+d*0.2, y+d],
+                [x-d*0.2, y+d],
+                [x-d*0.2, y-d],
+                [x+d*0.2, y-d],
+            ].to_vec().into();
+        
+            let fill_color = if new_blocks.contains(&block.header.hash) {
+                theme_color.block_dag_new_block_fill_color
+            } else {
+                theme_color.block_dag_block_fill_color
+            };
+
+            let stroke = if selected == Some(block.header.hash) {
+                Stroke::new(3.0, theme_color.block_dag_vspc_connect_color)
+            } else {
+                Stroke::new(1.0, theme_color.block_dag_block_stroke_color)
+            };
+
+            Polygon::new(points)
+                .name(block.header.hash.to_string())
+                .fill_color(fill_color)
+                .stroke(stroke)
+                .style(LineStyle::Solid)
+
+            
+        }).collect::<Vec<_>>();
+
+        // let lines_separators = separators.iter().map(|daa_score| {
+        //     let x = *daa_score as f64;
+        //     let points: PlotPoints = [
+        //         [x, 0.0 - y_dist],
+        //         [x, 0.0 + y_dist],
+        //     ].to_vec().into();
+        //     Line::new(points).color(theme_color.block_dag_separator_color).style(LineStyle::Dotted { spacing: 0.75 })
+        // }).collect::<Vec<_>>();
+
+        let center_on = self.center_on.take().map(|PlotPoint { x, y }| {
+            let (width, height) = if self.plot_bounds.is_valid() {
+                (self.plot_bounds.width(), self.plot_bounds.height())
+            } else {
+                (self.daa_range, 30.0)
+            };
+            PlotBounds::from_min_max([x - width * 0.5, y - height * 0.5], [x + width * 0.5, y + height * 0.5])
+        });
+
+        let plot_response = plot.show(ui, |plot_ui| {
+            if let Some(bounds) = center_on {
+                plot_ui.set_plot_bounds(bounds);
+            }
+            // lines_separators.into_iter().for_each(|line| {
+            //     plot_ui.line(line);
+            // });
+            lines_parent.into_iter().for_each(|line| {
+                plot_ui.line(line);
+            });
+            lines_vspc.into_iter().for_each(|line| {
+                plot_ui.line(line);
+            });
+            polygons.into_iter().for_each(|polygon| {
+                plot_ui.polygon(polygon);
+            });
+        });
+
+        if daa_diff > 0.001 || !graph_settled {
+            runtime().request_repaint();
+        } 
+
+        if plot_response.response.clicked() {
+            if let Some(position) = plot_response.response.interact_pointer_pos() {
+                let PlotPoint { x, y } = plot_response.transform.value_from_position(position);
+                let clicked = blocks.iter().find(|(_, point, _, _)| {
+                    (point.x - x).abs() <= block_size * 0.2 && (point.y - y).abs() <= block_size
+                });
+                if let Some((block, _, vspc, _)) = clicked {
+                    self.inspect(block.header.hash, Some(block.clone()), Some(*vspc));
+                }
+            }
+        }
+
+        self.plot_bounds = *plot_response.transform.bounds();
+        self.last_repaint = Instant::now();
+
+    }
+
+    fn activate(&mut self, core: &mut Core) {
+        let block_dag_monitor_service = crate::runtime::runtime().block_dag_monitor_service().clone();
+        block_dag_monitor_service.enable(core.state().current_daa_score().map(|score|score - 2));
+        block_dag_monitor_service.activate(true);
+    }
+
+    fn deactivate(&mut self, core: &mut Core) {
+        if !self.background() {
+            let block_dag_monitor_service = crate::runtime::runtime().block_dag_monitor_service().clone();
+            self.running = false;
+            block_dag_monitor_service.disable(core.state().current_daa_score());
+            block_dag_monitor_service.activate(false);
+        }
+    }
+
+    fn disconnect(&mut self, _core: &mut Core) {
+        self.reset_state();
+    }
+
+    fn show(&mut self, _core: &mut Core) {
+        self.reset_state();
+    }
+
+}
+
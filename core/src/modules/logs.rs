@@ -1,16 +1,224 @@
 use crate::imports::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::runtime::services::kaspa::{logs::Log, DaemonCrash};
+
+/// Log levels displayed by the [`Logs`] module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LogLevels {
+    info: bool,
+    warning: bool,
+    error: bool,
+    debug: bool,
+    trace: bool,
+    processed: bool,
+}
+
+impl Default for LogLevels {
+    fn default() -> Self {
+        Self {
+            info: true,
+            warning: true,
+            error: true,
+            debug: true,
+            trace: true,
+            processed: true,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl LogLevels {
+    fn matches(&self, log: &Log) -> bool {
+        match log {
+            Log::Info(_) => self.info,
+            Log::Warning(_) => self.warning,
+            Log::Error(_) => self.error,
+            Log::Debug(_) => self.debug,
+            Log::Trace(_) => self.trace,
+            Log::Processed(_) => self.processed,
+        }
+    }
+}
+
+/// Filtered log lines, recomputed only when the log buffer
+/// (identified by its generation) or the filter changes.
+#[cfg(not(target_arch = "wasm32"))]
+struct LogView {
+    /// Log buffer generation, `None` for the paused log snapshot
+    generation: Option<u64>,
+    levels: LogLevels,
+    search: String,
+    logs: Arc<Vec<Log>>,
+}
 
 pub struct Logs {
     #[allow(dead_code)]
     runtime: Runtime,
+    #[allow(dead_code)]
+    confirm_reset: bool,
+    #[allow(dead_code)]
+    levels: LogLevels,
+    #[allow(dead_code)]
+    search: String,
+    #[allow(dead_code)]
+    follow: bool,
+    /// Log snapshot displayed while the log view is paused
+    #[cfg(not(target_arch = "wasm32"))]
+    paused: Option<Vec<Log>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    view: Option<LogView>,
 }
 
 impl Logs {
     pub fn new(runtime: Runtime) -> Self {
         Self {
             runtime,
+            confirm_reset: false,
+            levels: LogLevels::default(),
+            search: String::default(),
+            follow: true,
+            #[cfg(not(target_arch = "wasm32"))]
+            paused: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            view: None,
         }
     }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn filtered_logs(&mut self) -> Arc<Vec<Log>> {
+        let generation = self.paused.is_none().then(|| self.runtime.kaspa_service().logs_generation());
+        if let Some(view) = self.view.as_ref() {
+            if view.generation == generation && view.levels == self.levels && view.search == self.search {
+                return view.logs.clone();
+            }
+        }
+
+        let search = self.search.trim().to_lowercase();
+        let filter = |log: &&Log| {
+            self.levels.matches(log)
+                && (search.is_empty() || log.text().to_lowercase().contains(search.as_str()))
+        };
+
+        let logs = if let Some(logs) = self.paused.as_ref() {
+            logs.iter().filter(filter).cloned().collect()
+        } else {
+            self.runtime.kaspa_service().logs().iter().filter(filter).cloned().collect()
+        };
+
+        let logs = Arc::new(logs);
+        self.view = Some(LogView { generation, levels: self.levels, search: self.search.clone(), logs: logs.clone() });
+        logs
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_toolbar(&mut self, ui: &mut Ui, logs: &[Log]) {
+        use egui_phosphor::light::{CLIPBOARD_TEXT, MAGNIFYING_GLASS, PAUSE, PLAY};
+
+        ui.horizontal_wrapped(|ui| {
+            let LogLevels { info, warning, error, debug, trace, processed } = &mut self.levels;
+            ui.checkbox(error, i18n("Error"));
+            ui.checkbox(warning, i18n("Warning"));
+            ui.checkbox(info, i18n("Info"));
+            ui.checkbox(processed, i18n("Processed"));
+            ui.checkbox(debug, i18n("Debug"));
+            ui.checkbox(trace, i18n("Trace"));
+
+            ui.separator();
+            ui.label(RichText::new(MAGNIFYING_GLASS).size(16.));
+            ui.add(TextEdit::singleline(&mut self.search).hint_text(i18n("Search...")).desired_width(160.));
+
+            ui.separator();
+            if self.paused.is_some() {
+                if ui.button(format!("{PLAY} {}", i18n("Resume"))).clicked() {
+                    self.paused = None;
+                    self.view = None;
+                }
+            } else if ui.button(format!("{PAUSE} {}", i18n("Pause"))).clicked() {
+                self.paused = Some(self.runtime.kaspa_service().logs().clone());
+                self.view = None;
+            }
+            ui.checkbox(&mut self.follow, i18n("Follow"));
+
+            ui.separator();
+            if ui.button(CLIPBOARD_TEXT).on_hover_text_at_pointer(i18n("Copy logs to clipboard")).clicked() {
+                let logs = logs.iter().map(|log| log.to_string()).collect::<Vec<String>>().join("\n");
+                ui.output_mut(|o| o.copied_text = logs);
+                runtime().notify_clipboard(i18n("Copied to clipboard"));
+            }
+
+            if ui.button(i18n("Export")).on_hover_text_at_pointer(i18n("Export logs of the current session")).clicked() {
+                if let Some(target) = rfd::FileDialog::new().set_file_name("kaspad.log").add_filter("Log", &["log", "txt"]).save_file() {
+                    match self.runtime.kaspa_service().export_logs(&target) {
+                        Ok(()) => runtime().notify(UserNotification::success(format!("{} {}", i18n("Logs exported to"), target.display()))),
+                        Err(err) => runtime().error(format!("{} {err}", i18n("Unable to export logs:"))),
+                    }
+                }
+            }
+
+            if let Some(folder) = self.runtime.kaspa_service().log_folder() {
+                if ui.button(i18n("Open Log Folder")).clicked() {
+                    self.runtime.kaspa_service().flush_logs();
+                    if let Err(err) = open::that(&folder) {
+                        runtime().error(format!("Error opening folder: {:?}", err));
+                    }
+                }
+            }
+        });
+
+        ui.separator();
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_crash_report(&mut self, core: &mut Core, ui: &mut Ui, crash: &DaemonCrash) {
+        let color = if crash.is_final() { theme_color().error_color } else { theme_color().warning_color };
+        ui.colored_label(color, format!("{} {} ({} {})", i18n("Kaspa node has exited unexpectedly:"), crash.status, i18n("crash count:"), crash.count));
+
+        CollapsingHeader::new(i18n("Crash Log"))
+            .id_source("node_crash_log")
+            .default_open(false)
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .id_source("node_crash_log_scroll")
+                    .max_height(200.0)
+                    .auto_shrink([false, true])
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in crash.log.iter() {
+                            ui.label(RichText::from(&Log::from(line.as_str())));
+                        }
+                    });
+            });
+
+        if crash.is_final() {
+            ui.label(i18n("The node has failed repeatedly and will not be restarted automatically. If the node database is damaged, resetting the database will cause the node to resync from the network."));
+
+            if self.confirm_reset {
+                ui.colored_label(theme_color().alert_color, i18n("Please Confirm Database Reset"));
+                if let Some(response) = ui.confirm_medium_apply_cancel(Align::Min) {
+                    if matches!(response, Confirm::Ack) {
+                        if let Err(err) = self.runtime.kaspa_service().resync(&core.settings.node) {
+                            runtime().error(format!("Unable to reset the node database: {err}"));
+                        }
+                    }
+                    self.confirm_reset = false;
+                }
+            } else {
+                ui.horizontal_wrapped(|ui| {
+                    if ui.medium_button(i18n("Reset Database")).clicked() {
+                        self.confirm_reset = true;
+                    }
+                    if ui.medium_button(i18n("Restart Node")).clicked() {
+                        self.runtime.kaspa_service().update_services(&core.settings.node, None);
+                    }
+                    if ui.medium_button(i18n("Dismiss")).clicked() {
+                        self.runtime.kaspa_service().clear_daemon_crash();
+                    }
+                });
+            }
+        }
+
+        ui.separator();
+    }
 }
 
 impl ModuleT for Logs {
@@ -22,35 +230,31 @@ impl ModuleT for Logs {
         _frame: &mut eframe::Frame,
         ui: &mut egui::Ui,
     ) {
-        use egui_phosphor::light::CLIPBOARD_TEXT;
-
-        let available_width = ui.available_width();
-
         #[cfg(not(target_arch = "wasm32"))]
-        egui::ScrollArea::vertical()
-            .id_source("node_logs")
-            .auto_shrink([false; 2])
-            .stick_to_bottom(true)
-            .show(ui, |ui| {
+        if let Some(crash) = self.runtime.kaspa_service().daemon_crash() {
+            self.render_crash_report(core, ui, &crash);
+        }
 
-                for log in self.runtime.kaspa_service().logs().iter() {
-                    ui.label(RichText::from(log));
-                }
-            });
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let logs = self.filtered_logs();
+            self.render_toolbar(ui, &logs);
 
-        let copy_to_clipboard = Button::new(RichText::new(format!(" {CLIPBOARD_TEXT} ")).size(20.));
+            egui::ScrollArea::vertical()
+                .id_source("node_logs")
+                .auto_shrink([false; 2])
+                .stick_to_bottom(self.follow)
+                .show(ui, |ui| {
 
-        let button_rect = Rect::from_min_size(
-            pos2(available_width - 48.0, core.device().top_offset() + 32.0),
-            vec2(38.0, 20.0),
-        );
+                    for log in logs.iter() {
+                        ui.label(RichText::from(log));
+                    }
+                });
+        }
 
-        if ui.put(button_rect, copy_to_clipboard)
-            .on_hover_text_at_pointer(i18n("Copy logs to clipboard"))
-            .clicked() {
-                let logs = self.runtime.kaspa_service().logs().iter().map(|log| log.to_string()).collect::<Vec<String>>().join("\n");
-                ui.output_mut(|o| o.copied_text = logs);
-                runtime().notify_clipboard(i18n("Copied to clipboard"));
-            }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = (core, ui);
+        }
     }
 }
@@ -0,0 +1,282 @@
+use crate::imports::*;
+use crate::runtime::services::mempool_monitor::{MempoolEntry, MempoolFilter, MempoolSort};
+use kaspa_rpc_core::RpcTransactionId;
+
+/// Maximum number of entries displayed in the mempool table
+const MAX_DISPLAYED_ENTRIES : usize = 500;
+
+pub struct Mempool {
+    #[allow(dead_code)]
+    runtime: Runtime,
+    filter : MempoolFilter,
+    selected : Option<RpcTransactionId>,
+}
+
+impl Mempool {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            runtime,
+            filter : MempoolFilter::default(),
+            selected : None,
+        }
+    }
+
+    fn render_sort_header(&mut self, ui : &mut Ui, column : MempoolSort, title : &str) {
+        let text = if self.filter.sort == column {
+            format!("{title} {}", if self.filter.descending { "⏷" } else { "⏶" })
+        } else {
+            title.to_string()
+        };
+
+        if ui.add(Label::new(RichText::new(text).strong()).sense(Sense::click())).clicked() {
+            if self.filter.sort == column {
+                self.filter.descending = !self.filter.descending;
+            } else {
+                self.filter.sort = column;
+                self.filter.descending = true;
+            }
+            self.update_filter();
+        }
+    }
+
+    fn update_filter(&self) {
+        self.runtime.mempool_monitor_service().set_filter(self.filter.clone());
+    }
+
+    fn render_details(&mut self, ui : &mut Ui, entry : &MempoolEntry, network_type : &NetworkType) {
+        let transaction = &entry.entry.transaction;
+
+        ui.horizontal(|ui| {
+            ui.heading(i18n("Transaction"));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("✕").clicked() {
+                    self.selected = None;
+                }
+            });
+        });
+        ui.separator();
+
+        let id = entry.id.to_string();
+        let response = ui.add(Label::new(RichText::new(id.as_str()).font(FontId::monospace(12.))).sense(Sense::click()))
+            .on_hover_text_at_pointer(i18n("Click to copy the transaction id to clipboard"));
+        if response.clicked() {
+            ui.output_mut(|o| o.copied_text = id.clone());
+            runtime().notify_clipboard(i18n("Copied to clipboard"));
+        }
+        ui.space();
+
+        egui::ScrollArea::vertical()
+            .id_source("mempool_transaction_details")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+
+                Grid::new("mempool_transaction_grid")
+                    .num_columns(2)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(i18n("Fee"));
+                        ui.label(sompi_to_kaspa_string_with_suffix(entry.entry.fee, network_type));
+                        ui.end_row();
+                        ui.label(i18n("Mass"));
+                        ui.label(entry.mass.separated_string());
+                        ui.end_row();
+                        ui.label(i18n("Fee Rate"));
+                        ui.label(format!("{:.2} {}", entry.fee_rate, i18n("SOMPI/g")));
+                        ui.end_row();
+                        ui.label(i18n("Orphan"));
+                        ui.label(if entry.entry.is_orphan { i18n("Yes") } else { i18n("No") });
+                        ui.end_row();
+                        ui.label(i18n("Age"));
+                        ui.label(format_age(unixtime_as_millis_f64() - entry.first_seen));
+                        ui.end_row();
+                        ui.label(i18n("Version"));
+                        ui.label(transaction.version.to_string());
+                        ui.end_row();
+                        ui.label(i18n("Lock Time"));
+                        ui.label(transaction.lock_time.to_string());
+                        ui.end_row();
+                    });
+
+                ui.space();
+
+                CollapsingHeader::new(format!("{} ({})", i18n("Inputs"), transaction.inputs.len()))
+                    .id_source("mempool_transaction_inputs")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for input in transaction.inputs.iter() {
+                            let outpoint = &input.previous_outpoint;
+                            ui.label(RichText::new(format!("{}:{}", format_partial_string(outpoint.transaction_id.to_string(), Some(12)), outpoint.index)).font(FontId::monospace(12.)));
+                        }
+                    });
+
+                CollapsingHeader::new(format!("{} ({})", i18n("Outputs"), transaction.outputs.len()))
+                    .id_source("mempool_transaction_outputs")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for output in transaction.outputs.iter() {
+                            let address = output.verbose_data.as_ref()
+                                .map(|data| format_address(&data.script_public_key_address, Some(8)))
+                                .unwrap_or_else(|| i18n("Unknown").to_string());
+                            ui.label(RichText::new(format!("{address} {}", sompi_to_kaspa_string_with_suffix(output.value, network_type))).font(FontId::monospace(12.)));
+                        }
+                    });
+            });
+    }
+}
+
+impl ModuleT for Mempool {
+
+    fn style(&self) -> ModuleStyle {
+        ModuleStyle::Default
+    }
+
+    fn render(
+        &mut self,
+        core: &mut Core,
+        _ctx: &egui::Context,
+        _frame: &mut eframe::Frame,
+        ui: &mut egui::Ui,
+    ) {
+
+        ui.horizontal(|ui| {
+            ui.heading(i18n("Mempool"));
+            ui.space();
+            let search = ui.add(TextEdit::singleline(&mut self.filter.search)
+                .hint_text(i18n("Search by address or transaction id..."))
+                .desired_width(320.)
+            );
+            let show_orphans = ui.checkbox(&mut self.filter.show_orphans, i18n("Show orphans"));
+            let wallet_only = ui.checkbox(&mut self.filter.wallet_only, i18n("Wallet transactions only"));
+            if search.changed() || show_orphans.changed() || wallet_only.changed() {
+                self.update_filter();
+            }
+        });
+        ui.separator();
+
+        if !core.state().is_connected() {
+            ui.label(i18n("Not connected"));
+            return;
+        }
+
+        let Some(view) = self.runtime.mempool_monitor_service().view() else {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(i18n("Loading mempool entries..."));
+            });
+            return;
+        };
+
+        let network_type = NetworkType::from(core.network());
+
+        ui.horizontal_wrapped(|ui| {
+            let color = theme_color().node_data_color;
+            ui.label(i18n("Transactions:"));
+            ui.colored_label(color, view.entries.len().separated_string());
+            ui.label(i18n("Orphans:"));
+            ui.colored_label(color, view.orphans.separated_string());
+            ui.label(i18n("Total Fees:"));
+            ui.colored_label(color, sompi_to_kaspa_string_with_suffix(view.total_fees, &network_type));
+            ui.label(i18n("Wallet:"));
+            ui.colored_label(color, view.wallet.separated_string());
+        });
+        ui.space();
+
+        if let Some(selected) = self.selected {
+            if let Some(entry) = view.get(&selected) {
+                SidePanel::right("mempool_transaction")
+                    .resizable(true)
+                    .default_width(360.)
+                    .min_width(280.)
+                    .show_inside(ui, |ui| {
+                        self.render_details(ui, entry, &network_type);
+                    });
+            }
+        }
+
+        if view.visible.is_empty() {
+            ui.label(i18n("No matching transactions"));
+            return;
+        }
+
+        if view.visible.len() > MAX_DISPLAYED_ENTRIES {
+            ui.label(format!("{} {} / {}", i18n("Showing"), MAX_DISPLAYED_ENTRIES, view.visible.len().separated_string()));
+        }
+
+        let now = unixtime_as_millis_f64();
+        let mut selected = None;
+
+        egui::ScrollArea::vertical()
+            .id_source("mempool_entries")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                Grid::new("mempool_entries_grid")
+                    .num_columns(8)
+                    .striped(true)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(i18n("Transaction ID")).strong());
+                        self.render_sort_header(ui, MempoolSort::Fee, i18n("Fee"));
+                        self.render_sort_header(ui, MempoolSort::Mass, i18n("Mass"));
+                        self.render_sort_header(ui, MempoolSort::FeeRate, i18n("Fee Rate"));
+                        ui.label(RichText::new(i18n("Inputs")).strong());
+                        ui.label(RichText::new(i18n("Outputs")).strong());
+                        ui.label(RichText::new(i18n("Orphan")).strong());
+                        self.render_sort_header(ui, MempoolSort::Age, i18n("Age"));
+                        ui.end_row();
+
+                        for entry in view.visible().take(MAX_DISPLAYED_ENTRIES) {
+                            let mut id = RichText::new(format_partial_string(entry.id.to_string(), Some(12))).font(FontId::monospace(12.));
+                            if entry.is_wallet {
+                                id = id.color(theme_color().selection_text_color).background_color(theme_color().selection_background_color);
+                            }
+                            if ui.add(Label::new(id).sense(Sense::click())).clicked() {
+                                selected = Some(entry.id);
+                            }
+
+                            ui.label(sompi_to_kaspa_string_with_suffix(entry.entry.fee, &network_type));
+                            ui.label(entry.mass.separated_string());
+                            ui.label(format!("{:.2}", entry.fee_rate));
+                            ui.label(entry.entry.transaction.inputs.len().to_string());
+                            ui.label(entry.entry.transaction.outputs.len().to_string());
+                            if entry.entry.is_orphan {
+                                ui.colored_label(theme_color().warning_color, i18n("Yes"));
+                            } else {
+                                ui.label(i18n("No"));
+                            }
+                            ui.label(format_age(now - entry.first_seen));
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if selected.is_some() {
+            self.selected = selected;
+        }
+    }
+
+    fn activate(&mut self, _core: &mut Core) {
+        self.update_filter();
+        self.runtime.mempool_monitor_service().enable();
+    }
+
+    fn deactivate(&mut self, _core: &mut Core) {
+        self.runtime.mempool_monitor_service().disable();
+    }
+
+    fn disconnect(&mut self, _core: &mut Core) {
+        self.selected = None;
+    }
+
+}
+
+/// Time since the transaction was first observed by the mempool monitor
+fn format_age(millis : f64) -> String {
+    let seconds = (millis.max(0.0) / 1000.0) as u64;
+    if seconds < 60 {
+        format!("{seconds}s")
+    } else if seconds < 60 * 60 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}h {:02}m", seconds / 3600, (seconds / 60) % 60)
+    }
+}
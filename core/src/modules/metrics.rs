@@ -1,9 +1,11 @@
 use crate::imports::*;
-use crate::runtime::services::metrics_monitor::MAX_METRICS_SAMPLES;
+use crate::runtime::services::metrics_monitor::{MetricsHistoryData, MAX_METRICS_SAMPLES};
 use egui_extras::{StripBuilder, Size};
 use kaspa_metrics_core::{Metric,MetricGroup, MetricsSnapshot};
 use chrono::DateTime;
 use egui_plot::{
+    AxisHints,
+    HPlacement,
     Legend,
     Line,
     LineStyle,
@@ -14,14 +16,74 @@ use egui_plot::{
 const METRICS_SAMPLES_START : isize = -(MAX_METRICS_SAMPLES as isize);
 const MIN_RANGE : isize = 15;
 
+/// Line colors of metrics overlaid on a dashboard panel
+const OVERLAY_COLORS : [Color32; 4] = [
+    Color32::from_rgb(255, 200, 60),
+    Color32::from_rgb(90, 200, 255),
+    Color32::from_rgb(120, 220, 120),
+    Color32::from_rgb(255, 120, 160),
+];
+
+/// Linear mapping of overlaid metric values onto the value range
+/// of the primary (left axis) metric of a dashboard panel.
+#[derive(Clone, Copy)]
+struct PanelScale {
+    from : f64,
+    to : f64,
+    factor : f64,
+}
+
+impl PanelScale {
+    fn identity() -> Self {
+        Self { from : 0.0, to : 0.0, factor : 1.0 }
+    }
+
+    fn new(source : (f64, f64), target : (f64, f64)) -> Self {
+        Self { from : source.0, to : target.0, factor : (target.1 - target.0) / (source.1 - source.0) }
+    }
+
+    fn apply(&self, y : f64) -> f64 {
+        self.to + (y - self.from) * self.factor
+    }
+
+    fn invert(&self, y : f64) -> f64 {
+        self.from + (y - self.to) / self.factor
+    }
+}
+
+/// Value range of the plot data; a flat or empty series yields a unit range.
+fn value_range(data : &[PlotPoint]) -> (f64, f64) {
+    let (min, max) = data.iter()
+        .map(|point| point.y)
+        .filter(|y| y.is_finite())
+        .fold((f64::MAX, f64::MIN), |(min, max), y| (min.min(y), max.max(y)));
+    if min > max {
+        (0.0, 1.0)
+    } else if min == max {
+        (min, min + 1.0)
+    } else {
+        (min, max)
+    }
+}
+
+enum DashboardAction {
+    MovePanel(usize, isize),
+    RemovePanel(usize),
+    AddMetric(usize, Metric),
+    RemoveMetric(usize, usize),
+    AddPanel,
+    Delete,
+}
+
 pub struct Metrics {
     #[allow(dead_code)]
     runtime: Runtime,
+    editing : bool,
 }
 
 impl Metrics {
     pub fn new(runtime: Runtime) -> Self {
-        Self { runtime }
+        Self { runtime, editing : false }
     }
 }
 
@@ -46,6 +108,8 @@ impl ModuleT for Metrics {
         let mut graph_height = core.settings.user_interface.metrics.graph_height;
         let mut graph_range_from = core.settings.user_interface.metrics.graph_range_from;
         let mut graph_range_to = core.settings.user_interface.metrics.graph_range_to;
+        #[allow(unused_mut)]
+        let mut history_range = core.settings.user_interface.metrics.history_range;
 
         if graph_range_from < METRICS_SAMPLES_START {
             graph_range_from = METRICS_SAMPLES_START;
@@ -63,8 +127,38 @@ impl ModuleT for Metrics {
             graph_range_to = 0;
         }
 
+        let dashboard_count = core.settings.user_interface.metrics.dashboards.len();
+        let mut dashboard = core.settings.user_interface.metrics.dashboard.filter(|index| *index < dashboard_count);
+
         ui.horizontal(|ui|{
             ui.heading(i18n("Metrics"));
+            ui.separator();
+
+            let dashboards = &mut core.settings.user_interface.metrics.dashboards;
+            let selected = dashboard.and_then(|index| dashboards.get(index)).map(|dashboard| dashboard.name.clone()).unwrap_or(i18n("All Metrics").to_string());
+            egui::ComboBox::from_id_source("metrics_dashboard")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut dashboard, None, i18n("All Metrics"));
+                    for (index, item) in dashboards.iter().enumerate() {
+                        ui.selectable_value(&mut dashboard, Some(index), item.name.as_str());
+                    }
+                });
+
+            if ui.button(i18n("New Dashboard")).clicked() {
+                dashboards.push(MetricsDashboard {
+                    name : format!("{} {}", i18n("Dashboard"), dashboards.len() + 1),
+                    panels : vec![MetricsPanel { metrics : vec![Metric::NodeCpuUsage], range : MetricsPanelRange::default() }],
+                });
+                dashboard = Some(dashboards.len() - 1);
+                self.editing = true;
+                store_settings = true;
+            }
+
+            if dashboard.is_some() {
+                ui.toggle_value(&mut self.editing, i18n("Edit"));
+            }
+
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 PopupPanel::new(PopupPanel::id(ui,"metrics_settings"),|ui|{ ui.add(Label::new("Settings ⏷").sense(Sense::click())) }, |ui, _| {
                     ui.add(
@@ -137,26 +231,62 @@ impl ModuleT for Metrics {
 
                 ui.separator();
 
-                ui.add(
-                    Slider::new(&mut graph_range_to, (METRICS_SAMPLES_START+MIN_RANGE)..=0)
-                        .logarithmic(true)
-                        .orientation(SliderOrientation::Horizontal)
-                        .show_value(false)
-                        // .custom_formatter(|v, _range| {
-                        //     format_duration(-v as u64)
-                        // })
-                );
-                ui.add(
-                    Slider::new(&mut graph_range_from, METRICS_SAMPLES_START..=-MIN_RANGE)
-                        .logarithmic(true)
-                        .orientation(SliderOrientation::Horizontal)
-                        .show_value(false)
-                        // .custom_formatter(|v, _range| {
-                        //     format_duration(-v as u64)
-                        // })
-                );
-                ui.label(format!("{} ... {}", format_duration(-graph_range_from as u64), format_duration(-graph_range_to as u64)));
-                if core.device().orientation() != Orientation::Portrait {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if ui.button(i18n("Export CSV")).clicked() {
+                        let metrics = Metric::list().into_iter().filter(|metric| !core.settings.user_interface.metrics.disabled.contains(metric)).collect::<Vec<_>>();
+                        // export the range displayed by the metric graphs (dashboard
+                        // panels have their own ranges, so all samples are exported)
+                        let (history, range) = if dashboard.is_none() {
+                            (self.runtime.metrics_service().history(core.settings.node.network, history_range), graph_range_from..graph_range_to)
+                        } else {
+                            (None, METRICS_SAMPLES_START..0)
+                        };
+                        if let Some(target) = rfd::FileDialog::new().set_file_name("metrics.csv").add_filter("CSV", &["csv"]).save_file() {
+                            match self.runtime.metrics_service().export_csv(&metrics, history.as_deref(), range, &target) {
+                                Ok(()) => runtime().notify(UserNotification::success(format!("{} {}", i18n("Metrics exported to"), target.display()))),
+                                Err(err) => runtime().error(format!("{} {err}", i18n("Unable to export metrics:"))),
+                            }
+                        }
+                    }
+                    ui.separator();
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if dashboard.is_none() {
+                    egui::ComboBox::from_id_source("metrics_history_range")
+                        .selected_text(history_range.to_string())
+                        .show_ui(ui, |ui| {
+                            for range in MetricsHistoryRange::list() {
+                                ui.selectable_value(&mut history_range, range, range.to_string());
+                            }
+                        });
+                    ui.separator();
+                }
+
+                // dashboard panels have their own time ranges
+                if dashboard.is_none() && history_range == MetricsHistoryRange::Live {
+                    ui.add(
+                        Slider::new(&mut graph_range_to, (METRICS_SAMPLES_START+MIN_RANGE)..=0)
+                            .logarithmic(true)
+                            .orientation(SliderOrientation::Horizontal)
+                            .show_value(false)
+                            // .custom_formatter(|v, _range| {
+                            //     format_duration(-v as u64)
+                            // })
+                    );
+                    ui.add(
+                        Slider::new(&mut graph_range_from, METRICS_SAMPLES_START..=-MIN_RANGE)
+                            .logarithmic(true)
+                            .orientation(SliderOrientation::Horizontal)
+                            .show_value(false)
+                            // .custom_formatter(|v, _range| {
+                            //     format_duration(-v as u64)
+                            // })
+                    );
+                    ui.label(format!("{} ... {}", format_duration(-graph_range_from as u64), format_duration(-graph_range_to as u64)));
+                }
+                if dashboard.is_none() && core.device().orientation() != Orientation::Portrait {
                     ui.label(i18n("Range:"));
                 }
 
@@ -188,17 +318,42 @@ impl ModuleT for Metrics {
         || graph_height != core.settings.user_interface.metrics.graph_height 
         || graph_range_from != core.settings.user_interface.metrics.graph_range_from 
         || graph_range_to != core.settings.user_interface.metrics.graph_range_to 
+        || history_range != core.settings.user_interface.metrics.history_range
+        || dashboard != core.settings.user_interface.metrics.dashboard
         {
+            if dashboard != core.settings.user_interface.metrics.dashboard {
+                self.editing = false;
+            }
+
             core.settings.user_interface.metrics.graph_columns = graph_columns;
             core.settings.user_interface.metrics.graph_height = graph_height;
             core.settings.user_interface.metrics.graph_range_from = graph_range_from;
             core.settings.user_interface.metrics.graph_range_to = graph_range_to;
+            core.settings.user_interface.metrics.history_range = history_range;
+            core.settings.user_interface.metrics.dashboard = dashboard;
             
             core.store_settings();
         }
 
         ui.separator();
 
+        if let Some(index) = dashboard {
+            if self.editing {
+                if self.render_dashboard_editor(core, ui, index) {
+                    core.store_settings();
+                }
+                return;
+            }
+
+            if let Some(metrics) = core.metrics().as_ref() {
+                self.render_dashboard(core, ui, index, metrics);
+                return;
+            }
+        }
+
+        // long-range views are served from the on-disk metrics history
+        let history = self.runtime.metrics_service().history(core.settings.node.network, history_range);
+
         if let Some(metrics) = core.metrics().as_ref() {
 
             egui::ScrollArea::vertical()
@@ -223,7 +378,7 @@ impl ModuleT for Metrics {
                                     if let Some(metric) = metric_iter.next() {
                                         let range_from = core.settings.user_interface.metrics.graph_range_from;
                                         let range_to = core.settings.user_interface.metrics.graph_range_to;
-                                        self.render_metric(ui,metric,metrics,range_from..range_to,graph_width,graph_height);
+                                        self.render_metric(ui,metric,metrics,history.as_deref(),range_from..range_to,graph_width,graph_height);
                                     } else {
                                         draw = false;
                                     }
@@ -261,6 +416,7 @@ impl Metrics {
         ui : &mut Ui, 
         metric : Metric, 
         metrics : &MetricsSnapshot, 
+        history : Option<&MetricsHistoryData>,
         range : std::ops::Range<isize>,
         graph_width : f32, 
         graph_height : f32
@@ -268,6 +424,7 @@ impl Metrics {
 
         let group = MetricGroup::from(metric);
         let graph_color = group.to_color();
+        let time_format = if history.is_some() { "%m-%d %H:%M" } else { "%H:%M:%S" };
 
         StripBuilder::new(ui)
             .size(Size::exact(graph_width))
@@ -283,18 +440,10 @@ impl Metrics {
                         });
 
                         // ---
-                        let graph_data = {
-                            let metrics_data = self.runtime.metrics_service().metrics_data();
-                            let data = metrics_data.get(&metric).unwrap();
-                            let mut start = range.start.clamp(METRICS_SAMPLES_START, 0).unsigned_abs();
-                            let mut end = range.end.clamp(METRICS_SAMPLES_START, 0).unsigned_abs();
-                            if start > data.len() {
-                                start = data.len();
-                            }
-                            if end > data.len() {
-                                end = data.len();
-                            }
-                            data[data.len()-start..data.len()-end].to_vec()
+                        let graph_data = if let Some(history) = history {
+                            history.get(&metric).cloned().unwrap_or_default()
+                        } else {
+                            self.runtime.metrics_service().metrics_data_range(&metric, range)
                         };
 
                         let mut plot = Plot::new(metric.as_str())
@@ -320,7 +469,7 @@ impl Metrics {
                                 DateTime::<chrono::Utc>::from_timestamp((x / 1000.0) as i64, 0)
                                     .expect("could not parse timestamp")
                                     .with_timezone(&chrono::Local)
-                                    .format("%H:%M:%S")
+                                    .format(time_format)
                                     .to_string()
                             })
                             .x_grid_spacer(
@@ -338,7 +487,7 @@ impl Metrics {
                                 format!("{} @ {}", metric.format(*y, true, true), DateTime::<chrono::Utc>::from_timestamp((*x / 1000.0) as i64, 0)
                                     .expect("could not parse timestamp")
                                     .with_timezone(&chrono::Local)
-                                    .format("%H:%M:%S")
+                                    .format(time_format)
                                 )
                             })                                                    
                             .coordinates_formatter(Corner::LeftTop, CoordinatesFormatter::new(move |point,_| {
@@ -374,6 +523,284 @@ impl Metrics {
                 });
         });
     }
+
+    fn render_dashboard(&mut self, core : &Core, ui : &mut Ui, index : usize, metrics : &MetricsSnapshot) {
+        let Some(dashboard) = core.settings.user_interface.metrics.dashboards.get(index) else {
+            return;
+        };
+
+        let panels = dashboard.panels.iter().enumerate().filter(|(_, panel)| !panel.metrics.is_empty()).collect::<Vec<_>>();
+        if panels.is_empty() {
+            ui.label(i18n("This dashboard has no metrics. Use 'Edit' to add panels and metrics."));
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_source("metrics_dashboard")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                let view_width = ui.available_width() - 32.;
+                let graph_height = core.settings.user_interface.metrics.graph_height as f32;
+
+                let (columns, graph_width) = if core.device().orientation() == Orientation::Portrait {
+                    (1,view_width)
+                } else {
+                    (core.settings.user_interface.metrics.graph_columns, view_width / core.settings.user_interface.metrics.graph_columns as f32)
+                };
+
+                for row in panels.chunks(columns.max(1)) {
+                    ui.horizontal(|ui| {
+                        for (panel_index, panel) in row {
+                            self.render_panel(ui, *panel_index, panel, metrics, core.settings.node.network, graph_width, graph_height);
+                        }
+                    });
+                }
+            });
+    }
+
+    fn panel_data(&self, metric : &Metric, range : MetricsPanelRange, history : Option<&MetricsHistoryData>) -> Vec<PlotPoint> {
+        if let Some(history) = history {
+            let from = unixtime_as_millis_f64() - range.seconds() as f64 * 1000.0;
+            history.get(metric).map(|data| data.iter().filter(|point| point.x >= from).cloned().collect()).unwrap_or_default()
+        } else {
+            let metrics_data = self.runtime.metrics_service().metrics_data();
+            let data = metrics_data.get(metric).map(|data| data.as_slice()).unwrap_or_default();
+            data[data.len().saturating_sub(range.seconds() as usize)..].to_vec()
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_panel(
+        &mut self, 
+        ui : &mut Ui, 
+        index : usize,
+        panel : &MetricsPanel, 
+        metrics : &MetricsSnapshot, 
+        network : Network,
+        graph_width : f32, 
+        graph_height : f32
+    ) {
+        let history = panel.range.history_range().and_then(|range| self.runtime.metrics_service().history(network, range));
+        let time_format = if history.is_some() { "%m-%d %H:%M" } else { "%H:%M:%S" };
+
+        // overlaid metrics are scaled to the value range of the primary metric
+        let mut series = panel.metrics.iter().map(|metric| (*metric, self.panel_data(metric, panel.range, history.as_deref()), PanelScale::identity())).collect::<Vec<_>>();
+        let primary_range = value_range(&series[0].1);
+        for (_, data, scale) in series.iter_mut().skip(1) {
+            *scale = PanelScale::new(value_range(data), primary_range);
+            data.iter_mut().for_each(|point| point.y = scale.apply(point.y));
+        }
+
+        let primary = series[0].0;
+        let secondary = series.get(1).map(|(metric, _, scale)| (*metric, *scale));
+        let scales = series.iter().map(|(metric, _, scale)| (i18n(metric.title().0), *metric, *scale)).collect::<Vec<_>>();
+
+        StripBuilder::new(ui)
+            .size(Size::exact(graph_width))
+            .horizontal(|mut strip| {
+
+                strip.cell(|ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(8.);
+                        ui.horizontal(|ui|{
+                            ui.with_layout(Layout::right_to_left(egui::Align::Min), |ui| {
+                                let caption = series.iter().map(|(metric, _, _)| format!("{}: {}", i18n(metric.title().0), metric.format(metrics.get(metric), true, false))).collect::<Vec<_>>().join("  ");
+                                ui.colored_label(theme_color().metrics_text_color, format!("{} ({})", caption, panel.range));
+                            });
+                        });
+
+                        let mut y_axes = vec![AxisHints::default().formatter(move |y, _size, _range| primary.format(y, true, true))];
+                        if let Some((metric, scale)) = secondary {
+                            y_axes.push(AxisHints::default().placement(HPlacement::Right).formatter(move |y, _size, _range| metric.format(scale.invert(y), true, true)));
+                        }
+
+                        let plot = Plot::new(format!("metrics_panel_{index}"))
+                            .legend(Legend::default())
+                            .width(graph_width)
+                            .height(graph_height)
+                            .auto_bounds_x()
+                            .auto_bounds_y()
+                            .set_margin_fraction(vec2(0.0,0.0) )
+                            .custom_y_axes(y_axes)
+                            .show_axes(true)
+                            .show_grid(true)
+                            .allow_drag([false, false])
+                            .allow_scroll(false)
+                            .x_axis_formatter(move |x, _size, _range| {
+                                DateTime::<chrono::Utc>::from_timestamp((x / 1000.0) as i64, 0)
+                                    .expect("could not parse timestamp")
+                                    .with_timezone(&chrono::Local)
+                                    .format(time_format)
+                                    .to_string()
+                            })
+                            .x_grid_spacer(
+                                uniform_grid_spacer(move |input| {
+                                    let (start_time,stop_time) = input.bounds;
+                                    let range = stop_time - start_time;
+                                    let base_step_size = range / graph_width as f64 * 64.;
+                                    calculate_grid_lines(base_step_size)
+                                })
+                            )
+                            .label_formatter(move |name, point| {
+                                let PlotPoint { x, y } = point;
+                                let time = DateTime::<chrono::Utc>::from_timestamp((*x / 1000.0) as i64, 0)
+                                    .expect("could not parse timestamp")
+                                    .with_timezone(&chrono::Local)
+                                    .format(time_format);
+                                match scales.iter().find(|(title, _, _)| *title == name) {
+                                    Some((title, metric, scale)) => format!("{}: {} @ {}", title, metric.format(scale.invert(*y), true, true), time),
+                                    None => time.to_string(),
+                                }
+                            });
+
+                        let lines = series.into_iter().enumerate().map(|(index, (metric, data, _))| {
+                            let line = Line::new(PlotPoints::Owned(data))
+                                .name(i18n(metric.title().0))
+                                .style(LineStyle::Solid);
+                            if index == 0 {
+                                line.color(MetricGroup::from(metric).to_color()).fill(0.0)
+                            } else {
+                                line.color(OVERLAY_COLORS[(index - 1) % OVERLAY_COLORS.len()])
+                            }
+                        }).collect::<Vec<_>>();
+
+                        plot.show(ui, |plot_ui| {
+                            for line in lines {
+                                plot_ui.line(line);
+                            }
+                        });
+                    });
+                });
+        });
+    }
+
+    /// Render the dashboard editor, returning `true` if the dashboard was modified.
+    fn render_dashboard_editor(&mut self, core : &mut Core, ui : &mut Ui, index : usize) -> bool {
+        let dashboards = &mut core.settings.user_interface.metrics.dashboards;
+        let Some(dashboard) = dashboards.get_mut(index) else {
+            return false;
+        };
+
+        let mut changed = false;
+        let mut action = None;
+
+        egui::ScrollArea::vertical()
+            .id_source("metrics_dashboard_editor")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(i18n("Dashboard name:"));
+                    changed |= ui.add(TextEdit::singleline(&mut dashboard.name).desired_width(240.)).changed();
+                });
+
+                ui.add_space(8.);
+
+                let panel_count = dashboard.panels.len();
+                for (panel_index, panel) in dashboard.panels.iter_mut().enumerate() {
+                    ui.group(|ui| {
+                        ui.set_width(ui.available_width());
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(format!("{} {}", i18n("Panel"), panel_index + 1)).strong());
+                            ui.separator();
+                            ui.label(i18n("Range:"));
+                            egui::ComboBox::from_id_source(format!("metrics_panel_range_{panel_index}"))
+                                .selected_text(panel.range.to_string())
+                                .show_ui(ui, |ui| {
+                                    for range in MetricsPanelRange::list() {
+                                        changed |= ui.selectable_value(&mut panel.range, range, range.to_string()).changed();
+                                    }
+                                });
+
+                            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.small_button("✕").on_hover_text(i18n("Remove panel")).clicked() {
+                                    action = Some(DashboardAction::RemovePanel(panel_index));
+                                }
+                                if ui.add_enabled(panel_index + 1 < panel_count, Button::new("⏷").small()).clicked() {
+                                    action = Some(DashboardAction::MovePanel(panel_index, 1));
+                                }
+                                if ui.add_enabled(panel_index > 0, Button::new("⏶").small()).clicked() {
+                                    action = Some(DashboardAction::MovePanel(panel_index, -1));
+                                }
+                            });
+                        });
+
+                        ui.horizontal_wrapped(|ui| {
+                            if panel.metrics.is_empty() {
+                                ui.label(i18n("No metrics selected"));
+                            }
+
+                            for (metric_index, metric) in panel.metrics.iter().enumerate() {
+                                let label = match metric_index {
+                                    0 => format!("{} ({})", i18n(metric.title().0), i18n("left axis")),
+                                    1 => format!("{} ({})", i18n(metric.title().0), i18n("right axis")),
+                                    _ => i18n(metric.title().0).to_string(),
+                                };
+                                ui.label(label);
+                                if ui.small_button("✕").on_hover_text(i18n("Remove metric")).clicked() {
+                                    action = Some(DashboardAction::RemoveMetric(panel_index, metric_index));
+                                }
+                                ui.separator();
+                            }
+
+                            ui.menu_button(format!("{} ⏷", i18n("Add Metric")), |ui| {
+                                ScrollArea::vertical().max_height(320.).show(ui, |ui| {
+                                    for group in MetricGroup::list() {
+                                        ui.label(RichText::new(i18n(group.title())).strong());
+                                        for metric in group.metrics().iter().filter(|metric| !panel.metrics.contains(metric)) {
+                                            if ui.button(i18n(metric.title().0)).clicked() {
+                                                action = Some(DashboardAction::AddMetric(panel_index, *metric));
+                                                ui.close_menu();
+                                            }
+                                        }
+                                    }
+                                });
+                            });
+                        });
+                    });
+                }
+
+                ui.add_space(8.);
+                ui.horizontal(|ui| {
+                    if ui.button(i18n("Add Panel")).clicked() {
+                        action = Some(DashboardAction::AddPanel);
+                    }
+                    if ui.button(i18n("Delete Dashboard")).clicked() {
+                        action = Some(DashboardAction::Delete);
+                    }
+                });
+            });
+
+        if let Some(action) = action {
+            match action {
+                DashboardAction::MovePanel(panel_index, offset) => {
+                    let target = panel_index.saturating_add_signed(offset);
+                    if target < dashboard.panels.len() {
+                        dashboard.panels.swap(panel_index, target);
+                    }
+                }
+                DashboardAction::RemovePanel(panel_index) => {
+                    dashboard.panels.remove(panel_index);
+                }
+                DashboardAction::AddMetric(panel_index, metric) => {
+                    dashboard.panels[panel_index].metrics.push(metric);
+                }
+                DashboardAction::RemoveMetric(panel_index, metric_index) => {
+                    dashboard.panels[panel_index].metrics.remove(metric_index);
+                }
+                DashboardAction::AddPanel => {
+                    dashboard.panels.push(MetricsPanel { metrics : vec![], range : MetricsPanelRange::default() });
+                }
+                DashboardAction::Delete => {
+                    dashboards.remove(index);
+                    core.settings.user_interface.metrics.dashboard = None;
+                    self.editing = false;
+                }
+            }
+            changed = true;
+        }
+
+        changed
+    }
 }
 
 fn calculate_grid_lines(base_step_size : f64) -> [f64; 3] {
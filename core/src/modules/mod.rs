@@ -10,12 +10,14 @@ kaspa_ng_macros::register_modules!(
         donations,
         export,
         import,
+        mempool,
         overview,
         private_key_create,
         request,
         scanner,
         settings,
         testing,
+        tools,
         wallet_create,
         wallet_open,
         wallet_secret,
@@ -1,16 +1,171 @@
-use kaspa_rpc_core::RpcPeerInfo;
+use kaspa_rpc_core::{RpcContextualPeerAddress, RpcPeerInfo};
 
 use crate::imports::*;
+use crate::runtime::services::peer_monitor::PeerAction;
 use crate::utils::format_duration;
 
 pub struct Node {
     #[allow(dead_code)]
     runtime: Runtime,
+    peer_address: String,
+    peer_is_permanent: bool,
 }
 
 impl Node {
     pub fn new(runtime: Runtime) -> Self {
-        Self { runtime }
+        Self { runtime, peer_address: String::default(), peer_is_permanent: false }
+    }
+
+    /// Execute a peer action, updating the persistent ban list if needed.
+    fn apply_action(&self, core: &mut Core, action: PeerAction) {
+        let peer_monitor_service = self.runtime.peer_monitor_service();
+
+        match &action {
+            PeerAction::Ban(ip) => {
+                let ip = ip.to_string();
+                if !core.settings.peers.banned.contains(&ip) {
+                    core.settings.peers.banned.push(ip);
+                    core.store_settings();
+                }
+            }
+            PeerAction::Unban(ip) => {
+                let ip = ip.to_string();
+                if core.settings.peers.banned.contains(&ip) {
+                    core.settings.peers.banned.retain(|banned| *banned != ip);
+                    core.store_settings();
+                }
+            }
+            _ => {}
+        }
+
+        peer_monitor_service.update_settings(&core.settings.peers);
+        peer_monitor_service.action(action);
+    }
+
+    fn render_summary(&self, ui : &mut Ui, peers: &[RpcPeerInfo]) {
+        let color = theme_color().node_data_color;
+
+        let outbound = peers.iter().filter(|peer| peer.is_outbound).count();
+        let inbound = peers.len() - outbound;
+        let ibd = peers.iter().filter(|peer| peer.is_ibd_peer).count();
+
+        let mut user_agents = AHashMap::<&str, (usize, usize)>::default();
+        let mut protocols = AHashMap::<u32, usize>::default();
+        for peer in peers.iter() {
+            let (outbound, inbound) = user_agents.entry(peer.user_agent.as_str()).or_default();
+            if peer.is_outbound { *outbound += 1; } else { *inbound += 1; }
+            *protocols.entry(peer.advertised_protocol_version).or_default() += 1;
+        }
+
+        let mut user_agents = user_agents.into_iter().collect::<Vec<_>>();
+        user_agents.sort_by(|(a_agent, (a_out, a_in)), (b_agent, (b_out, b_in))| (b_out + b_in).cmp(&(a_out + a_in)).then(a_agent.cmp(b_agent)));
+        let mut protocols = protocols.into_iter().collect::<Vec<_>>();
+        protocols.sort_by(|a, b| b.0.cmp(&a.0));
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n("Peers:"));
+            ui.colored_label(color, peers.len().to_string());
+            ui.label(i18n("Outbound:"));
+            ui.colored_label(color, outbound.to_string());
+            ui.label(i18n("Inbound:"));
+            ui.colored_label(color, inbound.to_string());
+            ui.label(i18n("IBD:"));
+            ui.colored_label(color, ibd.to_string());
+        });
+
+        if outbound == 0 {
+            ui.colored_label(theme_color().warning_color, i18n("The node has no outbound peers; please check your network connection and firewall settings"));
+        }
+
+        Grid::new("peer_user_agent_grid")
+            .num_columns(3)
+            .striped(true)
+            .spacing([16.0,4.0])
+            .show(ui, |ui| {
+                ui.label(i18n("User Agent"));
+                ui.label(i18n("Outbound"));
+                ui.label(i18n("Inbound"));
+                ui.end_row();
+
+                for (user_agent, (outbound, inbound)) in user_agents {
+                    ui.colored_label(color, user_agent);
+                    ui.colored_label(color, outbound.to_string());
+                    ui.colored_label(color, inbound.to_string());
+                    ui.end_row();
+                }
+            });
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n("Protocol versions:"));
+            for (version, count) in protocols {
+                ui.colored_label(color, format!("v{version}: {count}"));
+            }
+        });
+    }
+
+    fn render_add_peer(&mut self, ui : &mut Ui) -> Option<PeerAction> {
+        let mut action = None;
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n("Peer Address:"));
+            ui.add(TextEdit::singleline(&mut self.peer_address).hint_text("127.0.0.1:16111"));
+            ui.checkbox(&mut self.peer_is_permanent, i18n("Permanent"));
+
+            let address = self.peer_address.trim().parse::<RpcContextualPeerAddress>();
+            if ui.medium_button_enabled(address.is_ok(), i18n("Add Peer")).clicked() {
+                if let Ok(address) = address {
+                    action = Some(PeerAction::AddPeer { address, is_permanent: self.peer_is_permanent });
+                    self.peer_address.clear();
+                }
+            }
+        });
+
+        if !self.peer_address.trim().is_empty() {
+            if let Err(err) = self.peer_address.trim().parse::<RpcContextualPeerAddress>() {
+                ui.colored_label(theme_color().warning_color, format!("{} {err}", i18n("Invalid peer address:")));
+            }
+        }
+
+        action
+    }
+
+    fn render_ban_list(&self, core: &Core, ui : &mut Ui) -> Option<PeerAction> {
+        let mut action = None;
+
+        let mut banned = self.runtime.peer_monitor_service().banned_addresses()
+            .map(|banned| banned.iter().map(|ip| ip.to_string()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        for ip in core.settings.peers.banned.iter() {
+            if !banned.contains(ip) {
+                banned.push(ip.clone());
+            }
+        }
+
+        if banned.is_empty() {
+            ui.label(i18n("No banned addresses"));
+            return None;
+        }
+
+        Grid::new("peer_ban_list_grid")
+            .num_columns(3)
+            .striped(true)
+            .spacing([16.0,4.0])
+            .show(ui, |ui| {
+                for ip in banned.iter() {
+                    ui.label(ip);
+                    if core.settings.peers.banned.contains(ip) {
+                        ui.label(i18n("Persistent"));
+                    } else {
+                        ui.label("");
+                    }
+                    if ui.small_button(i18n("Unban")).clicked() {
+                        action = ip.parse().ok().map(PeerAction::Unban);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        action
     }
 }
 
@@ -36,11 +191,49 @@ impl ModuleT for Node {
             return;
         }
 
+        let mut action = None;
+        let is_peer_management_available = core.settings.node.is_peer_management_available();
+        let mut banned = self.runtime.peer_monitor_service().banned_addresses()
+            .map(|banned| banned.iter().copied().collect::<AHashSet<_>>())
+            .unwrap_or_default();
+        banned.extend(core.settings.peers.banned.iter().filter_map(|ip| ip.parse().ok()));
+
         egui::ScrollArea::vertical()
             .auto_shrink([false; 2])
 
             .show(ui, |ui| {
 
+                let peers = self.runtime.peer_monitor_service().peer_info();
+
+                if let Some(peers) = peers.as_ref() {
+                    CollapsingHeader::new(i18n("Peer Summary"))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            self.render_summary(ui, peers);
+                        });
+                }
+
+                CollapsingHeader::new(i18n("Peer Management"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        if !is_peer_management_available {
+                            ui.label(i18n("Peer management unavailable"));
+                            ui.label(i18n("Peer management requires a local node with unsafe RPC calls enabled in the node settings"));
+                            return;
+                        }
+
+                        if let Some(add_peer) = self.render_add_peer(ui) {
+                            action = Some(add_peer);
+                        }
+
+                        CollapsingHeader::new(i18n("Banned Addresses"))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                if let Some(unban) = self.render_ban_list(core, ui) {
+                                    action = Some(unban);
+                                }
+                            });
+                    });
 
                 CollapsingHeader::new(i18n("Network Peers"))
                     .default_open(true)
@@ -48,7 +241,7 @@ impl ModuleT for Node {
 
                         ui.vertical(|ui| {
 
-                            if let Some(peers) = self.runtime.peer_monitor_service().peer_info() {
+                            if let Some(peers) = peers {
                                 let (outbound, inbound) : (Vec<_>,Vec<_>) = peers.iter().partition(|peer| peer.is_outbound);
 
                                 CollapsingHeader::new(i18n("Inbound"))
@@ -56,7 +249,9 @@ impl ModuleT for Node {
                                     .show(ui, |ui| {
 
                                         inbound.iter().for_each(|peer| {
-                                            render_peer(ui, peer);
+                                            if let Some(peer_action) = render_peer(ui, peer, is_peer_management_available && !banned.contains(&peer.address.ip)) {
+                                                action = Some(peer_action);
+                                            }
                                         });
                                     });
 
@@ -65,7 +260,9 @@ impl ModuleT for Node {
                                     .show(ui, |ui| {
 
                                         outbound.iter().for_each(|peer| {
-                                            render_peer(ui, peer);
+                                            if let Some(peer_action) = render_peer(ui, peer, is_peer_management_available && !banned.contains(&peer.address.ip)) {
+                                                action = Some(peer_action);
+                                            }
                                         });
                                     });
                             } else if core.state().metrics().as_ref().map(|m| m.data.node_active_peers).unwrap_or_default() > 0 {
@@ -81,7 +278,10 @@ impl ModuleT for Node {
 
                     });
                 });
-            
+
+        if let Some(action) = action {
+            self.apply_action(core, action);
+        }
     }
 
     fn activate(&mut self, _core: &mut Core) {
@@ -94,9 +294,11 @@ impl ModuleT for Node {
 
 }
 
-fn render_peer(ui : &mut Ui, peer: &RpcPeerInfo) {
+/// Render peer information; `is_manageable` enables the disconnect and ban actions.
+fn render_peer(ui : &mut Ui, peer: &RpcPeerInfo, is_manageable : bool) -> Option<PeerAction> {
 
     let color = theme_color().node_data_color;
+    let mut action = None;
 
     CollapsingHeader::new(peer.id.to_string())
         .default_open(true)
@@ -134,5 +336,17 @@ fn render_peer(ui : &mut Ui, peer: &RpcPeerInfo) {
                     ui.end_row();
                 });
 
+            if is_manageable {
+                ui.horizontal(|ui| {
+                    if ui.small_button(i18n("Disconnect")).clicked() {
+                        action = Some(PeerAction::Disconnect(peer.address.ip));
+                    }
+                    if ui.small_button(i18n("Ban")).clicked() {
+                        action = Some(PeerAction::Ban(peer.address.ip));
+                    }
+                });
+            }
         });
+
+    action
 }
\ No newline at end of file
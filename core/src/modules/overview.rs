@@ -6,23 +6,46 @@ use egui::load::Bytes;
 use kaspa_metrics_core::{Metric,MetricGroup};
 #[cfg(not(feature = "lean"))]
 use egui_plot::{
+    BoxElem,
+    BoxPlot,
+    BoxSpread,
     Legend,
     Line,
     LineStyle,
     Plot,
     PlotPoints,
 };
+#[cfg(not(feature = "lean"))]
+use chrono::DateTime;
 
 use crate::imports::*;
+#[cfg(not(feature = "lean"))]
+use crate::market::{Ohlc, OhlcRange};
+
+/// Interval at which the price chart re-requests OHLC data
+/// from the market monitor (the monitor serves it from cache
+/// until the data becomes stale).
+#[cfg(not(feature = "lean"))]
+const OHLC_REQUEST_INTERVAL: Duration = Duration::from_secs(60);
 
 pub struct Overview {
     #[allow(dead_code)]
     runtime: Runtime,
+    #[cfg(not(feature = "lean"))]
+    ohlc_range: OhlcRange,
+    #[cfg(not(feature = "lean"))]
+    ohlc_request: Option<(String, OhlcRange, Instant)>,
 }
 
 impl Overview {
     pub fn new(runtime: Runtime) -> Self {
-        Self { runtime }
+        Self {
+            runtime,
+            #[cfg(not(feature = "lean"))]
+            ohlc_range: OhlcRange::default(),
+            #[cfg(not(feature = "lean"))]
+            ohlc_request: None,
+        }
     }
 }
 
@@ -169,8 +192,11 @@ impl Overview {
                                     })
                                 }
                             });
-                        }
+
+                        #[cfg(not(feature = "lean"))]
+                        self.render_price_chart(core, ui);
                     }
+                }
 
                 #[cfg(not(target_arch = "wasm32"))]
                 CollapsingHeader::new(i18n("Kaspa NG"))
@@ -365,6 +391,137 @@ impl Overview {
             });
     }
 
+    #[cfg(not(feature = "lean"))]
+    fn render_price_chart(&mut self, core: &Core, ui : &mut Ui) {
+
+        let currency = core.settings.market.fiat_currency.to_lowercase();
+
+        let refresh = match self.ohlc_request.as_ref() {
+            Some((request_currency, range, instant)) => {
+                *request_currency != currency || *range != self.ohlc_range || instant.elapsed() > OHLC_REQUEST_INTERVAL
+            }
+            None => true,
+        };
+
+        if refresh {
+            self.runtime.market_monitor_service().request_ohlc(currency.as_str(), self.ohlc_range);
+            self.ohlc_request = Some((currency.clone(), self.ohlc_range, Instant::now()));
+        }
+
+        let ohlc = core.market.as_ref()
+            .and_then(|market| market.ohlc.as_ref())
+            .and_then(|ohlc| ohlc.get(&currency))
+            .filter(|ohlc| ohlc.range == self.ohlc_range)
+            .cloned();
+
+        CollapsingHeader::new(i18n("Price Chart"))
+            .default_open(true)
+            .show(ui, |ui| {
+
+                ui.horizontal(|ui| {
+                    OhlcRange::iter().for_each(|range| {
+                        if ui.selectable_label(self.ohlc_range == *range, range.to_string()).clicked() {
+                            self.ohlc_range = *range;
+                        }
+                    });
+                    ui.separator();
+                    ui.label(format!("KAS/{}", currency.to_uppercase()));
+                });
+
+                match ohlc {
+                    Some(ohlc) if !ohlc.points.is_empty() => {
+                        self.render_ohlc_plot(ui, &ohlc);
+                    }
+                    _ => {
+                        let provider = &core.settings.market.provider;
+                        if !provider.supports_ohlc() {
+                            ui.label(format!("{} {provider}", i18n("Market history is not supported by")));
+                        } else if let Some(err) = self.runtime.market_monitor_service().ohlc_error(currency.as_str(), self.ohlc_range) {
+                            ui.label(RichText::new(format!("{} {err}", i18n("Unable to load market history:"))).color(theme_color().error_color));
+                        } else {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(i18n("Loading market history..."));
+                            });
+                        }
+                    }
+                }
+            });
+    }
+
+    #[cfg(not(feature = "lean"))]
+    fn render_ohlc_plot(&self, ui : &mut Ui, ohlc : &Ohlc) {
+
+        let symbol = ohlc.currency.to_uppercase();
+        let precision = precision_from_symbol(ohlc.currency.as_str());
+        let time_format = match ohlc.range {
+            OhlcRange::Day => "%H:%M",
+            OhlcRange::Week | OhlcRange::Month => "%b %d",
+            OhlcRange::Year => "%b %Y",
+        };
+
+        let interval = ohlc.points.windows(2)
+            .map(|pair| pair[1].timestamp - pair[0].timestamp)
+            .fold(f64::MAX, f64::min);
+        let interval = if interval == f64::MAX { 60.0 * 60.0 * 1000.0 } else { interval };
+
+        let candles = ohlc.points.iter().map(|point| {
+            let color = if point.close >= point.open {
+                theme_color().market_up_color
+            } else {
+                theme_color().market_down_color
+            };
+            let (bottom, top) = if point.close >= point.open { (point.open, point.close) } else { (point.close, point.open) };
+            BoxElem::new(point.timestamp, BoxSpread::new(point.low, bottom, point.close, top, point.high))
+                .box_width(interval * 0.7)
+                .whisker_width(0.0)
+                .fill(color.gamma_multiply(0.5))
+                .stroke(Stroke::new(1.0, color))
+        }).collect::<Vec<_>>();
+
+        let close = ohlc.points.iter().map(|point| [point.timestamp, point.close]).collect::<Vec<_>>();
+
+        let frame = 
+            Frame::none()
+                .stroke(Stroke::new(1.0, theme_color().graph_frame_color))
+                .inner_margin(4.)
+                .rounding(6.);
+
+        frame.show(ui, |ui| {
+            let symbol_y = symbol.clone();
+            Plot::new("market_ohlc_plot")
+                .height(192.)
+                .auto_bounds_x()
+                .auto_bounds_y()
+                .allow_drag([false, false])
+                .allow_scroll(false)
+                .allow_zoom(false)
+                .show_background(false)
+                .y_axis_formatter(move |y, _size, _range| {
+                    format_currency(y, precision)
+                })
+                .x_axis_formatter(move |x, _size, _range| {
+                    DateTime::<chrono::Utc>::from_timestamp((x / 1000.0) as i64, 0)
+                        .map(|time| time.with_timezone(&chrono::Local).format(time_format).to_string())
+                        .unwrap_or_default()
+                })
+                .label_formatter(move |_name, point| {
+                    let time = DateTime::<chrono::Utc>::from_timestamp((point.x / 1000.0) as i64, 0)
+                        .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_default();
+                    format!("{} @ {}", format_currency_with_symbol(point.y, precision, symbol_y.as_str()), time)
+                })
+                .show(ui, |plot_ui| {
+                    plot_ui.line(
+                        Line::new(PlotPoints::from(close))
+                            .color(theme_color().market_default_color.gamma_multiply(0.5))
+                            .style(LineStyle::Solid)
+                    );
+                    plot_ui.box_plot(BoxPlot::new(candles).name(symbol.as_str()));
+                });
+        });
+    }
+
     #[cfg(not(feature = "lean"))]
     fn render_graphs(&mut self, core: &mut Core, ui : &mut Ui) {
 
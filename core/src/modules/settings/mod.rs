@@ -1,12 +1,196 @@
 use crate::imports::*;
-use crate::servers::render_public_server_selector;
+use crate::servers::{render_public_server_selector, update_user_servers, user_servers};
+use kaspa_metrics_core::{Metric, MetricGroup};
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum AlertRuleKind {
+    #[default]
+    PriceAbove,
+    PriceBelow,
+    FundsReceived,
+    BalanceBelow,
+    MetricAbove,
+    MetricBelow,
+    SyncStalled,
+}
+
+impl AlertRuleKind {
+    fn iter() -> impl Iterator<Item = &'static AlertRuleKind> {
+        [
+            AlertRuleKind::PriceAbove,
+            AlertRuleKind::PriceBelow,
+            AlertRuleKind::FundsReceived,
+            AlertRuleKind::BalanceBelow,
+            AlertRuleKind::MetricAbove,
+            AlertRuleKind::MetricBelow,
+            AlertRuleKind::SyncStalled,
+        ].iter()
+    }
+}
+
+impl std::fmt::Display for AlertRuleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertRuleKind::PriceAbove => write!(f, "{}", i18n("Price above")),
+            AlertRuleKind::PriceBelow => write!(f, "{}", i18n("Price below")),
+            AlertRuleKind::FundsReceived => write!(f, "{}", i18n("Funds received")),
+            AlertRuleKind::BalanceBelow => write!(f, "{}", i18n("Balance below")),
+            AlertRuleKind::MetricAbove => write!(f, "{}", i18n("Metric above")),
+            AlertRuleKind::MetricBelow => write!(f, "{}", i18n("Metric below")),
+            AlertRuleKind::SyncStalled => write!(f, "{}", i18n("Sync stalled")),
+        }
+    }
+}
+
+#[derive(Default)]
+struct AlertEditor {
+    kind : AlertRuleKind,
+    currency : String,
+    threshold : String,
+    account : Option<String>,
+    metric : Option<Metric>,
+    duration : String,
+}
+
+impl AlertEditor {
+    fn try_duration(&self) -> std::result::Result<u64, &'static str> {
+        let duration = self.duration.trim();
+        if duration.is_empty() {
+            Ok(0)
+        } else {
+            duration.parse::<u64>().map_err(|_| i18n("Please enter a valid duration"))
+        }
+    }
+
+    fn try_rule(&self) -> std::result::Result<AlertRule, &'static str> {
+        match self.kind {
+            AlertRuleKind::PriceAbove | AlertRuleKind::PriceBelow => {
+                let threshold = self.threshold.trim().parse::<f64>()
+                    .ok()
+                    .filter(|threshold| *threshold > 0.0)
+                    .ok_or(i18n("Please enter a valid price"))?;
+                let currency = self.currency.to_lowercase();
+                if self.kind == AlertRuleKind::PriceAbove {
+                    Ok(AlertRule::PriceAbove { currency, threshold })
+                } else {
+                    Ok(AlertRule::PriceBelow { currency, threshold })
+                }
+            }
+            AlertRuleKind::FundsReceived => {
+                Ok(AlertRule::FundsReceived { account: self.account.clone() })
+            }
+            AlertRuleKind::BalanceBelow => {
+                let threshold = try_kaspa_str_to_sompi(self.threshold.trim())
+                    .ok()
+                    .flatten()
+                    .ok_or(i18n("Please enter a valid amount"))?;
+                Ok(AlertRule::BalanceBelow { account: self.account.clone(), threshold })
+            }
+            AlertRuleKind::MetricAbove | AlertRuleKind::MetricBelow => {
+                let metric = self.metric.ok_or(i18n("Please select a metric"))?;
+                let threshold = self.threshold.trim().parse::<f64>()
+                    .ok()
+                    .filter(|threshold| threshold.is_finite())
+                    .ok_or(i18n("Please enter a valid threshold"))?;
+                let duration = self.try_duration()?;
+                if self.kind == AlertRuleKind::MetricAbove {
+                    Ok(AlertRule::MetricAbove { metric, threshold, duration })
+                } else {
+                    Ok(AlertRule::MetricBelow { metric, threshold, duration })
+                }
+            }
+            AlertRuleKind::SyncStalled => {
+                let duration = self.try_duration()?;
+                if duration == 0 {
+                    return Err(i18n("Please enter a valid duration"));
+                }
+                Ok(AlertRule::SyncStalled { duration })
+            }
+        }
+    }
+}
+
+struct ServerEditor {
+    index : Option<usize>,
+    name : String,
+    location : String,
+    network : Network,
+    protocol : WrpcEncoding,
+    address : String,
+    manual : bool,
+}
+
+impl Default for ServerEditor {
+    fn default() -> Self {
+        Self {
+            index : None,
+            name : String::new(),
+            location : String::new(),
+            network : Network::default(),
+            protocol : WrpcEncoding::Borsh,
+            address : String::new(),
+            manual : false,
+        }
+    }
+}
+
+impl ServerEditor {
+    fn edit(index : usize, server : &Server) -> Self {
+        Self {
+            index : Some(index),
+            name : server.name.clone().unwrap_or_default(),
+            location : server.location.clone().unwrap_or_default(),
+            network : server.network,
+            protocol : server.protocol,
+            address : server.address.clone(),
+            manual : server.manual.unwrap_or(false),
+        }
+    }
+
+    /// Create the server from the editor fields, rejecting addresses
+    /// already used by other servers in the `servers` list.
+    fn try_server(&self, enable : bool, servers : &[Server]) -> std::result::Result<Server, &'static str> {
+        let address = self.address.trim();
+        if address.is_empty() {
+            return Err(i18n("Please enter the server address"));
+        }
+        KaspaRpcClient::parse_url(address.to_string(), self.protocol, self.network.into()).map_err(|_| i18n("Invalid server address"))?;
+        if servers.iter().enumerate().any(|(index, server)| Some(index) != self.index && server.address == address) {
+            return Err(i18n("A server with this address already exists"));
+        }
+
+        let optional = |value : &str| { let value = value.trim(); (!value.is_empty()).then(|| value.to_string()) };
+        Ok(Server {
+            name : optional(&self.name),
+            ident : None,
+            location : optional(&self.location),
+            protocol : self.protocol,
+            address : address.to_string(),
+            enable : Some(enable),
+            link : None,
+            network : self.network,
+            bias : None,
+            manual : self.manual.then_some(true),
+            version : None,
+        })
+    }
+}
 
 pub struct Settings {
     #[allow(dead_code)]
     runtime: Runtime,
     settings : crate::settings::Settings,
     grpc_network_interface : NetworkInterfaceEditor,
+    #[allow(dead_code)]
+    prometheus_network_interface : NetworkInterfaceEditor,
+    connect_peers : String,
+    add_peers : String,
     reset_settings : bool,
+    market_currencies_request : Option<MarketDataProvider>,
+    market_currency_filter : String,
+    alert_editor : AlertEditor,
+    profile_name : String,
+    server_editor : ServerEditor,
 }
 
 impl Settings {
@@ -15,7 +199,15 @@ impl Settings {
             runtime,
             settings : crate::settings::Settings::default(),
             grpc_network_interface : NetworkInterfaceEditor::default(),
+            prometheus_network_interface : NetworkInterfaceEditor::default(),
+            connect_peers : String::new(),
+            add_peers : String::new(),
             reset_settings : false,
+            market_currencies_request : None,
+            market_currency_filter : String::new(),
+            alert_editor : AlertEditor::default(),
+            profile_name : String::new(),
+            server_editor : ServerEditor::default(),
         }
     }
 
@@ -23,12 +215,832 @@ impl Settings {
         self.settings = settings;
 
         self.grpc_network_interface = NetworkInterfaceEditor::from(&self.settings.node.grpc_network_interface);
+        self.prometheus_network_interface = NetworkInterfaceEditor::from(&self.settings.prometheus.network_interface);
+        self.load_kaspad_config_editor();
+    }
+
+    fn load_kaspad_config_editor(&mut self) {
+        self.connect_peers = self.settings.node.kaspad_config.connect_peers.join("\n");
+        self.add_peers = self.settings.node.kaspad_config.add_peers.join("\n");
     }
 
     pub fn change_current_network(&mut self, network : Network) {
         self.settings.node.network = network;
     }
 
+    pub fn change_node_settings(&mut self, node_settings : &NodeSettings) {
+        self.settings.node = node_settings.clone();
+        self.grpc_network_interface = NetworkInterfaceEditor::from(&self.settings.node.grpc_network_interface);
+        self.load_kaspad_config_editor();
+    }
+
+    /// Update the node storage folder after the data folder has been relocated.
+    pub fn change_storage_folder(&mut self, storage_folder : &str) {
+        self.settings.node.kaspad_daemon_storage_folder_enable = true;
+        self.settings.node.kaspad_daemon_storage_folder = storage_folder.to_string();
+    }
+
+    fn render_market_settings(&mut self, core: &mut Core, ui: &mut Ui) {
+
+        let market_monitor_service = self.runtime.market_monitor_service().clone();
+
+        ui.horizontal_wrapped(|ui|{
+            ui.label(i18n("Market data provider:"));
+            MarketDataProvider::iter().for_each(|provider| {
+                if ui.radio_value(&mut self.settings.market.provider, provider.clone(), provider.to_string()).changed() {
+                    core.settings.market.provider = self.settings.market.provider.clone();
+                    market_monitor_service.update_settings(&core.settings);
+                    core.store_settings();
+                }
+            });
+        });
+
+        if self.settings.market.provider == MarketDataProvider::Json {
+            CollapsingHeader::new(i18n("JSON Provider Settings"))
+                .default_open(true)
+                .show(ui, |ui| {
+                    let json_provider = &mut self.settings.market.json_provider;
+                    Grid::new("market_json_provider_grid")
+                        .num_columns(2)
+                        .spacing([8.0,4.0])
+                        .show(ui, |ui| {
+                            ui.label(i18n("URL:"));
+                            ui.add(TextEdit::singleline(&mut json_provider.url).desired_width(320.));
+                            ui.end_row();
+                            ui.label(i18n("Price field:"));
+                            ui.add(TextEdit::singleline(&mut json_provider.price_path));
+                            ui.end_row();
+                            ui.label(i18n("Market cap field:"));
+                            ui.add(TextEdit::singleline(&mut json_provider.market_cap_path));
+                            ui.end_row();
+                            ui.label(i18n("Volume field:"));
+                            ui.add(TextEdit::singleline(&mut json_provider.volume_path));
+                            ui.end_row();
+                            ui.label(i18n("24h change field:"));
+                            ui.add(TextEdit::singleline(&mut json_provider.change_path));
+                            ui.end_row();
+                        });
+
+                    ui.label(i18n("Use {currencies} in the URL for the comma-separated currency list and {currency} in field paths (e.g. kaspa.{currency})."));
+
+                    let error = if json_provider.url.trim().is_empty() {
+                        Some(i18n("Please specify the provider URL"))
+                    } else if json_provider.price_path.trim().is_empty() {
+                        Some(i18n("Please specify the price field"))
+                    } else {
+                        None
+                    };
+
+                    if let Some(error) = error {
+                        ui.colored_label(theme_color().warning_color, error);
+                    }
+
+                    let changed = *json_provider != core.settings.market.json_provider;
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(changed && error.is_none(), Button::new(i18n("Apply"))).clicked() {
+                            core.settings.market.json_provider = json_provider.clone();
+                            market_monitor_service.update_settings(&core.settings);
+                            core.store_settings();
+                        }
+                        if ui.add_enabled(changed, Button::new(i18n("Cancel"))).clicked() {
+                            *json_provider = core.settings.market.json_provider.clone();
+                        }
+                    });
+                });
+        }
+
+        if self.settings.market.provider == MarketDataProvider::CoinMarketCap {
+            CollapsingHeader::new(i18n("CoinMarketCap Settings"))
+                .default_open(true)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(i18n("API key:"));
+                        ui.add(TextEdit::singleline(&mut self.settings.market.coinmarketcap_api_key).password(true).desired_width(320.));
+                    });
+                    ui.label(i18n("CoinMarketCap requires an API key, available at https://coinmarketcap.com/api/"));
+
+                    if self.settings.market.coinmarketcap_api_key.trim().is_empty() {
+                        ui.colored_label(theme_color().warning_color, i18n("Please specify the API key"));
+                    }
+
+                    let changed = self.settings.market.coinmarketcap_api_key != core.settings.market.coinmarketcap_api_key;
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(changed, Button::new(i18n("Apply"))).clicked() {
+                            let api_key = self.settings.market.coinmarketcap_api_key.trim().to_string();
+                            self.settings.market.coinmarketcap_api_key = api_key.clone();
+                            core.settings.market.coinmarketcap_api_key = api_key;
+                            market_monitor_service.update_settings(&core.settings);
+                            market_monitor_service.request_available_currencies();
+                            core.store_settings();
+                        }
+                        if ui.add_enabled(changed, Button::new(i18n("Cancel"))).clicked() {
+                            self.settings.market.coinmarketcap_api_key = core.settings.market.coinmarketcap_api_key.clone();
+                        }
+                    });
+                });
+        }
+
+        let provider = self.settings.market.provider.clone();
+        if self.market_currencies_request.as_ref() != Some(&provider) {
+            market_monitor_service.request_available_currencies();
+            self.market_currencies_request = Some(provider);
+        }
+
+        ui.horizontal_wrapped(|ui|{
+            ui.label(i18n("Monitored currencies:"));
+
+            let mut remove = None;
+            self.settings.market.currencies.iter().enumerate().for_each(|(index, currency)| {
+                if ui.small_button(format!("{} ✕", currency.to_uppercase()))
+                    .on_hover_text(i18n("Remove"))
+                    .clicked() {
+                    remove = Some(index);
+                }
+            });
+
+            let mut add = None;
+            ui.menu_button(format!("{} ⏷", i18n("Add")), |ui| {
+                ui.add(TextEdit::singleline(&mut self.market_currency_filter).hint_text(i18n("Filter...")).desired_width(120.));
+                ScrollArea::vertical().max_height(240.).show(ui, |ui| {
+                    if let Some(available_currencies) = market_monitor_service.available_currencies() {
+                        let filter = self.market_currency_filter.to_lowercase();
+                        available_currencies.iter()
+                            .filter(|currency| !self.settings.market.currencies.contains(&currency.symbol.to_lowercase()))
+                            .filter(|currency| filter.is_empty() || currency.symbol.to_lowercase().contains(&filter) || currency.name.to_lowercase().contains(&filter))
+                            .for_each(|currency| {
+                                let text = if currency.name.eq_ignore_ascii_case(&currency.symbol) {
+                                    currency.symbol.to_uppercase()
+                                } else {
+                                    format!("{} - {}", currency.symbol.to_uppercase(), currency.name)
+                                };
+                                if ui.button(text).clicked() {
+                                    add = Some(currency.symbol.to_lowercase());
+                                    ui.close_menu();
+                                }
+                            });
+                    } else {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(i18n("Loading..."));
+                        });
+                        if ui.button(i18n("Retry")).clicked() {
+                            market_monitor_service.request_available_currencies();
+                        }
+                    }
+                });
+            });
+
+            if remove.is_some() || add.is_some() {
+                if let Some(index) = remove {
+                    self.settings.market.currencies.remove(index);
+                }
+                if let Some(currency) = add {
+                    self.settings.market.currencies.push(currency);
+                    self.market_currency_filter.clear();
+                }
+                core.settings.market.currencies = self.settings.market.currencies.clone();
+                market_monitor_service.update_settings(&core.settings);
+                core.store_settings();
+            }
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_kaspad_config(&mut self, core: &mut Core, ui: &mut Ui) -> Option<&'static str> {
+
+        let current = core.settings.node.kaspad_config.clone();
+        let config = &mut self.settings.node.kaspad_config;
+
+        let restart_marker = |ui: &mut Ui, changed: bool| {
+            if changed {
+                ui.label(RichText::new(egui_phosphor::light::ARROWS_CLOCKWISE).color(theme_color().warning_color))
+                    .on_hover_text(i18n("Requires node restart"));
+            }
+        };
+
+        let parse_peers = |text: &str| -> Vec<String> {
+            text.lines().map(str::trim).filter(|line| !line.is_empty()).map(String::from).collect()
+        };
+
+        CollapsingHeader::new(i18n("Node Options"))
+            .default_open(false)
+            .show(ui, |ui| {
+
+                Grid::new("kaspad_config_grid")
+                    .num_columns(3)
+                    .spacing([8.0,4.0])
+                    .show(ui, |ui| {
+
+                        ui.label(i18n("UTXO Index:"));
+                        ui.checkbox(&mut config.utxoindex, i18n("Enable UTXO index"));
+                        restart_marker(ui, config.utxoindex != current.utxoindex);
+                        ui.end_row();
+
+                        ui.label(i18n("Archival:"));
+                        ui.checkbox(&mut config.archival, i18n("Archival node (retain full history)"));
+                        restart_marker(ui, config.archival != current.archival);
+                        ui.end_row();
+
+                        ui.label(i18n("Outbound peers:"));
+                        ui.add(DragValue::new(&mut config.outbound_target).clamp_range(0..=KaspadConfig::MAX_PEERS));
+                        restart_marker(ui, config.outbound_target != current.outbound_target);
+                        ui.end_row();
+
+                        ui.label(i18n("Max inbound peers:"));
+                        ui.add(DragValue::new(&mut config.inbound_limit).clamp_range(0..=KaspadConfig::MAX_PEERS));
+                        restart_marker(ui, config.inbound_limit != current.inbound_limit);
+                        ui.end_row();
+
+                        ui.label(i18n("RPC max clients:"));
+                        ui.add(DragValue::new(&mut config.rpc_max_clients).clamp_range(1..=KaspadConfig::MAX_RPC_CLIENTS));
+                        restart_marker(ui, config.rpc_max_clients != current.rpc_max_clients);
+                        ui.end_row();
+
+                        ui.label(i18n("Performance metrics:"));
+                        ui.checkbox(&mut config.perf_metrics, i18n("Enable performance metrics"));
+                        restart_marker(ui, config.perf_metrics != current.perf_metrics);
+                        ui.end_row();
+
+                        ui.label(i18n("Log level:"));
+                        ui.menu_button(format!("{} ⏷", config.log_level), |ui| {
+                            NodeLogLevel::iter().for_each(|level| {
+                                if ui.button(level.to_string()).clicked() {
+                                    config.log_level = *level;
+                                    ui.close_menu();
+                                }
+                            });
+                        });
+                        restart_marker(ui, config.log_level != current.log_level);
+                        ui.end_row();
+
+                        ui.label(i18n("Connect only to:"));
+                        ui.add(TextEdit::multiline(&mut self.connect_peers).hint_text(i18n("One address per line")).desired_rows(2).font(FontId::monospace(14.0)));
+                        config.connect_peers = parse_peers(&self.connect_peers);
+                        restart_marker(ui, config.connect_peers != current.connect_peers);
+                        ui.end_row();
+
+                        ui.label(i18n("Additional peers:"));
+                        ui.add(TextEdit::multiline(&mut self.add_peers).hint_text(i18n("One address per line")).desired_rows(2).font(FontId::monospace(14.0)));
+                        config.add_peers = parse_peers(&self.add_peers);
+                        restart_marker(ui, config.add_peers != current.add_peers);
+                        ui.end_row();
+                    });
+
+                if !config.utxoindex {
+                    ui.colored_label(theme_color().warning_color, i18n("The wallet requires the UTXO index; wallet functionality will not be available without it."));
+                }
+                if !config.perf_metrics {
+                    ui.colored_label(theme_color().warning_color, i18n("Node metrics will not be available without performance metrics."));
+                }
+                if config.archival {
+                    ui.colored_label(theme_color().warning_color, i18n("Archival nodes require a large amount of disk space."));
+                }
+                if *config != current {
+                    ui.label(i18n("Changes to these options take effect after the node restarts."));
+                }
+            });
+
+        if let Err(err) = config.validate() {
+            ui.colored_label(theme_color().error_color, err);
+            Some(i18n("Invalid node configuration"))
+        } else {
+            None
+        }
+    }
+
+    fn render_alert_settings(&mut self, core: &mut Core, ui: &mut Ui) {
+
+        let mut changed = false;
+
+        changed |= ui.checkbox(&mut self.settings.alerts.enable, i18n("Enable alerts")).changed();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            changed |= ui.checkbox(&mut self.settings.alerts.os_notifications, i18n("Show desktop notifications")).changed();
+        }
+
+        let network_type = NetworkType::from(core.network());
+        let account_name = |id: &str| {
+            core.account_collection()
+                .as_ref()
+                .and_then(|collection| collection.iter().find(|account| account.id().to_hex() == id).map(|account| account.name_or_id()))
+                .unwrap_or_else(|| format!("{}...", &id[0..id.len().min(12)]))
+        };
+
+        if !self.settings.market_monitor && self.settings.alerts.rules.iter().any(|rule| rule.currency().is_some()) {
+            ui.colored_label(theme_color().warning_color, i18n("Price alerts require the Market Monitor to be enabled"));
+        }
+
+        let mut remove = None;
+        if self.settings.alerts.rules.is_empty() {
+            ui.label(i18n("No alerts defined"));
+        } else {
+            self.settings.alerts.rules.iter().enumerate().for_each(|(index, rule)| {
+                ui.horizontal(|ui| {
+                    if ui.small_button("✕").on_hover_text(i18n("Remove")).clicked() {
+                        remove = Some(index);
+                    }
+                    ui.label(rule.describe(&network_type, account_name));
+                });
+            });
+        }
+
+        if let Some(index) = remove {
+            self.settings.alerts.rules.remove(index);
+            changed = true;
+        }
+
+        ui.separator();
+
+        let editor = &mut self.alert_editor;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n("New alert:"));
+            AlertRuleKind::iter().for_each(|kind| {
+                ui.radio_value(&mut editor.kind, *kind, kind.to_string());
+            });
+        });
+
+        match editor.kind {
+            AlertRuleKind::PriceAbove | AlertRuleKind::PriceBelow => {
+                let mut currencies = self.settings.market.currencies.clone();
+                if !currencies.contains(&self.settings.market.fiat_currency) {
+                    currencies.push(self.settings.market.fiat_currency.clone());
+                }
+                if editor.currency.is_empty() {
+                    editor.currency = self.settings.market.fiat_currency.clone();
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(i18n("Currency:"));
+                    ui.menu_button(format!("{} ⏷", editor.currency.to_uppercase()), |ui| {
+                        currencies.iter().for_each(|currency| {
+                            if ui.button(currency.to_uppercase()).clicked() {
+                                editor.currency = currency.clone();
+                                ui.close_menu();
+                            }
+                        });
+                    });
+                    ui.label(i18n("Price:"));
+                    ui.add(TextEdit::singleline(&mut editor.threshold).desired_width(96.));
+                });
+            }
+            AlertRuleKind::FundsReceived | AlertRuleKind::BalanceBelow => {
+                ui.horizontal(|ui| {
+                    ui.label(i18n("Account:"));
+                    let selected = editor.account.as_deref().map(account_name).unwrap_or_else(|| i18n("Any account").to_string());
+                    ui.menu_button(format!("{} ⏷", selected), |ui| {
+                        if ui.button(i18n("Any account")).clicked() {
+                            editor.account = None;
+                            ui.close_menu();
+                        }
+                        if let Some(account_collection) = core.account_collection() {
+                            account_collection.iter().for_each(|account| {
+                                if ui.button(account.name_or_id()).clicked() {
+                                    editor.account = Some(account.id().to_hex());
+                                    ui.close_menu();
+                                }
+                            });
+                        }
+                    });
+
+                    if editor.kind == AlertRuleKind::BalanceBelow {
+                        ui.label(i18n("Minimum balance:"));
+                        ui.add(TextEdit::singleline(&mut editor.threshold).desired_width(96.));
+                        ui.label(kaspa_suffix(&network_type));
+                    }
+                });
+            }
+            AlertRuleKind::MetricAbove | AlertRuleKind::MetricBelow => {
+                ui.horizontal(|ui| {
+                    ui.label(i18n("Metric:"));
+                    let selected = editor.metric.map(|metric| i18n(metric.title().0)).unwrap_or(i18n("Select"));
+                    ui.menu_button(format!("{} ⏷", selected), |ui| {
+                        ScrollArea::vertical().max_height(320.).show(ui, |ui| {
+                            for group in MetricGroup::list() {
+                                ui.label(RichText::new(i18n(group.title())).strong());
+                                for metric in group.metrics() {
+                                    if ui.button(i18n(metric.title().0)).clicked() {
+                                        editor.metric = Some(*metric);
+                                        ui.close_menu();
+                                    }
+                                }
+                            }
+                        });
+                    });
+                    ui.label(i18n("Threshold:"));
+                    ui.add(TextEdit::singleline(&mut editor.threshold).desired_width(96.));
+                    ui.label(i18n("For (seconds):"));
+                    ui.add(TextEdit::singleline(&mut editor.duration).desired_width(64.));
+                });
+            }
+            AlertRuleKind::SyncStalled => {
+                ui.horizontal(|ui| {
+                    ui.label(i18n("DAA score not advancing for (seconds):"));
+                    ui.add(TextEdit::singleline(&mut editor.duration).desired_width(64.));
+                });
+            }
+        }
+
+        let rule = editor.try_rule();
+        ui.horizontal(|ui| {
+            if ui.add_enabled(rule.is_ok(), Button::new(i18n("Add Alert"))).clicked() {
+                if let Ok(rule) = rule.as_ref() {
+                    self.settings.alerts.rules.push(rule.clone());
+                    editor.threshold.clear();
+                    editor.duration.clear();
+                    changed = true;
+                }
+            }
+            if let Err(err) = rule.as_ref() {
+                if !editor.threshold.is_empty() || !editor.duration.is_empty() {
+                    ui.colored_label(theme_color().warning_color, *err);
+                }
+            }
+        });
+
+        if changed {
+            core.settings.alerts = self.settings.alerts.clone();
+            self.runtime.market_monitor_service().update_settings(&core.settings);
+            self.runtime.metrics_service().update_alert_settings(&core.settings.alerts);
+            core.store_settings();
+        }
+
+        CollapsingHeader::new(i18n("Alert History"))
+            .default_open(false)
+            .show(ui, |ui| {
+                let history = self.runtime.metrics_service().alert_history();
+                if history.is_empty() {
+                    ui.label(i18n("No alerts have been triggered"));
+                    return;
+                }
+
+                if ui.small_button(i18n("Clear")).clicked() {
+                    self.runtime.metrics_service().clear_alert_history();
+                }
+
+                ScrollArea::vertical()
+                    .id_source("alert_history")
+                    .max_height(240.)
+                    .show(ui, |ui| {
+                        for event in history {
+                            ui.horizontal(|ui| {
+                                let time = chrono::DateTime::<chrono::Utc>::from_timestamp((event.timestamp / 1000.0) as i64, 0)
+                                    .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                                    .unwrap_or_default();
+                                let color = ui.visuals().weak_text_color();
+                                ui.label(RichText::new(time).color(color));
+                                ui.label(event.message);
+                            });
+                        }
+                    });
+            });
+    }
+
+    fn render_connection_profiles(&mut self, core: &mut Core, ui: &mut Ui) {
+
+        let mut apply = None;
+        let mut remove = None;
+
+        if core.settings.connection_profiles.is_empty() {
+            ui.label(i18n("No connection profiles defined"));
+        } else {
+            core.settings.connection_profiles.iter().enumerate().for_each(|(index, profile)| {
+                ui.horizontal(|ui| {
+                    if ui.small_button("✕").on_hover_text(i18n("Remove")).clicked() {
+                        remove = Some(index);
+                    }
+                    let active = profile.matches(&core.settings.node);
+                    if ui.add_enabled(!active, Button::new(i18n("Apply")).small()).clicked() {
+                        apply = Some(index);
+                    }
+                    if active {
+                        ui.label(RichText::new(&profile.name).strong());
+                    } else {
+                        ui.label(&profile.name);
+                    }
+                    ui.label(RichText::new(profile.describe()).small());
+                });
+            });
+        }
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(i18n("Save current connection as:"));
+            ui.add(TextEdit::singleline(&mut self.profile_name).desired_width(160.));
+
+            let name = self.profile_name.trim();
+            let exists = core.settings.connection_profiles.iter().any(|profile| profile.name == name);
+            let caption = if exists { i18n("Replace") } else { i18n("Save") };
+            if ui.add_enabled(!name.is_empty(), Button::new(caption)).clicked() {
+                let profile = ConnectionProfile::new(name, &core.settings.node);
+                if let Some(existing) = core.settings.connection_profiles.iter_mut().find(|existing| existing.name == profile.name) {
+                    *existing = profile;
+                } else {
+                    core.settings.connection_profiles.push(profile);
+                }
+                self.settings.connection_profiles = core.settings.connection_profiles.clone();
+                self.profile_name.clear();
+                core.store_settings();
+            }
+        });
+
+        if self.settings.node.compare(&core.settings.node).is_some() {
+            ui.label(i18n("Profiles are saved from the applied node settings; pending changes are not included."));
+        }
+
+        if let Some(index) = remove {
+            core.settings.connection_profiles.remove(index);
+            self.settings.connection_profiles = core.settings.connection_profiles.clone();
+            core.store_settings();
+        }
+
+        if let Some(index) = apply {
+            // applied by the core, as the node settings of this module are updated as well
+            let profile = core.settings.connection_profiles[index].clone();
+            core.sender().try_send(Events::ApplyConnectionProfile(Box::new(profile))).unwrap();
+        }
+    }
+
+    fn render_user_servers(&mut self, ui: &mut Ui) {
+        let mut servers = user_servers().lock().unwrap().clone();
+        let mut changed = false;
+        let mut remove = None;
+
+        ui.label(i18n("User servers are listed along with the public nodes of the same network."));
+
+        if servers.is_empty() {
+            ui.label(i18n("No user servers defined"));
+        } else {
+            Grid::new("user_servers_grid")
+                .num_columns(5)
+                .striped(true)
+                .spacing([16.0, 4.0])
+                .show(ui, |ui| {
+                    for (index, server) in servers.iter_mut().enumerate() {
+                        let mut enable = server.enable.unwrap_or(true);
+                        if ui.checkbox(&mut enable, "").on_hover_text(i18n("Enable")).changed() {
+                            server.enable = Some(enable);
+                            changed = true;
+                        }
+                        ui.label(server.to_string());
+                        ui.label(server.network.to_string());
+                        ui.label(&server.address);
+                        ui.horizontal(|ui| {
+                            if ui.small_button(i18n("Edit")).clicked() {
+                                self.server_editor = ServerEditor::edit(index, server);
+                            }
+                            if ui.small_button("✕").on_hover_text(i18n("Remove")).clicked() {
+                                remove = Some(index);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        }
+
+        if let Some(index) = remove {
+            servers.remove(index);
+            if self.server_editor.index.is_some() {
+                self.server_editor = ServerEditor::default();
+            }
+            changed = true;
+        }
+
+        ui.separator();
+
+        let editor = &mut self.server_editor;
+        let editing = editor.index.is_some();
+        ui.label(if editing { i18n("Edit server:") } else { i18n("Add server:") });
+
+        Grid::new("user_server_editor_grid")
+            .num_columns(2)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(i18n("Name:"));
+                ui.add(TextEdit::singleline(&mut editor.name).desired_width(240.));
+                ui.end_row();
+
+                ui.label(i18n("Location:"));
+                ui.add(TextEdit::singleline(&mut editor.location).desired_width(240.));
+                ui.end_row();
+
+                ui.label(i18n("Network:"));
+                ui.horizontal_wrapped(|ui| {
+                    Network::iter().for_each(|network| {
+                        ui.radio_value(&mut editor.network, *network, network.name());
+                    });
+                });
+                ui.end_row();
+
+                ui.label(i18n("wRPC Encoding:"));
+                ui.horizontal(|ui| {
+                    WrpcEncoding::iter().for_each(|encoding| {
+                        ui.radio_value(&mut editor.protocol, *encoding, encoding.to_string());
+                    });
+                });
+                ui.end_row();
+
+                ui.label(i18n("wRPC URL:"));
+                ui.add(TextEdit::singleline(&mut editor.address).desired_width(240.));
+                ui.end_row();
+
+                ui.label("");
+                ui.checkbox(&mut editor.manual, i18n("Exclude from random node selection"));
+                ui.end_row();
+            });
+
+        let enable = editor.index.and_then(|index| servers.get(index)).map(|server| server.enable.unwrap_or(true)).unwrap_or(true);
+        let server = editor.try_server(enable, &servers);
+        ui.horizontal(|ui| {
+            let caption = if editing { i18n("Save") } else { i18n("Add Server") };
+            if ui.add_enabled(server.is_ok(), Button::new(caption)).clicked() {
+                if let Ok(server) = server.as_ref() {
+                    match editor.index.and_then(|index| servers.get_mut(index)) {
+                        Some(existing) => *existing = server.clone(),
+                        None => servers.push(server.clone()),
+                    }
+                    *editor = ServerEditor::default();
+                    changed = true;
+                }
+            }
+            if editing && ui.button(i18n("Cancel")).clicked() {
+                *editor = ServerEditor::default();
+            }
+            if let Err(err) = server.as_ref() {
+                if !editor.address.is_empty() {
+                    ui.colored_label(theme_color().warning_color, *err);
+                }
+            }
+        });
+
+        if changed {
+            update_user_servers(servers);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use crate::servers::{export_user_servers, import_user_servers};
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(i18n("Import Server List")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new().add_filter("TOML", &["toml"]).pick_file() {
+                        match std::fs::read_to_string(&path).map_err(Error::from).and_then(|toml| import_user_servers(&toml)) {
+                            Ok(count) => {
+                                runtime().notify(UserNotification::success(format!("{} {count} {}", i18n("Imported"), i18n("servers"))));
+                            }
+                            Err(err) => {
+                                runtime().error(format!("{} {err}", i18n("Unable to import server list:")));
+                            }
+                        }
+                    }
+                }
+
+                if ui.button(i18n("Export Server List")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new().set_file_name("Servers.toml").add_filter("TOML", &["toml"]).save_file() {
+                        if let Err(err) = export_user_servers().and_then(|toml| Ok(std::fs::write(&path, toml)?)) {
+                            runtime().error(format!("{} {err}", i18n("Unable to export server list:")));
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_prometheus_settings(&mut self, core: &mut Core, ui: &mut Ui) {
+        use crate::runtime::services::metrics_monitor::PROMETHEUS_DEFAULT_PORT;
+
+        let metrics_service = self.runtime.metrics_service().clone();
+
+        ui.checkbox(&mut self.settings.prometheus.enable, i18n("Publish node metrics on a Prometheus endpoint"));
+        if self.settings.prometheus.enable {
+            CollapsingHeader::new(i18n("Network Interface & Port"))
+                .default_open(true)
+                .show(ui, |ui| {
+                    self.prometheus_network_interface.ui(ui);
+                    ui.label(format!("{} {PROMETHEUS_DEFAULT_PORT}", i18n("Default port:")));
+                });
+        }
+
+        let network_interface = NetworkInterfaceConfig::try_from(&self.prometheus_network_interface);
+        match network_interface {
+            Err(_) => {
+                ui.label(RichText::new(i18n("Invalid network interface configuration")).color(theme_color().error_color));
+            }
+            Ok(network_interface) => {
+                self.settings.prometheus.network_interface = network_interface;
+                if self.settings.prometheus != core.settings.prometheus {
+                    ui.add_space(8.);
+                    if let Some(response) = ui.confirm_medium_apply_cancel(Align::Max) {
+                        match response {
+                            Confirm::Ack => {
+                                core.settings.prometheus = self.settings.prometheus.clone();
+                                metrics_service.update_prometheus_settings(&core.settings.prometheus);
+                                core.store_settings();
+                            },
+                            Confirm::Nack => {
+                                self.settings.prometheus = core.settings.prometheus.clone();
+                                self.prometheus_network_interface = NetworkInterfaceEditor::from(&self.settings.prometheus.network_interface);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(address) = metrics_service.prometheus_address() {
+            ui.label(format!("{} http://{address}/metrics", i18n("Serving metrics at")));
+        }
+    }
+
+    fn render_server_health(&mut self, core: &mut Core, ui: &mut Ui) {
+        use egui_phosphor::light::{CHECK, WARNING, X};
+
+        let server_monitor_service = self.runtime.server_monitor_service().clone();
+
+        let mut changed = false;
+        changed |= ui.checkbox(&mut self.settings.server_monitor.enable, i18n("Monitor public node health")).changed();
+        ui.add_enabled_ui(self.settings.server_monitor.enable, |ui| {
+            changed |= ui.checkbox(&mut self.settings.server_monitor.automatic_failover, i18n("Switch to another node if the current node disconnects or stalls")).changed();
+        });
+
+        if changed {
+            core.settings.server_monitor = self.settings.server_monitor.clone();
+            server_monitor_service.update_settings(&core.settings.server_monitor);
+            core.store_settings();
+        }
+
+        let health = server_monitor_service.health();
+        let is_probing = server_monitor_service.is_probing();
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!is_probing, Button::new(i18n("Probe Now"))).clicked() {
+                server_monitor_service.probe();
+            }
+            if is_probing {
+                ui.add(egui::Spinner::new());
+            } else if let Some(timestamp) = health.timestamp {
+                ui.label(format!("{} {} {}", i18n("Updated"), timestamp.elapsed().as_secs(), i18n("seconds ago")));
+            }
+        });
+
+        if health.network != Some(core.settings.node.network) || health.servers.is_empty() {
+            ui.label(i18n("No health information available"));
+            return;
+        }
+
+        let current = self.runtime.kaspa_service().rpc_url();
+
+        Grid::new("public_node_health")
+            .num_columns(6)
+            .striped(true)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(RichText::new(i18n("Server")).strong());
+                ui.label(RichText::new(i18n("Latency")).strong());
+                ui.label(RichText::new(i18n("Version")).strong());
+                ui.label(RichText::new(i18n("Synced")).strong());
+                ui.label(RichText::new(i18n("DAA Score")).strong());
+                ui.label(RichText::new(i18n("Status")).strong());
+                ui.end_row();
+
+                let mut servers = health.servers.iter().collect::<Vec<_>>();
+                servers.sort_by(|a, b| {
+                    let a = a.score().unwrap_or(f64::MAX);
+                    let b = b.score().unwrap_or(f64::MAX);
+                    a.total_cmp(&b)
+                });
+
+                for server_health in servers {
+                    let is_current = current.as_ref().map(|url| server_health.server.matches_url(url)).unwrap_or(false);
+                    let name = if is_current {
+                        RichText::new(format!("{} {CHECK}", server_health.server)).strong()
+                    } else {
+                        RichText::new(server_health.server.to_string())
+                    };
+                    ui.label(name).on_hover_text(&server_health.server.address);
+                    ui.label(server_health.latency.map(|latency| format!("{} ms", latency.as_millis())).unwrap_or_else(|| "-".to_string()));
+                    ui.label(server_health.server_version.clone().unwrap_or_else(|| "-".to_string()));
+                    ui.label(match server_health.is_synced {
+                        Some(true) => i18n("Yes"),
+                        Some(false) => i18n("No"),
+                        None => "-",
+                    });
+                    ui.label(server_health.virtual_daa_score.map(|score| score.separated_string()).unwrap_or_else(|| "-".to_string()));
+                    if let Some(error) = server_health.error.as_ref() {
+                        ui.colored_label(theme_color().error_color, format!("{X} {}", i18n("Unreachable"))).on_hover_text(error);
+                    } else if !server_health.is_healthy() {
+                        let reason = if server_health.has_utxo_index == Some(false) { i18n("No UTXO index") } else { i18n("Not synced") };
+                        ui.colored_label(theme_color().warning_color, format!("{WARNING} {reason}"));
+                    } else {
+                        ui.label(format!("{CHECK} {}", i18n("OK")));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
     pub fn render_remote_settings(core: &mut Core, ui: &mut Ui, settings : &mut NodeSettings) -> Option<&'static str> {
 
         let mut node_settings_error = None;
@@ -48,6 +1060,38 @@ impl Settings {
             match settings.connection_config_kind {
                 NodeConnectionConfigKind::Custom => {
 
+                    cfg_if! {
+                        if #[cfg(not(target_arch = "wasm32"))] {
+                            ui.horizontal(|ui|{
+                                ui.label(i18n("RPC Protocol:"));
+                                [RpcKind::Wrpc, RpcKind::Grpc].into_iter().for_each(|kind| {
+                                    ui.radio_value(&mut settings.rpc_kind, kind, kind.to_string());
+                                });
+                            });
+                        }
+                    }
+
+                    if settings.rpc_kind == RpcKind::Grpc && cfg!(not(target_arch = "wasm32")) {
+                        CollapsingHeader::new(i18n("gRPC Connection Settings"))
+                            .default_open(true)
+                            .show(ui, |ui| {
+
+                                ui.horizontal(|ui|{
+                                    ui.label(i18n("gRPC URL:"));
+                                    ui.add(TextEdit::singleline(&mut settings.grpc_url));
+                                });
+
+                                #[cfg(not(target_arch = "wasm32"))]
+                                if let Err(err) = crate::runtime::services::kaspa::grpc::parse_grpc_url(&settings.grpc_url, settings.network) {
+                                    ui.label(
+                                        RichText::new(format!("{err}"))
+                                            .color(theme_color().warning_color),
+                                    );
+                                    node_settings_error = Some(i18n("Invalid gRPC URL"));
+                                }
+                            });
+                    } else {
+
                     CollapsingHeader::new(i18n("wRPC Connection Settings"))
                         .default_open(true)
                         .show(ui, |ui| {
@@ -75,6 +1119,7 @@ impl Settings {
                                 node_settings_error = Some(i18n("Invalid wRPC URL"));
                             }
                         });
+                    }
                     // cfg_if! {
                     //     if #[cfg(not(target_arch = "wasm32"))] {
                     //         ui.horizontal_wrapped(|ui|{
@@ -283,9 +1328,15 @@ impl Settings {
                                         ui.label(i18n("Resulting daemon arguments:"));
                                         ui.add_space(4.);
 
-                                        let config = Config::from(self.settings.node.clone());
-                                        let config = Vec::<String>::from(config).join(" ");
-                                        ui.label(RichText::new(config).code().font(FontId::monospace(14.0)).color(theme_color().strong_color));
+                                        match Config::try_from(self.settings.node.clone()) {
+                                            Ok(config) => {
+                                                let config = Vec::<String>::from(config).join(" ");
+                                                ui.label(RichText::new(config).code().font(FontId::monospace(14.0)).color(theme_color().strong_color));
+                                            }
+                                            Err(err) => {
+                                                ui.colored_label(theme_color().error_color, err.to_string());
+                                            }
+                                        }
                                         ui.add_space(4.);
 
 
@@ -339,6 +1390,20 @@ impl Settings {
 
                 if self.settings.node.node_kind == KaspadNodeKind::Remote {
                     node_settings_error = Self::render_remote_settings(core, ui, &mut self.settings.node);
+
+                    if self.settings.node.connection_config_kind != NodeConnectionConfigKind::Custom {
+                        CollapsingHeader::new(i18n("Public Node Health"))
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                self.render_server_health(core, ui);
+                            });
+                    }
+
+                    CollapsingHeader::new(i18n("User Servers"))
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            self.render_user_servers(ui);
+                        });
                 }
 
                 #[cfg(not(target_arch = "wasm32"))]
@@ -364,6 +1429,11 @@ impl Settings {
                                                 // - TODO
                                                 // ui.add(TextEdit::singleline(&mut self.settings.node.grpc_network_interface));
                                             }
+
+                                            ui.checkbox(&mut self.settings.node.enable_unsafe_rpc, i18n("Allow unsafe RPC calls (required for peer management)"));
+                                            if self.settings.node.enable_unsafe_rpc {
+                                                ui.colored_label(theme_color().warning_color, i18n("Unsafe RPC calls allow any RPC client to manage node peers; do not enable them if the node RPC interfaces are reachable from other hosts"));
+                                            }
                                         });
 
                                 });
@@ -376,7 +1446,11 @@ impl Settings {
                                             ui.checkbox(&mut self.settings.node.enable_upnp, i18n("Enable UPnP"));
                                         });
                                     });
-                                });
+
+                                if let Some(error) = self.render_kaspad_config(core, ui) {
+                                    node_settings_error = Some(error);
+                                }
+                            });
                         });
                 } // is_config_capable
 
@@ -397,6 +1471,7 @@ impl Settings {
                     if matches!(response, Confirm::Nack) {
                         self.settings.node = core.settings.node.clone();
                         self.grpc_network_interface = NetworkInterfaceEditor::from(&self.settings.node.grpc_network_interface);
+                        self.load_kaspad_config_editor();
                     }
                 }
 
@@ -427,6 +1502,7 @@ impl Settings {
                             Confirm::Nack => {
                                 self.settings = core.settings.clone();
                                 self.grpc_network_interface = NetworkInterfaceEditor::from(&self.settings.node.grpc_network_interface);
+                                self.load_kaspad_config_editor();
                             }
                         }
                     }
@@ -540,6 +1616,12 @@ impl Settings {
 
         self.render_node_settings(core,ui);
 
+        CollapsingHeader::new(i18n("Connection Profiles"))
+            .default_open(false)
+            .show(ui, |ui| {
+                self.render_connection_profiles(core, ui);
+            });
+
         self.render_ui_settings(core,ui);
 
         CollapsingHeader::new(i18n("Services"))
@@ -554,6 +1636,36 @@ impl Settings {
                             self.runtime.market_monitor_service().enable(core.settings.market_monitor);
                             core.store_settings();
                         }
+
+                        if self.settings.market_monitor {
+                            ui.horizontal(|ui| {
+                                ui.label(i18n("Preferred fiat currency:"));
+                                ui.menu_button(
+                                    format!("{} ⏷", self.settings.market.fiat_currency.to_uppercase()),
+                                    |ui| {
+                                        ScrollArea::vertical().max_height(240.).show(ui, |ui| {
+                                            crate::market::FIAT_CURRENCIES.iter().for_each(|currency| {
+                                                if ui.button(currency.to_uppercase()).clicked() {
+                                                    self.settings.market.fiat_currency = currency.to_string();
+                                                    core.settings.market.fiat_currency = currency.to_string();
+                                                    self.runtime.market_monitor_service().update_settings(&core.settings);
+                                                    core.store_settings();
+                                                    ui.close_menu();
+                                                }
+                                            });
+                                        });
+                                    },
+                                );
+                            });
+
+                            self.render_market_settings(core, ui);
+                        }
+                    });
+
+                CollapsingHeader::new(i18n("Alerts"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        self.render_alert_settings(core, ui);
                     });
 
                 #[cfg(not(target_arch = "wasm32"))]
@@ -566,6 +1678,13 @@ impl Settings {
                             core.store_settings();
                         }
                     });    
+
+                #[cfg(not(target_arch = "wasm32"))]
+                CollapsingHeader::new(i18n("Prometheus Endpoint"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        self.render_prometheus_settings(core, ui);
+                    });
             });
 
         #[cfg(not(target_arch = "wasm32"))]
@@ -1,133 +1,374 @@
 use crate::imports::*;
+use super::rpc_api;
+use kaspa_addresses::Prefix;
+use kaspa_rpc_core::RpcUtxosByAddressesEntry;
 
-pub enum State {
-    Select,
-    CheckBalanceStart,
-    CheckBalanceProcess,
-    CheckBalanceResult(Result<u64>),
-}
+/// Maximum number of concurrent address queries
+const MAX_CONCURRENT_REQUESTS : usize = 8;
 
-pub struct Tools {
-    #[allow(dead_code)]
-    runtime: Runtime,
-    state: State,
-    address_string: String,
-    address: Option<Address>,
+#[derive(Clone)]
+pub struct AddressBalance {
+    pub address : Address,
+    pub balance : u64,
+    pub utxos : Vec<RpcUtxosByAddressesEntry>,
+    pub error : Option<String>,
 }
 
-impl Tools {
-    pub fn new(runtime: Runtime) -> Self {
-        Self {
-            runtime,
-            state: State::Select,
-            address_string: Default::default(),
-            address: None,
-        }
+impl AddressBalance {
+    pub fn largest_utxo(&self) -> Option<u64> {
+        self.utxos.iter().map(|entry| entry.utxo_entry.amount).max()
     }
 }
 
-impl ModuleT for Tools {
-    fn render(
-        &mut self,
-        core: &mut Wallet,
-        _ctx: &egui::Context,
-        _frame: &mut eframe::Frame,
-        ui: &mut egui::Ui,
-    ) {
-        match &self.state {
-            State::Select => {
-                ui.heading("Tools");
-                ui.separator();
-                ui.label("This is the tools page");
-                if ui.button("Check Balance").clicked() {
-                    self.state = State::CheckBalanceStart;
-                }
+/// Addresses parsed from the input text, updated when the text or the network changes.
+#[derive(Default)]
+struct ParsedAddresses {
+    text : String,
+    network_type : Option<NetworkType>,
+    addresses : Vec<Address>,
+    invalid : Vec<String>,
+}
+
+#[derive(Default)]
+pub struct CheckBalance {
+    addresses : String,
+    parsed : ParsedAddresses,
+    result : Option<std::result::Result<Arc<Vec<AddressBalance>>, String>>,
+    selected : Option<Address>,
+}
+
+impl CheckBalance {
+
+    pub fn reset(&mut self) {
+        self.result = None;
+        self.selected = None;
+    }
+
+    pub fn render(&mut self, core : &mut Core, ui : &mut Ui) {
+        let balance_result = Payload::<Result<Arc<Vec<AddressBalance>>>>::new("tools_check_balance_result");
+        if let Some(result) = balance_result.take() {
+            self.result = Some(result.map_err(|err| err.to_string()));
+        }
+
+        let network_type = NetworkType::from(core.network());
+
+        ui.label(i18n("Check the balances of a list of addresses, one per line or separated by commas (requires the connected node to run with --utxoindex)"));
+        ui.space();
+
+        ui.add(TextEdit::multiline(&mut self.addresses)
+            .hint_text(i18n("Paste or import addresses..."))
+            .font(FontId::monospace(12.))
+            .desired_rows(6)
+            .desired_width(f32::INFINITY)
+        );
+
+        if self.parsed.text != self.addresses || self.parsed.network_type != Some(network_type) {
+            let (addresses, invalid) = parse_addresses(&self.addresses, network_type);
+            self.parsed = ParsedAddresses { text : self.addresses.clone(), network_type : Some(network_type), addresses, invalid };
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(format!("{} {}", self.parsed.addresses.len().separated_string(), i18n("addresses")));
+            if !self.parsed.invalid.is_empty() {
+                ui.colored_label(theme_color().error_color, format!("{} {}", self.parsed.invalid.len().separated_string(), i18n("invalid entries")))
+                    .on_hover_ui(|ui| { ui.label(self.parsed.invalid.join("\n")); });
             }
-            State::CheckBalanceStart => {
-                Panel::new(self)
-                    .with_caption("Check Balance")
-                    .with_back(|this| {
-                        this.state = State::Select;
-                    })
-                    .with_close(|_| {
-                        wallet.back();
-                    })
-                    .with_header(|_, ui| {
-                        ui.label("Check Balance for a specific address");
-                    })
-                    .with_body(|this, ui| {
-                        let address_string = this.address_string.clone();
-                        ui.add(
-                            TextEdit::singleline(&mut this.address_string)
-                                .hint_text("Wallet Name...")
-                                .vertical_align(Align::Center),
-                        );
-
-                        if address_string != this.address_string {
-                            this.address_string = address_string.clone();
-                            this.address = Address::try_from(address_string).ok();
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let enabled = !self.parsed.addresses.is_empty() && !balance_result.is_pending();
+                if ui.add_enabled(enabled, Button::new(i18n("Check Balances"))).clicked() {
+                    self.result = None;
+                    self.selected = None;
+                    let addresses = self.parsed.addresses.clone();
+                    spawn_with_result(&balance_result, async move { check_balances(addresses).await });
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let results = self.result.as_ref().and_then(|result| result.as_ref().ok()).cloned();
+                    if ui.add_enabled(results.is_some(), Button::new(i18n("Export CSV"))).clicked() {
+                        if let Some(results) = results {
+                            if let Some(target) = rfd::FileDialog::new().set_file_name("balances.csv").add_filter("CSV", &["csv"]).save_file() {
+                                match export_csv(&results, &target) {
+                                    Ok(()) => runtime().notify(UserNotification::success(format!("{} {}", i18n("Balances exported to"), target.display()))),
+                                    Err(err) => runtime().error(format!("{} {err}", i18n("Unable to export balances:"))),
+                                }
+                            }
                         }
-                    })
-                    .with_footer(|this, ui| {
-                        if ui
-                            .large_button_enabled(this.address.is_some(), "Check Balance")
-                            .clicked()
-                        {
-                            this.state = State::CheckBalanceProcess;
+                    }
+
+                    if ui.button(i18n("Import")).clicked() {
+                        if let Some(path) = rfd::FileDialog::new().add_filter("Addresses", &["csv", "txt"]).pick_file() {
+                            match std::fs::read_to_string(&path) {
+                                Ok(text) => {
+                                    // keep only valid addresses, dropping CSV headers and other columns
+                                    let (imported, _) = parse_addresses(&text, network_type);
+                                    let imported = imported.iter().map(|address| address.to_string()).collect::<Vec<_>>();
+                                    if !self.addresses.trim().is_empty() {
+                                        self.addresses.push('\n');
+                                    }
+                                    self.addresses.push_str(&imported.join("\n"));
+                                    runtime().notify(UserNotification::info(format!("{} {} {}", i18n("Imported"), imported.len(), i18n("addresses"))).short());
+                                }
+                                Err(err) => runtime().error(format!("{} {err}", i18n("Unable to import addresses:"))),
+                            }
                         }
-                    })
-                    .render(ui);
+                    }
+                }
+
+                if ui.button(i18n("Clear")).clicked() {
+                    self.addresses.clear();
+                    self.reset();
+                }
+            });
+        });
+        ui.separator();
+
+        if balance_result.is_pending() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(i18n("Checking balances..."));
+            });
+            return;
+        }
+
+        let results = match self.result.as_ref() {
+            Some(Ok(results)) => results.clone(),
+            Some(Err(err)) => {
+                ui.label(RichText::new(err).color(theme_color().error_color));
+                return;
             }
-            State::CheckBalanceProcess => {
-                Panel::new(self)
-                    .with_caption("Check Balance")
-                    .with_back(|this| {
-                        this.state = State::Select;
-                    })
-                    .with_close(|_| {
-                        wallet.back();
-                    })
-                    .with_header(|_, ui| {
-                        ui.label("Check Balance for a specific address");
-                    })
-                    .with_body(|this, _ui| {
-                        let address_balance_result =
-                            Payload::<Result<u64>>::new("tools_check_balance_result");
-                        if !address_balance_result.is_pending() {
-                            let _wallet = this.runtime.wallet().clone();
-                            spawn_with_result(&address_balance_result, async move {
-                                todo!("CheckBalanceProcess")
-                            });
-                        }
+            None => return,
+        };
+
+        let total = results.iter().map(|result| result.balance).sum::<u64>();
+        let utxos = results.iter().map(|result| result.utxos.len()).sum::<usize>();
+        let errors = results.iter().filter(|result| result.error.is_some()).count();
+
+        ui.horizontal_wrapped(|ui| {
+            let color = theme_color().node_data_color;
+            ui.label(i18n("Total:"));
+            ui.colored_label(color, sompi_to_kaspa_string_with_suffix(total, &network_type));
+            ui.label(i18n("Addresses:"));
+            ui.colored_label(color, results.len().separated_string());
+            ui.label(i18n("UTXOs:"));
+            ui.colored_label(color, utxos.separated_string());
+            if errors > 0 {
+                ui.label(i18n("Errors:"));
+                ui.colored_label(theme_color().error_color, errors.separated_string());
+            }
+        });
+        ui.space();
+
+        if let Some(selected) = self.selected.as_ref() {
+            if let Some(result) = results.iter().find(|result| &result.address == selected) {
+                let mut close = false;
+                SidePanel::right("tools_check_balance_utxos")
+                    .resizable(true)
+                    .default_width(360.)
+                    .min_width(280.)
+                    .show_inside(ui, |ui| {
+                        close = render_utxos(ui, result, &network_type);
+                    });
+                if close {
+                    self.selected = None;
+                }
+            }
+        }
+
+        let mut selected = None;
 
-                        if let Some(balance_result) = address_balance_result.take() {
-                            this.state = State::CheckBalanceResult(balance_result);
+        egui::ScrollArea::vertical()
+            .id_source("tools_check_balance_results")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                Grid::new("tools_check_balance_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(i18n("Address")).strong());
+                        ui.label(RichText::new(i18n("Balance")).strong());
+                        ui.label(RichText::new(i18n("UTXOs")).strong());
+                        ui.label(RichText::new(i18n("Largest UTXO")).strong());
+                        ui.end_row();
+
+                        for result in results.iter() {
+                            let response = ui.add(Label::new(RichText::new(format_address(&result.address, Some(12))).font(FontId::monospace(12.))).sense(Sense::click()))
+                                .on_hover_text_at_pointer(i18n("Click to list the address UTXOs"));
+                            if response.clicked() {
+                                selected = Some(result.address.clone());
+                            }
+
+                            if let Some(err) = result.error.as_ref() {
+                                ui.colored_label(theme_color().error_color, err);
+                                ui.end_row();
+                                continue;
+                            }
+
+                            ui.label(sompi_to_kaspa_string_with_suffix(result.balance, &network_type));
+                            ui.label(result.utxos.len().separated_string());
+                            ui.label(result.largest_utxo().map(|amount| sompi_to_kaspa_string_with_suffix(amount, &network_type)).unwrap_or_default());
+                            ui.end_row();
                         }
-                    })
-                    .render(ui);
+
+                        ui.label(RichText::new(i18n("Total")).strong());
+                        ui.label(RichText::new(sompi_to_kaspa_string_with_suffix(total, &network_type)).strong());
+                        ui.label(RichText::new(utxos.separated_string()).strong());
+                        ui.label("");
+                        ui.end_row();
+                    });
+            });
+
+        if selected.is_some() {
+            self.selected = selected;
+        }
+    }
+}
+
+/// Returns `true` if the panel should be closed.
+fn render_utxos(ui : &mut Ui, result : &AddressBalance, network_type : &NetworkType) -> bool {
+    let mut close = false;
+
+    ui.horizontal(|ui| {
+        ui.heading(i18n("UTXOs"));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("✕").clicked() {
+                close = true;
             }
-            State::CheckBalanceResult(_result) => {
-                Panel::new(self)
-                    .with_caption("Check Balance")
-                    .with_back(|this| {
-                        this.state = State::Select;
-                    })
-                    .with_close(|_| {
-                        wallet.back();
-                    })
-                    .with_header(|_, ui| {
-                        ui.label("Check Balance for a specific address");
-                    })
-                    .with_body(|_this, _ui| {
-                        todo!("CheckBalanceResult");
-                    })
-                    .with_handler(|_this| {})
-                    .render(ui);
+        });
+    });
+    ui.separator();
+
+    let address = result.address.to_string();
+    let response = ui.add(Label::new(RichText::new(address.as_str()).font(FontId::monospace(12.))).sense(Sense::click()))
+        .on_hover_text_at_pointer(i18n("Click to copy the address to clipboard"));
+    if response.clicked() {
+        ui.output_mut(|o| o.copied_text = address.clone());
+        runtime().notify_clipboard(i18n("Copied to clipboard"));
+    }
+    ui.space();
+
+    let mut utxos = result.utxos.iter().collect::<Vec<_>>();
+    utxos.sort_by_key(|entry| std::cmp::Reverse(entry.utxo_entry.amount));
+
+    egui::ScrollArea::vertical()
+        .id_source("tools_check_balance_utxo_list")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            Grid::new("tools_check_balance_utxo_grid")
+                .num_columns(3)
+                .striped(true)
+                .spacing([16.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new(i18n("Outpoint")).strong());
+                    ui.label(RichText::new(i18n("Amount")).strong());
+                    ui.label(RichText::new(i18n("DAA Score")).strong());
+                    ui.end_row();
+
+                    for entry in utxos {
+                        let outpoint = &entry.outpoint;
+                        ui.label(RichText::new(format!("{}:{}", format_partial_string(outpoint.transaction_id.to_string(), Some(12)), outpoint.index)).font(FontId::monospace(12.)));
+                        ui.label(sompi_to_kaspa_string_with_suffix(entry.utxo_entry.amount, network_type));
+                        ui.label(entry.utxo_entry.block_daa_score.separated_string());
+                        ui.end_row();
+                    }
+                });
+        });
+
+    close
+}
+
+/// Unique addresses of the given network found in the text, separated by
+/// whitespace, commas or semicolons, along with the entries that are not
+/// valid addresses of the network.
+pub(crate) fn parse_addresses(text : &str, network_type : NetworkType) -> (Vec<Address>, Vec<String>) {
+    let prefix = Prefix::from(network_type);
+    let mut addresses = Vec::new();
+    let mut unique = AHashSet::default();
+    let mut invalid = Vec::new();
+
+    let tokens = text
+        .split(|c : char| c.is_whitespace() || c == ',' || c == ';')
+        .map(|token| token.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|token| !token.is_empty());
+
+    for token in tokens {
+        match Address::try_from(token) {
+            Ok(address) if address.prefix != prefix => {
+                invalid.push(format!("{token} ({})", i18n("wrong network")));
+            }
+            Ok(address) => {
+                if unique.insert(address.clone()) {
+                    addresses.push(address);
+                }
             }
+            Err(_) => invalid.push(token.to_string()),
         }
+    }
 
-        ui.heading("Request");
-        ui.separator();
-        ui.label("This is the payment request page");
+    (addresses, invalid)
+}
+
+async fn check_balances(addresses : Vec<Address>) -> Result<Arc<Vec<AddressBalance>>> {
+    let rpc_api = rpc_api()?;
+    if !rpc_api.get_server_info().await?.has_utxo_index {
+        return Err(Error::custom(i18n("Balance checks require the connected node to run with the UTXO index enabled (--utxoindex)")));
+    }
+
+    let results = futures::stream::iter(addresses)
+        .map(|address| {
+            let rpc_api = rpc_api.clone();
+            async move {
+                match rpc_api.get_utxos_by_addresses(vec![address.clone()]).await {
+                    Ok(utxos) => AddressBalance {
+                        address,
+                        balance : utxos.iter().map(|entry| entry.utxo_entry.amount).sum(),
+                        utxos,
+                        error : None,
+                    },
+                    Err(err) => AddressBalance {
+                        address,
+                        balance : 0,
+                        utxos : Vec::new(),
+                        error : Some(err.to_string()),
+                    },
+                }
+            }
+        })
+        .buffered(MAX_CONCURRENT_REQUESTS)
+        .collect::<Vec<_>>()
+        .await;
+
+    Ok(Arc::new(results))
+}
+
+/// Exact KAS amount without digit grouping
+#[cfg(not(target_arch = "wasm32"))]
+fn format_kaspa(sompi : u64) -> String {
+    use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+    format!("{}.{:08}", sompi / SOMPI_PER_KASPA, sompi % SOMPI_PER_KASPA)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn export_csv(results : &[AddressBalance], target : &Path) -> Result<()> {
+    use std::io::Write;
+
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(target)?);
+    writeln!(writer, "address,balance,utxo_count,largest_utxo,error")?;
+    for result in results.iter() {
+        let error = result.error.as_ref()
+            .map(|err| format!("\"{}\"", err.replace('"', "\"\"")))
+            .unwrap_or_default();
+        writeln!(writer, "{},{},{},{},{}",
+            result.address,
+            format_kaspa(result.balance),
+            result.utxos.len(),
+            result.largest_utxo().map(format_kaspa).unwrap_or_default(),
+            error,
+        )?;
     }
+    writer.flush()?;
+
+    Ok(())
 }
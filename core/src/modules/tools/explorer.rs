@@ -0,0 +1,662 @@
+use crate::imports::*;
+use super::rpc_api;
+use chrono::DateTime;
+use egui_plot::{Line, Plot};
+use kaspa_consensus_core::errors::consensus::ConsensusError;
+use kaspa_rpc_core::{
+    error::RpcError, RpcBlock, RpcMempoolEntry, RpcMempoolEntryByAddress, RpcTransaction, RpcTransactionId,
+    RpcUtxosByAddressesEntry,
+};
+
+/// Default blue score window below the sink searched for the transaction acceptance
+const DEFAULT_SEARCH_DEPTH : usize = 1000;
+const MAX_SEARCH_DEPTH : usize = 10_000;
+/// Maximum number of header requests used to locate the start of the search window
+const MAX_WINDOW_STEPS : usize = 32;
+/// Maximum number of UTXO entries displayed for an address
+const MAX_DISPLAYED_UTXOS : usize = 500;
+
+#[derive(Clone)]
+pub enum Lookup {
+    Block(Arc<RpcBlock>),
+    Transaction(Arc<TransactionLookup>),
+    Address(Arc<AddressLookup>),
+}
+
+pub struct TransactionLookup {
+    pub id : RpcTransactionId,
+    /// Mempool entry if the transaction is pending
+    pub mempool : Option<RpcMempoolEntry>,
+    /// Chain block that accepted the transaction
+    pub accepting_block : Option<KaspaHash>,
+    /// Block containing the transaction
+    pub block : Option<Arc<RpcBlock>>,
+    pub transaction : Option<RpcTransaction>,
+    /// Blue score window searched for the transaction acceptance
+    pub depth : usize,
+}
+
+pub struct AddressLookup {
+    pub address : Address,
+    pub balance : u64,
+    pub utxos : Vec<RpcUtxosByAddressesEntry>,
+    pub pending : Option<RpcMempoolEntryByAddress>,
+    pub virtual_daa_score : u64,
+}
+
+impl AddressLookup {
+    /// Cumulative balance of the unspent outputs ordered by the
+    /// DAA score of the block that created them.
+    fn utxo_history(&self) -> Vec<[f64; 2]> {
+        let mut utxos = self.utxos.iter()
+            .map(|entry| (entry.utxo_entry.block_daa_score, entry.utxo_entry.amount))
+            .collect::<Vec<_>>();
+        utxos.sort_by_key(|(daa_score, _)| *daa_score);
+
+        let mut points = Vec::with_capacity(utxos.len() * 2 + 1);
+        let mut balance = 0;
+        for (daa_score, amount) in utxos {
+            points.push([daa_score as f64, sompi_to_kaspa(balance)]);
+            balance += amount;
+            points.push([daa_score as f64, sompi_to_kaspa(balance)]);
+        }
+        if !points.is_empty() {
+            points.push([self.virtual_daa_score as f64, sompi_to_kaspa(balance)]);
+        }
+        points
+    }
+}
+
+enum Action {
+    Lookup(String),
+    Back,
+}
+
+pub struct Explorer {
+    query : String,
+    depth : usize,
+    current : Option<String>,
+    history : Vec<String>,
+    result : Option<std::result::Result<Lookup, String>>,
+}
+
+impl Default for Explorer {
+    fn default() -> Self {
+        Self {
+            query : String::new(),
+            depth : DEFAULT_SEARCH_DEPTH,
+            current : None,
+            history : Vec::new(),
+            result : None,
+        }
+    }
+}
+
+impl Explorer {
+
+    pub fn reset(&mut self) {
+        self.current = None;
+        self.history.clear();
+        self.result = None;
+    }
+
+    fn lookup(&mut self, query : String, payload : &Payload<Result<Lookup>>) {
+        if query.is_empty() || payload.is_pending() {
+            return;
+        }
+
+        if let Some(current) = self.current.replace(query.clone()) {
+            if current != query {
+                self.history.push(current);
+            }
+        }
+
+        self.query = query.clone();
+        self.result = None;
+        let depth = self.depth;
+        spawn_with_result(payload, async move { lookup(query, depth).await });
+    }
+
+    pub fn render(&mut self, core : &mut Core, ui : &mut Ui) {
+        let lookup_result = Payload::<Result<Lookup>>::new("tools_explorer_lookup_result");
+        if let Some(result) = lookup_result.take() {
+            self.result = Some(result.map_err(|err| err.to_string()));
+        }
+
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!self.history.is_empty(), Button::new("⏴")).on_hover_text(i18n("Back")).clicked() {
+                action = Some(Action::Back);
+            }
+
+            let response = ui.add(TextEdit::singleline(&mut self.query)
+                .hint_text(i18n("Transaction id, block hash or address..."))
+                .font(FontId::monospace(12.))
+                .desired_width(520.)
+            );
+            if (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))) || ui.button(i18n("Lookup")).clicked() {
+                action = Some(Action::Lookup(self.query.trim().to_string()));
+            }
+        });
+
+        CollapsingHeader::new(i18n("Options"))
+            .id_source("tools_explorer_options")
+            .show(ui, |ui| {
+                ui.add(
+                    Slider::new(&mut self.depth, 100..=MAX_SEARCH_DEPTH)
+                        .logarithmic(true)
+                        .text(i18n("Transaction search depth (blue score)"))
+                );
+                ui.label(RichText::new(i18n("The node does not index transactions; accepted transactions are located by scanning the recent selected chain.")).small());
+            });
+        ui.separator();
+
+        if lookup_result.is_pending() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(i18n("Looking up..."));
+            });
+        } else {
+            let network_type = NetworkType::from(core.network());
+            let mut next = None;
+
+            match self.result.as_ref() {
+                None => {
+                    ui.label(i18n("Enter a transaction id, block hash or address to look up its details on the connected node."));
+                }
+                Some(Err(err)) => {
+                    ui.label(RichText::new(err).color(theme_color().error_color));
+                }
+                Some(Ok(lookup)) => {
+                    egui::ScrollArea::vertical()
+                        .id_source("tools_explorer_result")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            match lookup {
+                                Lookup::Block(block) => render_block(ui, block, &network_type, &mut next),
+                                Lookup::Transaction(transaction) => render_transaction_lookup(ui, transaction, &network_type, &mut next),
+                                Lookup::Address(address) => render_address(ui, address, &network_type, &mut next),
+                            }
+                        });
+                }
+            }
+
+            if let Some(query) = next {
+                action = Some(Action::Lookup(query));
+            }
+        }
+
+        match action {
+            Some(Action::Lookup(query)) => {
+                self.lookup(query, &lookup_result);
+            }
+            Some(Action::Back) if !lookup_result.is_pending() => {
+                if let Some(query) = self.history.pop() {
+                    self.current = None;
+                    self.lookup(query, &lookup_result);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn lookup(query : String, depth : usize) -> Result<Lookup> {
+    let rpc_api = rpc_api()?;
+
+    if let Ok(address) = Address::try_from(query.as_str()) {
+        return Ok(Lookup::Address(Arc::new(lookup_address(&rpc_api, address).await?)));
+    }
+
+    let hash = KaspaHash::from_str(&query)
+        .map_err(|_| Error::custom(i18n("Please enter a valid transaction id, block hash or address")))?;
+
+    match rpc_api.get_block(hash, true).await {
+        Ok(block) => Ok(Lookup::Block(Arc::new(block))),
+        Err(err) if is_block_not_found(&err) => {
+            Ok(Lookup::Transaction(Arc::new(lookup_transaction(&rpc_api, hash, depth).await?)))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Check if the error reports an unknown block hash. Errors relayed by
+/// remote nodes are received as messages, so these are matched as well.
+fn is_block_not_found(err : &RpcError) -> bool {
+    match err {
+        RpcError::ConsensusError(ConsensusError::BlockNotFound(_) | ConsensusError::HeaderNotFound(_)) => true,
+        err => {
+            let message = err.to_string();
+            message.contains("cannot find full block") || message.contains("cannot find header")
+        }
+    }
+}
+
+async fn lookup_address(rpc_api : &Arc<dyn RpcApi>, address : Address) -> Result<AddressLookup> {
+    let server_info = rpc_api.get_server_info().await?;
+    if !server_info.has_utxo_index {
+        return Err(Error::custom(i18n("Address lookups require the connected node to run with the UTXO index enabled (--utxoindex)")));
+    }
+
+    let balance = rpc_api.get_balance_by_address(address.clone()).await?;
+    let utxos = rpc_api.get_utxos_by_addresses(vec![address.clone()]).await?;
+    let pending = rpc_api.get_mempool_entries_by_addresses(vec![address.clone()], true, false).await?
+        .into_iter()
+        .find(|entry| entry.address == address);
+
+    Ok(AddressLookup {
+        address,
+        balance,
+        utxos,
+        pending,
+        virtual_daa_score : server_info.virtual_daa_score,
+    })
+}
+
+/// Locate a transaction in the mempool or, as the node keeps no transaction
+/// index, by scanning the acceptance data of the chain blocks within the last
+/// `depth` blue score units. The start of the window is located by following
+/// the block parents of increasing levels, each step covering (on average)
+/// twice the distance of the previous one, so only a few headers are fetched.
+async fn lookup_transaction(rpc_api : &Arc<dyn RpcApi>, id : RpcTransactionId, depth : usize) -> Result<TransactionLookup> {
+    if let Ok(entry) = rpc_api.get_mempool_entry(id, true, false).await {
+        return Ok(TransactionLookup {
+            id,
+            transaction : Some(entry.transaction.clone()),
+            mempool : Some(entry),
+            accepting_block : None,
+            block : None,
+            depth : 0,
+        });
+    }
+
+    let sink = rpc_api.get_block_dag_info().await?.sink;
+    let header = rpc_api.get_block(sink, false).await?.header;
+    let sink_blue_score = header.blue_score;
+    let target = sink_blue_score.saturating_sub(depth as u64);
+
+    let mut low = sink;
+    let mut blue_score = sink_blue_score;
+    let mut parents_by_level = header.parents_by_level;
+    let mut level = 0;
+    for _ in 0..MAX_WINDOW_STEPS {
+        if blue_score <= target {
+            break;
+        }
+
+        let parent = parents_by_level.get(level).or(parents_by_level.last()).and_then(|parents| parents.first()).copied();
+        // reached the genesis, or the parent header has been pruned
+        let Some((parent, header)) = (match parent {
+            Some(parent) => rpc_api.get_block(parent, false).await.ok().map(|block| (parent, block.header)),
+            None => None,
+        }) else {
+            break;
+        };
+
+        low = parent;
+        blue_score = header.blue_score;
+        parents_by_level = header.parents_by_level;
+        level += 1;
+    }
+    let searched = (sink_blue_score - blue_score) as usize;
+
+    let chain = rpc_api.get_virtual_chain_from_block(low, true).await?;
+    let accepting_block = chain.accepted_transaction_ids.iter()
+        .find(|accepted| accepted.accepted_transaction_ids.contains(&id))
+        .map(|accepted| accepted.accepting_block_hash);
+
+    let mut lookup = TransactionLookup {
+        id,
+        mempool : None,
+        accepting_block,
+        block : None,
+        transaction : None,
+        depth : searched,
+    };
+
+    if let Some(accepting_block) = accepting_block {
+        // the transaction is contained in one of the blocks merged by the accepting block
+        let merge_set = rpc_api.get_block(accepting_block, false).await?
+            .verbose_data
+            .map(|data| data.merge_set_blues_hashes.into_iter().chain(data.merge_set_reds_hashes).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        for hash in merge_set {
+            let block = rpc_api.get_block(hash, true).await?;
+            let index = block.verbose_data.as_ref()
+                .and_then(|data| data.transaction_ids.iter().position(|transaction_id| *transaction_id == id));
+            if let Some(index) = index {
+                lookup.transaction = block.transactions.get(index).cloned();
+                lookup.block = Some(Arc::new(block));
+                break;
+            }
+        }
+    }
+
+    Ok(lookup)
+}
+
+fn render_hash(ui : &mut Ui, title : &str, hash : &str) {
+    ui.horizontal_wrapped(|ui| {
+        ui.heading(title);
+        let response = ui.add(Label::new(RichText::new(hash).font(FontId::monospace(12.))).sense(Sense::click()))
+            .on_hover_text_at_pointer(i18n("Click to copy to clipboard"));
+        if response.clicked() {
+            ui.output_mut(|o| o.copied_text = hash.to_string());
+            runtime().notify_clipboard(i18n("Copied to clipboard"));
+        }
+    });
+    ui.space();
+}
+
+fn hash_link(ui : &mut Ui, hash : &KaspaHash, next : &mut Option<String>) {
+    if ui.link(RichText::new(format_partial_string(hash.to_string(), Some(12))).font(FontId::monospace(12.))).clicked() {
+        *next = Some(hash.to_string());
+    }
+}
+
+fn address_link(ui : &mut Ui, address : &Address, next : &mut Option<String>) {
+    if ui.link(RichText::new(format_address(address, Some(8))).font(FontId::monospace(12.))).clicked() {
+        *next = Some(address.to_string());
+    }
+}
+
+fn format_timestamp(timestamp : u64) -> String {
+    DateTime::<chrono::Utc>::from_timestamp((timestamp / 1000) as i64, 0)
+        .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+fn render_block(ui : &mut Ui, block : &RpcBlock, network_type : &NetworkType, next : &mut Option<String>) {
+    let header = &block.header;
+    let verbose_data = block.verbose_data.as_ref();
+
+    render_hash(ui, i18n("Block"), &header.hash.to_string());
+
+    Grid::new("tools_explorer_block_header")
+        .num_columns(2)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            if let Some(verbose_data) = verbose_data {
+                ui.label(i18n("Chain Block"));
+                if verbose_data.is_chain_block {
+                    ui.label(RichText::new(i18n("Yes")).color(theme_color().strong_color));
+                } else {
+                    ui.label(i18n("No"));
+                }
+                ui.end_row();
+            }
+            ui.label(i18n("DAA Score"));
+            ui.label(header.daa_score.separated_string());
+            ui.end_row();
+            ui.label(i18n("Blue Score"));
+            ui.label(header.blue_score.separated_string());
+            ui.end_row();
+            ui.label(i18n("Timestamp"));
+            ui.label(format!("{} ({} ms)", format_timestamp(header.timestamp), header.timestamp));
+            ui.end_row();
+            if let Some(verbose_data) = verbose_data {
+                ui.label(i18n("Difficulty"));
+                ui.label(format!("{:.2}", verbose_data.difficulty));
+                ui.end_row();
+                ui.label(i18n("Selected Parent"));
+                hash_link(ui, &verbose_data.selected_parent_hash, next);
+                ui.end_row();
+            }
+            ui.label(i18n("Pruning Point"));
+            hash_link(ui, &header.pruning_point, next);
+            ui.end_row();
+            ui.label(i18n("Version"));
+            ui.label(header.version.to_string());
+            ui.end_row();
+            ui.label(i18n("Nonce"));
+            ui.label(header.nonce.to_string());
+            ui.end_row();
+        });
+
+    ui.space();
+
+    CollapsingHeader::new(format!("{} ({})", i18n("Parents"), header.direct_parents().len()))
+        .id_source("tools_explorer_block_parents")
+        .show(ui, |ui| {
+            for parent in header.direct_parents().iter() {
+                hash_link(ui, parent, next);
+            }
+        });
+
+    if let Some(verbose_data) = verbose_data {
+        CollapsingHeader::new(format!("{} ({})", i18n("Merge Set"), verbose_data.merge_set_blues_hashes.len() + verbose_data.merge_set_reds_hashes.len()))
+            .id_source("tools_explorer_block_merge_set")
+            .show(ui, |ui| {
+                ui.label(format!("{} ({})", i18n("Blues"), verbose_data.merge_set_blues_hashes.len()));
+                for hash in verbose_data.merge_set_blues_hashes.iter() {
+                    hash_link(ui, hash, next);
+                }
+                ui.space();
+                ui.label(format!("{} ({})", i18n("Reds"), verbose_data.merge_set_reds_hashes.len()));
+                for hash in verbose_data.merge_set_reds_hashes.iter() {
+                    hash_link(ui, hash, next);
+                }
+            });
+
+        CollapsingHeader::new(format!("{} ({})", i18n("Children"), verbose_data.children_hashes.len()))
+            .id_source("tools_explorer_block_children")
+            .show(ui, |ui| {
+                for hash in verbose_data.children_hashes.iter() {
+                    hash_link(ui, hash, next);
+                }
+            });
+    }
+
+    CollapsingHeader::new(format!("{} ({})", i18n("Transactions"), block.transactions.len()))
+        .id_source("tools_explorer_block_transactions")
+        .default_open(true)
+        .show(ui, |ui| {
+            for (index, transaction) in block.transactions.iter().enumerate() {
+                let id = transaction.verbose_data.as_ref().map(|data| data.transaction_id)
+                    .or_else(|| verbose_data.and_then(|data| data.transaction_ids.get(index).cloned()))
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| format!("#{index}"));
+                let total = transaction.outputs.iter().map(|output| output.value).sum::<u64>();
+
+                CollapsingHeader::new(RichText::new(format!("{}  {}", format_partial_string(id, Some(12)), sompi_to_kaspa_string_with_suffix(total, network_type))).font(FontId::monospace(12.)))
+                    .id_source(("tools_explorer_block_transaction", index))
+                    .show(ui, |ui| {
+                        render_transaction(ui, transaction, network_type, next);
+                    });
+            }
+        });
+}
+
+fn render_transaction(ui : &mut Ui, transaction : &RpcTransaction, network_type : &NetworkType, next : &mut Option<String>) {
+    CollapsingHeader::new(format!("{} ({})", i18n("Inputs"), transaction.inputs.len()))
+        .id_source(ui.id().with("inputs"))
+        .default_open(true)
+        .show(ui, |ui| {
+            for input in transaction.inputs.iter() {
+                let outpoint = &input.previous_outpoint;
+                ui.horizontal(|ui| {
+                    hash_link(ui, &outpoint.transaction_id, next);
+                    ui.label(RichText::new(format!(":{}", outpoint.index)).font(FontId::monospace(12.)));
+                });
+            }
+        });
+
+    CollapsingHeader::new(format!("{} ({})", i18n("Outputs"), transaction.outputs.len()))
+        .id_source(ui.id().with("outputs"))
+        .default_open(true)
+        .show(ui, |ui| {
+            for output in transaction.outputs.iter() {
+                ui.horizontal(|ui| {
+                    if let Some(verbose_data) = output.verbose_data.as_ref() {
+                        address_link(ui, &verbose_data.script_public_key_address, next);
+                    } else {
+                        ui.label(RichText::new(i18n("Unknown")).font(FontId::monospace(12.)));
+                    }
+                    ui.label(RichText::new(sompi_to_kaspa_string_with_suffix(output.value, network_type)).font(FontId::monospace(12.)));
+                });
+            }
+        });
+}
+
+fn render_transaction_lookup(ui : &mut Ui, lookup : &TransactionLookup, network_type : &NetworkType, next : &mut Option<String>) {
+    render_hash(ui, i18n("Transaction"), &lookup.id.to_string());
+
+    Grid::new("tools_explorer_transaction")
+        .num_columns(2)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            ui.label(i18n("Status"));
+            if let Some(entry) = lookup.mempool.as_ref() {
+                if entry.is_orphan {
+                    ui.colored_label(theme_color().warning_color, i18n("Pending (orphan)"));
+                } else {
+                    ui.colored_label(theme_color().warning_color, i18n("Pending"));
+                }
+                ui.end_row();
+                ui.label(i18n("Fee"));
+                ui.label(sompi_to_kaspa_string_with_suffix(entry.fee, network_type));
+                ui.end_row();
+            } else if let Some(accepting_block) = lookup.accepting_block.as_ref() {
+                ui.label(RichText::new(i18n("Accepted")).color(theme_color().strong_color));
+                ui.end_row();
+                ui.label(i18n("Accepting Block"));
+                hash_link(ui, accepting_block, next);
+                ui.end_row();
+                if let Some(block) = lookup.block.as_ref() {
+                    ui.label(i18n("Included in Block"));
+                    hash_link(ui, &block.header.hash, next);
+                    ui.end_row();
+                    ui.label(i18n("DAA Score"));
+                    ui.label(block.header.daa_score.separated_string());
+                    ui.end_row();
+                    ui.label(i18n("Timestamp"));
+                    ui.label(format_timestamp(block.header.timestamp));
+                    ui.end_row();
+                }
+            } else {
+                ui.colored_label(theme_color().error_color, format!("{} {} {}", i18n("Not found in the mempool or within the last"), lookup.depth.separated_string(), i18n("blue score units")));
+                ui.end_row();
+            }
+
+            if let Some(transaction) = lookup.transaction.as_ref() {
+                let total = transaction.outputs.iter().map(|output| output.value).sum::<u64>();
+                ui.label(i18n("Total Output"));
+                ui.label(sompi_to_kaspa_string_with_suffix(total, network_type));
+                ui.end_row();
+                ui.label(i18n("Mass"));
+                ui.label(transaction.mass.separated_string());
+                ui.end_row();
+                ui.label(i18n("Version"));
+                ui.label(transaction.version.to_string());
+                ui.end_row();
+                ui.label(i18n("Lock Time"));
+                ui.label(transaction.lock_time.to_string());
+                ui.end_row();
+            }
+        });
+
+    if let Some(transaction) = lookup.transaction.as_ref() {
+        ui.space();
+        ui.push_id("tools_explorer_transaction_details", |ui| {
+            render_transaction(ui, transaction, network_type, next);
+        });
+    }
+}
+
+fn render_address(ui : &mut Ui, lookup : &AddressLookup, network_type : &NetworkType, next : &mut Option<String>) {
+    render_hash(ui, i18n("Address"), &lookup.address.to_string());
+
+    let (sending, receiving) = lookup.pending.as_ref()
+        .map(|pending| (pending.sending.len(), pending.receiving.len()))
+        .unwrap_or_default();
+
+    Grid::new("tools_explorer_address")
+        .num_columns(2)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            ui.label(i18n("Balance"));
+            ui.label(RichText::new(sompi_to_kaspa_string_with_suffix(lookup.balance, network_type)).color(theme_color().strong_color));
+            ui.end_row();
+            ui.label(i18n("UTXOs"));
+            ui.label(lookup.utxos.len().separated_string());
+            ui.end_row();
+            ui.label(i18n("Pending (sending)"));
+            ui.label(sending.separated_string());
+            ui.end_row();
+            ui.label(i18n("Pending (receiving)"));
+            ui.label(receiving.separated_string());
+            ui.end_row();
+        });
+
+    if let Some(pending) = lookup.pending.as_ref() {
+        if sending + receiving > 0 {
+            CollapsingHeader::new(i18n("Pending Transactions"))
+                .id_source("tools_explorer_address_pending")
+                .default_open(true)
+                .show(ui, |ui| {
+                    for entry in pending.sending.iter().chain(pending.receiving.iter()) {
+                        if let Some(verbose_data) = entry.transaction.verbose_data.as_ref() {
+                            hash_link(ui, &verbose_data.transaction_id, next);
+                        }
+                    }
+                });
+        }
+    }
+
+    ui.space();
+
+    let history = lookup.utxo_history();
+    if !history.is_empty() {
+        CollapsingHeader::new(i18n("Unspent Output History"))
+            .id_source("tools_explorer_address_history")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.label(RichText::new(i18n("Cumulative balance of the current unspent outputs by DAA score; spent outputs are not tracked by the node.")).small());
+                Plot::new("tools_explorer_address_history_plot")
+                    .height(180.)
+                    .allow_scroll(false)
+                    .allow_zoom(false)
+                    .allow_drag(false)
+                    .include_y(0.0)
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(PlotPoints::from(history)).color(theme_color().strong_color).fill(0.0));
+                    });
+            });
+    }
+
+    CollapsingHeader::new(format!("{} ({})", i18n("UTXOs"), lookup.utxos.len()))
+        .id_source("tools_explorer_address_utxos")
+        .default_open(true)
+        .show(ui, |ui| {
+            if lookup.utxos.len() > MAX_DISPLAYED_UTXOS {
+                ui.label(format!("{} {} / {}", i18n("Showing"), MAX_DISPLAYED_UTXOS, lookup.utxos.len().separated_string()));
+            }
+
+            let mut utxos = lookup.utxos.iter().collect::<Vec<_>>();
+            utxos.sort_by_key(|entry| std::cmp::Reverse(entry.utxo_entry.block_daa_score));
+
+            Grid::new("tools_explorer_address_utxos_grid")
+                .num_columns(5)
+                .striped(true)
+                .spacing([16.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new(i18n("Transaction ID")).strong());
+                    ui.label(RichText::new(i18n("Index")).strong());
+                    ui.label(RichText::new(i18n("Amount")).strong());
+                    ui.label(RichText::new(i18n("DAA Score")).strong());
+                    ui.label(RichText::new(i18n("Coinbase")).strong());
+                    ui.end_row();
+
+                    for entry in utxos.into_iter().take(MAX_DISPLAYED_UTXOS) {
+                        hash_link(ui, &entry.outpoint.transaction_id, next);
+                        ui.label(entry.outpoint.index.to_string());
+                        ui.label(sompi_to_kaspa_string_with_suffix(entry.utxo_entry.amount, network_type));
+                        ui.label(entry.utxo_entry.block_daa_score.separated_string());
+                        ui.label(if entry.utxo_entry.is_coinbase { i18n("Yes") } else { i18n("No") });
+                        ui.end_row();
+                    }
+                });
+        });
+}
@@ -1 +1,77 @@
-pub mod check_balance;
+use crate::imports::*;
+
+pub(crate) mod check_balance;
+mod explorer;
+
+use check_balance::CheckBalance;
+use explorer::Explorer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    Explorer,
+    CheckBalance,
+}
+
+pub struct Tools {
+    #[allow(dead_code)]
+    runtime: Runtime,
+    tool : Tool,
+    explorer : Explorer,
+    check_balance : CheckBalance,
+}
+
+impl Tools {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            runtime,
+            tool : Tool::Explorer,
+            explorer : Explorer::default(),
+            check_balance : CheckBalance::default(),
+        }
+    }
+}
+
+impl ModuleT for Tools {
+
+    fn style(&self) -> ModuleStyle {
+        ModuleStyle::Default
+    }
+
+    fn render(
+        &mut self,
+        core: &mut Core,
+        _ctx: &egui::Context,
+        _frame: &mut eframe::Frame,
+        ui: &mut egui::Ui,
+    ) {
+
+        ui.horizontal(|ui| {
+            ui.heading(i18n("Tools"));
+            ui.space();
+            ui.selectable_value(&mut self.tool, Tool::Explorer, i18n("Explorer"));
+            ui.selectable_value(&mut self.tool, Tool::CheckBalance, i18n("Check Balance"));
+        });
+        ui.separator();
+
+        if !core.state().is_connected() {
+            ui.label(i18n("Not connected"));
+            return;
+        }
+
+        match self.tool {
+            Tool::Explorer => self.explorer.render(core, ui),
+            Tool::CheckBalance => self.check_balance.render(core, ui),
+        }
+    }
+
+    fn disconnect(&mut self, _core: &mut Core) {
+        self.explorer.reset();
+        self.check_balance.reset();
+    }
+
+}
+
+/// RPC API of the connected node
+fn rpc_api() -> Result<Arc<dyn RpcApi>> {
+    runtime().kaspa_service().rpc_api().ok_or_else(|| Error::custom(i18n("Not connected")))
+}
@@ -71,6 +71,11 @@ impl DagBlock {
     pub fn direct_parents(&self) -> &[KaspaHash] {
         self.data.header.direct_parents()
     }
+
+    /// `true` if the block is on the virtual selected parent chain
+    pub fn is_vspc(&self) -> bool {
+        self.vspc
+    }
 }
 
 pub struct DaaBucket {
@@ -129,6 +129,7 @@ impl Transaction {
         current_daa_score: Option<u64>,
         _include_utxos: bool,
         largest: Option<u64>,
+        fiat_price: Option<&FiatPrice>,
     ) {
         let width = ui.available_width() / ui.ctx().pixels_per_point();
 
@@ -140,6 +141,7 @@ impl Transaction {
 
         let ps2k = |sompi| padded_sompi_to_kaspa_string_with_suffix(sompi, &network_type, padding);
         let s2k = |sompi| sompi_to_kaspa_string_with_suffix(sompi, &network_type);
+        let s2f = |sompi| fiat_price.map(|fiat_price| fiat_price.format_sompi(sompi));
 
         let timestamp = record
             .unixtime_as_locale_string()
@@ -206,6 +208,10 @@ impl Transaction {
                     .text(timestamp.as_str(), default_color)
                     .text(&value, TransactionKind::Incoming.as_color());
 
+                if let Some(fiat) = s2f(record.value()) {
+                    job = job.text(&fiat, default_color);
+                }
+
                 // ui.LayoutJobBuilder::new(width,8.0(&transaction_id, false, |ui,state| {
                 //     ui.horizontal( |ui| {
 
@@ -330,6 +336,10 @@ impl Transaction {
                         TransactionKind::Outgoing.as_color(),
                     );
 
+                    if let Some(fiat) = s2f(*payment_value + *fees) {
+                        job = job.text(&fiat, default_color);
+                    }
+
                     if !maturity.unwrap_or(true) {
                         job = job.text("Submitting...", strong_color);
                     }
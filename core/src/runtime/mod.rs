@@ -35,8 +35,10 @@ pub struct Inner {
 
     kaspa: Arc<KaspaService>,
     peer_monitor_service: Arc<PeerMonitorService>,
+    mempool_monitor_service: Arc<MempoolMonitorService>,
     update_monitor_service: Arc<UpdateMonitorService>,
     market_monitor_service: Arc<MarketMonitorService>,
+    server_monitor_service: Arc<ServerMonitorService>,
 
     // #[cfg(not(feature = "lean"))]
     metrics_service: Arc<MetricsService>,
@@ -75,6 +77,10 @@ impl Runtime {
             application_events.clone(),
             settings,
         ));
+        let mempool_monitor_service = Arc::new(MempoolMonitorService::new(
+            application_events.clone(),
+            settings,
+        ));
         let market_monitor_service = Arc::new(MarketMonitorService::new(
             application_events.clone(),
             settings,
@@ -85,6 +91,11 @@ impl Runtime {
             settings,
         ));
 
+        let server_monitor_service = Arc::new(ServerMonitorService::new(
+            application_events.clone(),
+            settings,
+        ));
+
         let metrics_service = Arc::new(MetricsService::new(application_events.clone(), settings));
         cfg_if! {
             if #[cfg(not(feature = "lean"))] {
@@ -104,8 +115,10 @@ impl Runtime {
             repaint_service.clone(),
             kaspa.clone(),
             peer_monitor_service.clone(),
+            mempool_monitor_service.clone(),
             market_monitor_service.clone(),
             update_monitor_service.clone(),
+            server_monitor_service.clone(),
             // #[cfg(not(feature = "lean"))]
             metrics_service.clone(),
             #[cfg(not(feature = "lean"))]
@@ -119,8 +132,10 @@ impl Runtime {
                 repaint_service,
                 kaspa,
                 peer_monitor_service,
+                mempool_monitor_service,
                 market_monitor_service,
                 update_monitor_service,
+                server_monitor_service,
                 egui_ctx: egui_ctx.clone(),
                 is_running: Arc::new(AtomicBool::new(false)),
                 start_time: Instant::now(),
@@ -231,6 +246,10 @@ impl Runtime {
         &self.inner.peer_monitor_service
     }
 
+    pub fn mempool_monitor_service(&self) -> &Arc<MempoolMonitorService> {
+        &self.inner.mempool_monitor_service
+    }
+
     pub fn metrics_service(&self) -> &Arc<MetricsService> {
         &self.inner.metrics_service
     }
@@ -252,6 +271,10 @@ impl Runtime {
         &self.inner.update_monitor_service
     }
 
+    pub fn server_monitor_service(&self) -> &Arc<ServerMonitorService> {
+        &self.inner.server_monitor_service
+    }
+
     /// Returns the reference to the application events channel.
     pub fn application_events(&self) -> &ApplicationEventsChannel {
         &self.inner.application_events
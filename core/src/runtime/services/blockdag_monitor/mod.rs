@@ -0,0 +1,607 @@
+use crate::imports::*;
+use kaspa_notify::{listener::ListenerId, scope::*};
+use kaspa_rpc_core::api::notifications::Notification;
+use kaspa_rpc_core::notify::connection::{ChannelConnection, ChannelType};
+use kaspa_rpc_core::{RpcBlock, VirtualChainChangedNotification};
+use std::ops::RangeInclusive;
+
+cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        pub mod recording;
+        pub use recording::{BlockDagEvent, BlockDagRecorder, BlockDagRecording, BlockDagReplay, RECORDING_FILE_EXTENSION};
+
+        const REPLAY_TICK: Duration = Duration::from_millis(20);
+    }
+}
+
+pub enum BlockDagMonitorEvents {
+    Enable,
+    Disable,
+    Settings(Arc<BlockDagGraphSettings>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Replay(BlockDagEvent),
+    Reset,
+    Exit,
+}
+
+pub struct BlockDagMonitorService {
+    pub application_events: ApplicationEventsChannel,
+    pub service_events: Channel<BlockDagMonitorEvents>,
+    pub task_ctl: Channel<()>,
+    pub rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
+    listener_id: Mutex<Option<ListenerId>>,
+    notification_channel: Channel<Notification>,
+    is_enabled: Arc<AtomicBool>,
+    is_active: Arc<AtomicBool>,
+    is_connected: Arc<AtomicBool>,
+    pub chain: Mutex<AHashMap<u64, DaaBucket>>,
+    pub separators: Mutex<Vec<u64>>,
+    pub new_blocks: Arc<Mutex<AHashSet<kaspa_consensus_core::Hash>>>,
+    pub settings: Mutex<Arc<BlockDagGraphSettings>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    recorder: Mutex<Option<BlockDagRecorder>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    replay: Arc<BlockDagReplay>,
+}
+
+impl BlockDagMonitorService {
+    pub fn new(application_events: ApplicationEventsChannel, _settings: &Settings) -> Self {
+        Self {
+            application_events,
+            service_events: Channel::unbounded(),
+            task_ctl: Channel::oneshot(),
+            rpc_api: Mutex::new(None),
+            listener_id: Mutex::new(None),
+            notification_channel: Channel::<Notification>::unbounded(),
+            chain: Mutex::new(AHashMap::new()),
+            separators: Mutex::new(Vec::new()),
+            new_blocks: Arc::new(Mutex::new(AHashSet::new())),
+            is_enabled: Arc::new(AtomicBool::new(false)),
+            is_active: Arc::new(AtomicBool::new(false)),
+            is_connected: Arc::new(AtomicBool::new(false)),
+            settings: Mutex::new(Arc::new(BlockDagGraphSettings::default())),
+            #[cfg(not(target_arch = "wasm32"))]
+            recorder: Mutex::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            replay: Arc::new(BlockDagReplay::default()),
+        }
+    }
+
+    pub async fn register_notification_listener(&self) -> Result<()> {
+        if let Some(rpc_api) = self.rpc_api() {
+            let listener_id = rpc_api.register_new_listener(ChannelConnection::new(
+                "blockdag-monitor",
+                self.notification_channel.sender.clone(),
+                ChannelType::Persistent,
+            ));
+            *self.listener_id.lock().unwrap() = Some(listener_id);
+            rpc_api
+                .start_notify(listener_id, Scope::BlockAdded(BlockAddedScope {}))
+                .await?;
+            rpc_api
+                .start_notify(
+                    listener_id,
+                    Scope::VirtualChainChanged(VirtualChainChangedScope {
+                        include_accepted_transaction_ids: false,
+                    }),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn unregister_notification_listener(&self) -> Result<()> {
+        if let Some(rpc_api) = self.rpc_api() {
+            let listener_id = self.listener_id.lock().unwrap().take();
+            if let Some(id) = listener_id {
+                // we do not need this as we are unregister the entire listener here...
+                rpc_api.unregister_listener(id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rpc_api(&self) -> Option<Arc<dyn RpcApi>> {
+        self.rpc_api.lock().unwrap().clone()
+    }
+
+    pub fn activate(&self, state: bool) {
+        self.is_active.store(state, Ordering::Relaxed);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active.load(Ordering::Relaxed)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn enable(&self, _current_daa_score: Option<u64>) {
+        self.service_events
+            .sender
+            .try_send(BlockDagMonitorEvents::Enable)
+            .unwrap();
+        // if let Some(current_daa_score) = current_daa_score {
+        //     self._update_separators(current_daa_score);
+        // }
+    }
+
+    pub fn disable(&self, _current_daa_score: Option<u64>) {
+        self.service_events
+            .sender
+            .try_send(BlockDagMonitorEvents::Disable)
+            .unwrap();
+        // if let Some(current_daa_score) = current_daa_score {
+        //     self._update_separators(current_daa_score);
+        // }
+    }
+
+    fn _update_separators(&self, current_daa_score: u64) {
+        let graph_length_daa = self.settings.lock().unwrap().graph_length_daa as u64;
+        let mut separators = self.separators.lock().unwrap();
+        while let Some(first) = separators.first() {
+            if *first < (current_daa_score - graph_length_daa) {
+                separators.remove(0);
+            } else {
+                break;
+            }
+        }
+        separators.push(current_daa_score);
+    }
+
+    pub fn separators(&self) -> MutexGuard<'_, Vec<u64>> {
+        self.separators.lock().unwrap()
+    }
+
+    pub fn update_settings(&self, settings: BlockDagGraphSettings) {
+        self.service_events
+            .sender
+            .try_send(BlockDagMonitorEvents::Settings(Arc::new(settings)))
+            .unwrap();
+    }
+
+    /// Find a block tracked by the monitor, returning the block, its
+    /// position in the graph and its virtual selected parent chain state.
+    pub fn find_block(&self, hash: &KaspaHash) -> Option<(Arc<RpcBlock>, PlotPoint, bool)> {
+        self.chain.lock().unwrap().values().find_map(|bucket| {
+            bucket
+                .blocks
+                .iter()
+                .find(|block| block.data.header.hash == *hash)
+                .map(|block| {
+                    (
+                        block.data.clone(),
+                        [bucket.daa_score, block.src_y].into(),
+                        block.is_vspc(),
+                    )
+                })
+        })
+    }
+
+    /// Blocks within the given DAA score range at their settled graph
+    /// positions, along with their virtual selected parent chain state.
+    pub fn settled_blocks(
+        &self,
+        daa_range: RangeInclusive<u64>,
+    ) -> Vec<(Arc<RpcBlock>, PlotPoint, bool)> {
+        self.chain
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(daa_score, _)| daa_range.contains(daa_score))
+            .flat_map(|(_, bucket)| {
+                bucket.blocks.iter().map(|block| {
+                    (
+                        block.data.clone(),
+                        [bucket.daa_score, block.dst_y].into(),
+                        block.is_vspc(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Fetch the block with its transactions and verbose data from the node.
+    pub async fn fetch_block(&self, hash: KaspaHash) -> Result<Arc<RpcBlock>> {
+        let rpc_api = self
+            .rpc_api()
+            .ok_or_else(|| Error::custom("Not connected to a node"))?;
+        Ok(Arc::new(rpc_api.get_block(hash, true).await?))
+    }
+
+    /// `true` while a recording is being replayed; live
+    /// notifications are ignored during the replay.
+    pub fn is_replaying(&self) -> bool {
+        cfg_if! {
+            if #[cfg(not(target_arch = "wasm32"))] {
+                self.replay.is_active()
+            } else {
+                false
+            }
+        }
+    }
+
+    /// DAA score of the last replayed block
+    pub fn replay_daa_score(&self) -> Option<u64> {
+        cfg_if! {
+            if #[cfg(not(target_arch = "wasm32"))] {
+                self.replay.daa_score()
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn new_blocks(&self) -> MutexGuard<'_, AHashSet<kaspa_consensus_core::Hash>> {
+        self.new_blocks.lock().unwrap()
+    }
+
+    fn update_new_blocks(&self, block: &Arc<RpcBlock>) {
+        let mut new_blocks = self.new_blocks.lock().unwrap();
+        new_blocks.insert(block.header.hash);
+        let parents = block
+            .header
+            .parents_by_level
+            .iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        parents.iter().for_each(|hash| {
+            new_blocks.remove(hash);
+        });
+    }
+
+    fn block_added(
+        &self,
+        block: Arc<RpcBlock>,
+        blocks_by_hash: &mut AHashMap<KaspaHash, Arc<RpcBlock>>,
+        settings: &BlockDagGraphSettings,
+    ) {
+        self.update_new_blocks(&block);
+
+        blocks_by_hash.insert(block.header.hash, block.clone());
+
+        let daa_score = block.header.daa_score;
+        let mut chain = self.chain.lock().unwrap();
+        if let Some(bucket) = chain.get_mut(&daa_score) {
+            bucket.push(DagBlock::new(block, settings), settings);
+        } else {
+            let mut bucket = DaaBucket::new(daa_score as f64, DagBlock::new(block, settings));
+            bucket.update(settings);
+            chain.insert(daa_score, bucket);
+        }
+
+        let last_daa = daa_score.saturating_sub(settings.graph_length_daa as u64);
+        chain.retain(|daa_score, bucket| {
+            if *daa_score > last_daa {
+                true
+            } else {
+                bucket.blocks.iter().for_each(|block| {
+                    blocks_by_hash.remove(&block.data.header.hash);
+                });
+                false
+            }
+        });
+    }
+
+    fn virtual_chain_changed(
+        &self,
+        removed_chain_block_hashes: &[KaspaHash],
+        added_chain_block_hashes: &[KaspaHash],
+        blocks_by_hash: &AHashMap<KaspaHash, Arc<RpcBlock>>,
+        settings: &BlockDagGraphSettings,
+    ) {
+        let mut chain = self.chain.lock().unwrap();
+        removed_chain_block_hashes.iter().for_each(|hash| {
+            if let Some(block) = blocks_by_hash.get(hash) {
+                if let Some(bucket) = chain.get_mut(&block.header.daa_score) {
+                    bucket.update_vspc(*hash, false, settings);
+                }
+            }
+        });
+        added_chain_block_hashes.iter().for_each(|hash| {
+            if let Some(block) = blocks_by_hash.get(hash) {
+                if let Some(bucket) = chain.get_mut(&block.header.daa_score) {
+                    bucket.update_vspc(*hash, true, settings);
+                }
+            }
+        });
+    }
+}
+
+cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        impl BlockDagMonitorService {
+            /// Start recording block-added and virtual chain changed
+            /// notifications to the given file.
+            pub fn start_recording(&self, path: &Path) -> Result<()> {
+                let network = *runtime().kaspa_service().network.lock().unwrap();
+                let recorder = BlockDagRecorder::try_new(path, network)?;
+                if let Some(previous) = self.recorder.lock().unwrap().replace(recorder) {
+                    previous.finish()?;
+                }
+                Ok(())
+            }
+
+            /// Stop recording, returning the recording file path
+            /// and the number of recorded events.
+            pub fn stop_recording(&self) -> Result<Option<(PathBuf, usize)>> {
+                if let Some(recorder) = self.recorder.lock().unwrap().take() {
+                    let info = (recorder.path().to_path_buf(), recorder.records());
+                    recorder.finish()?;
+                    Ok(Some(info))
+                } else {
+                    Ok(None)
+                }
+            }
+
+            pub fn is_recording(&self) -> bool {
+                self.recorder.lock().unwrap().is_some()
+            }
+
+            fn record(&self, event: impl FnOnce() -> BlockDagEvent) {
+                let mut recorder = self.recorder.lock().unwrap();
+                if let Some(active) = recorder.as_mut() {
+                    if let Err(err) = active.record(event()) {
+                        log_warn!("Unable to record BlockDAG event: {err}");
+                        recorder.take();
+                        runtime().error(format!("BlockDAG recording stopped: {err}"));
+                    }
+                }
+            }
+
+            pub fn replay(&self) -> &Arc<BlockDagReplay> {
+                &self.replay
+            }
+
+            /// Replay a recording of the given network, replacing the live
+            /// BlockDAG stream until [`stop_replay`](Self::stop_replay) is
+            /// called. Events are dispatched according to their recorded time
+            /// offsets scaled by the replay speed.
+            pub fn start_replay(self: &Arc<Self>, path: PathBuf, network: Network) {
+                let this = self.clone();
+                let epoch = self.replay.next_epoch();
+                spawn(async move {
+                    let recording = tokio::task::spawn_blocking(move || BlockDagRecording::load(&path))
+                        .await
+                        .map_err(|err| Error::custom(err.to_string()))
+                        .and_then(|result| result);
+                    let recording = match recording {
+                        Ok(recording) => recording,
+                        Err(err) => {
+                            runtime().error(format!("Unable to load BlockDAG recording: {err}"));
+                            return Ok(());
+                        }
+                    };
+
+                    if recording.header.network != network {
+                        runtime().error(format!(
+                            "{} {} ({} {network})",
+                            i18n("Unable to replay a BlockDAG recording of"),
+                            recording.header.network,
+                            i18n("current network is"),
+                        ));
+                        return Ok(());
+                    }
+
+                    let replay = this.replay.clone();
+                    if replay.epoch() != epoch {
+                        return Ok(());
+                    }
+
+                    let duration = recording.duration();
+                    replay.begin(duration);
+                    this.service_events.sender.send(BlockDagMonitorEvents::Reset).await?;
+
+                    let mut position = 0.0;
+                    let mut records = recording.records.into_iter().peekable();
+                    while replay.epoch() == epoch {
+                        if !replay.is_paused() {
+                            position += REPLAY_TICK.as_millis() as f64 * replay.speed();
+                        }
+
+                        let mut daa_score = None;
+                        while let Some(record) = records.next_if(|record| record.time as f64 <= position) {
+                            if let BlockDagEvent::BlockAdded(block) = &record.event {
+                                daa_score = Some(block.header.daa_score);
+                            }
+                            this.service_events.sender.send(BlockDagMonitorEvents::Replay(record.event)).await?;
+                        }
+                        replay.update((position as u64).min(duration), daa_score);
+
+                        if records.peek().is_none() {
+                            replay.finish();
+                            runtime().request_repaint();
+                            break;
+                        }
+
+                        task::sleep(REPLAY_TICK).await;
+                    }
+
+                    Ok(())
+                });
+            }
+
+            /// Stop the replay and resume displaying the live BlockDAG stream.
+            pub fn stop_replay(&self) {
+                self.replay.next_epoch();
+                if self.replay.is_active() {
+                    self.replay.end();
+                    self.service_events
+                        .sender
+                        .try_send(BlockDagMonitorEvents::Reset)
+                        .unwrap();
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Service for BlockDagMonitorService {
+    fn name(&self) -> &'static str {
+        "block-dag-monitor"
+    }
+
+    async fn attach_rpc(self: Arc<Self>, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+        self.rpc_api.lock().unwrap().replace(rpc_api.clone());
+        Ok(())
+    }
+
+    async fn detach_rpc(self: Arc<Self>) -> Result<()> {
+        self.rpc_api.lock().unwrap().take();
+
+        Ok(())
+    }
+
+    async fn connect_rpc(self: Arc<Self>) -> Result<()> {
+        self.is_connected.store(true, Ordering::Relaxed);
+        if self.is_enabled.load(Ordering::Relaxed) {
+            self.register_notification_listener().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn disconnect_rpc(self: Arc<Self>) -> Result<()> {
+        self.is_connected.store(false, Ordering::Relaxed);
+        if self.listener_id.lock().unwrap().is_some() {
+            self.unregister_notification_listener().await?;
+        }
+
+        if !self.is_replaying() {
+            self.service_events
+                .sender
+                .try_send(BlockDagMonitorEvents::Reset)
+                .unwrap();
+        }
+
+        Ok(())
+    }
+
+    async fn spawn(self: Arc<Self>) -> Result<()> {
+        let _application_events_sender = self.application_events.sender.clone();
+
+        let mut blocks_by_hash: AHashMap<kaspa_consensus_core::Hash, Arc<RpcBlock>> =
+            AHashMap::default();
+
+        let mut settings = (*self.settings.lock().unwrap()).clone();
+        loop {
+            select! {
+
+                msg = self.notification_channel.receiver.recv().fuse() => {
+                    if let Ok(notification) = msg {
+                        if self.is_replaying() {
+                            continue;
+                        }
+
+                        match notification {
+                            Notification::BlockAdded(block_added_notification) => {
+                                let block = block_added_notification.block.clone();
+                                #[cfg(not(target_arch = "wasm32"))]
+                                self.record(|| BlockDagEvent::BlockAdded((*block).clone()));
+                                self.block_added(block, &mut blocks_by_hash, &settings);
+                            },
+                            Notification::VirtualChainChanged(virtual_chain_changed_notification) => {
+                                let VirtualChainChangedNotification {
+                                    removed_chain_block_hashes,
+                                    added_chain_block_hashes,
+                                    ..
+                                } = virtual_chain_changed_notification;
+
+                                #[cfg(not(target_arch = "wasm32"))]
+                                self.record(|| BlockDagEvent::VirtualChainChanged {
+                                    removed: removed_chain_block_hashes.to_vec(),
+                                    added: added_chain_block_hashes.to_vec(),
+                                });
+                                self.virtual_chain_changed(&removed_chain_block_hashes, &added_chain_block_hashes, &blocks_by_hash, &settings);
+                            },
+                            _ => {
+                                // println!("notification: {:?}", notification);
+                            }
+                        }
+
+                        runtime().request_repaint();
+                    } else {
+                        break;
+                    }
+                },
+
+                msg = self.as_ref().service_events.receiver.recv().fuse() => {
+                    if let Ok(event) = msg {
+                        match event {
+                            BlockDagMonitorEvents::Enable => {
+                                if !self.is_enabled.load(Ordering::Relaxed) {
+                                    self.is_enabled.store(true, Ordering::Relaxed);
+                                    if self.rpc_api().is_some() && self.is_connected.load(Ordering::SeqCst) {
+                                        self.register_notification_listener().await.unwrap();
+                                    }
+                                }
+                            }
+                            BlockDagMonitorEvents::Disable => {
+                                if self.is_enabled.load(Ordering::Relaxed) {
+                                    self.is_enabled.store(false, Ordering::Relaxed);
+                                    self.unregister_notification_listener().await.unwrap();
+                                }
+                            }
+                            BlockDagMonitorEvents::Exit => {
+                                #[cfg(not(target_arch = "wasm32"))]
+                                self.stop_recording().ok();
+
+                                if self.is_enabled.load(Ordering::Relaxed) {
+                                    self.is_enabled.store(false, Ordering::Relaxed);
+                                    self.unregister_notification_listener().await.unwrap();
+                                }
+
+                                break;
+                            }
+                            #[cfg(not(target_arch = "wasm32"))]
+                            BlockDagMonitorEvents::Replay(event) => {
+                                match event {
+                                    BlockDagEvent::BlockAdded(block) => {
+                                        self.block_added(Arc::new(block), &mut blocks_by_hash, &settings);
+                                    }
+                                    BlockDagEvent::VirtualChainChanged { removed, added } => {
+                                        self.virtual_chain_changed(&removed, &added, &blocks_by_hash, &settings);
+                                    }
+                                }
+                                runtime().request_repaint();
+                            }
+                            BlockDagMonitorEvents::Reset => {
+                                self.chain.lock().unwrap().clear();
+                                self.new_blocks.lock().unwrap().clear();
+                                blocks_by_hash.clear();
+                            }
+                            BlockDagMonitorEvents::Settings(new_settings) => {
+                                *self.settings.lock().unwrap() = new_settings.clone();
+                                settings = new_settings;
+                                let mut chain = self.chain.lock().unwrap();
+                                for bucket in chain.values_mut() {
+                                    bucket.reset(&settings);
+                                }
+                            }
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.task_ctl.send(()).await.unwrap();
+        Ok(())
+    }
+
+    fn terminate(self: Arc<Self>) {
+        self.service_events
+            .sender
+            .try_send(BlockDagMonitorEvents::Exit)
+            .unwrap();
+    }
+
+    async fn join(self: Arc<Self>) -> Result<()> {
+        self.task_ctl.recv().await.unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+//!
+//! BlockDAG stream recording.
+//!
+//! A recording is a JSON lines file. The first line contains the
+//! [`RecordingHeader`], followed by one [`BlockDagRecord`] per line, each
+//! holding the time offset in milliseconds from the start of the recording
+//! and the recorded [`BlockDagEvent`].
+//!
+
+use crate::imports::*;
+use kaspa_rpc_core::RpcBlock;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+pub const RECORDING_FILE_EXTENSION: &str = "kdag";
+const RECORDING_FORMAT_VERSION: u32 = 1;
+/// Interval at which buffered records are written to the recording file
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RecordingHeader {
+    pub version: u32,
+    pub network: Network,
+    /// Unixtime in milliseconds at the start of the recording
+    pub timestamp: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlockDagEvent {
+    BlockAdded(RpcBlock),
+    VirtualChainChanged {
+        removed: Vec<KaspaHash>,
+        added: Vec<KaspaHash>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BlockDagRecord {
+    /// Time offset in milliseconds from the start of the recording
+    pub time: u64,
+    pub event: BlockDagEvent,
+}
+
+pub struct BlockDagRecorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    start: f64,
+    records: usize,
+    last_flush: Instant,
+}
+
+impl BlockDagRecorder {
+    pub fn try_new(path: &Path, network: Network) -> Result<Self> {
+        let start = unixtime_as_millis_f64();
+        let mut writer = BufWriter::new(File::create(path)?);
+        let header = RecordingHeader {
+            version: RECORDING_FORMAT_VERSION,
+            network,
+            timestamp: start,
+        };
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer,
+            start,
+            records: 0,
+            last_flush: Instant::now(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    pub fn record(&mut self, event: BlockDagEvent) -> Result<()> {
+        let record = BlockDagRecord {
+            time: (unixtime_as_millis_f64() - self.start).max(0.0) as u64,
+            event,
+        };
+        serde_json::to_writer(&mut self.writer, &record)?;
+        self.writer.write_all(b"\n")?;
+        self.records += 1;
+
+        // limit the records lost if the application terminates abnormally
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.writer.flush()?;
+            self.last_flush = Instant::now();
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+pub struct BlockDagRecording {
+    pub header: RecordingHeader,
+    pub records: Vec<BlockDagRecord>,
+}
+
+impl BlockDagRecording {
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut lines = BufReader::new(File::open(path)?).lines();
+
+        let header = lines
+            .next()
+            .ok_or_else(|| Error::custom("Empty BlockDAG recording"))??;
+        let header: RecordingHeader = serde_json::from_str(&header)?;
+        if header.version != RECORDING_FORMAT_VERSION {
+            return Err(Error::custom(format!(
+                "Unsupported BlockDAG recording format version {}",
+                header.version
+            )));
+        }
+
+        let mut records = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<BlockDagRecord>(&line) {
+                Ok(record) => records.push(record),
+                // a partially written trailing record is ignored
+                Err(err) => {
+                    log_warn!("Unable to read BlockDAG record: {err}");
+                    break;
+                }
+            }
+        }
+
+        Ok(Self { header, records })
+    }
+
+    /// Duration of the recording in milliseconds
+    pub fn duration(&self) -> u64 {
+        self.records
+            .last()
+            .map(|record| record.time)
+            .unwrap_or_default()
+    }
+}
+
+/// Replay state shared between the replay task and the BlockDAG module.
+pub struct BlockDagReplay {
+    epoch: AtomicU64,
+    active: AtomicBool,
+    finished: AtomicBool,
+    paused: AtomicBool,
+    speed: Mutex<f64>,
+    position: AtomicU64,
+    duration: AtomicU64,
+    daa_score: AtomicU64,
+}
+
+impl Default for BlockDagReplay {
+    fn default() -> Self {
+        Self {
+            epoch: AtomicU64::new(0),
+            active: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            speed: Mutex::new(1.0),
+            position: AtomicU64::new(0),
+            duration: AtomicU64::new(0),
+            daa_score: AtomicU64::new(0),
+        }
+    }
+}
+
+impl BlockDagReplay {
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// All recorded events have been replayed; the replayed
+    /// BlockDAG remains displayed until the replay is stopped.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn speed(&self) -> f64 {
+        *self.speed.lock().unwrap()
+    }
+
+    pub fn set_speed(&self, speed: f64) {
+        *self.speed.lock().unwrap() = speed;
+    }
+
+    /// Replay position and recording duration in milliseconds
+    pub fn progress(&self) -> (u64, u64) {
+        (
+            self.position.load(Ordering::SeqCst),
+            self.duration.load(Ordering::SeqCst),
+        )
+    }
+
+    /// DAA score of the last replayed block
+    pub fn daa_score(&self) -> Option<u64> {
+        let daa_score = self.daa_score.load(Ordering::SeqCst);
+        (daa_score != 0).then_some(daa_score)
+    }
+
+    pub(super) fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::SeqCst)
+    }
+
+    /// Invalidate the running replay task, returning the new epoch.
+    pub(super) fn next_epoch(&self) -> u64 {
+        self.epoch.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub(super) fn begin(&self, duration: u64) {
+        self.position.store(0, Ordering::SeqCst);
+        self.duration.store(duration, Ordering::SeqCst);
+        self.daa_score.store(0, Ordering::SeqCst);
+        self.paused.store(false, Ordering::SeqCst);
+        self.finished.store(false, Ordering::SeqCst);
+        self.active.store(true, Ordering::SeqCst);
+    }
+
+    pub(super) fn finish(&self) {
+        self.finished.store(true, Ordering::SeqCst);
+    }
+
+    pub(super) fn end(&self) {
+        self.active.store(false, Ordering::SeqCst);
+        self.finished.store(false, Ordering::SeqCst);
+        self.daa_score.store(0, Ordering::SeqCst);
+    }
+
+    pub(super) fn update(&self, position: u64, daa_score: Option<u64>) {
+        self.position.store(position, Ordering::SeqCst);
+        if let Some(daa_score) = daa_score {
+            self.daa_score.store(daa_score, Ordering::SeqCst);
+        }
+    }
+}
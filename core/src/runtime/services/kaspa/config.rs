@@ -32,11 +32,37 @@ pub struct Config {
     kaspad_daemon_storage_folder_enable: bool,
     kaspad_daemon_storage_folder: String,
     memory_scale: NodeMemoryScale,
+    kaspad_config: KaspadConfig,
+    enable_unsafe_rpc: bool,
+    reset_db: bool,
 }
 
-impl From<NodeSettings> for Config {
-    fn from(node_settings: NodeSettings) -> Self {
-        Self {
+impl Config {
+    /// Instruct the node to delete its existing database
+    /// on startup, causing a full resync from the network.
+    pub fn with_reset_db(mut self) -> Self {
+        self.reset_db = true;
+        self
+    }
+
+    pub fn without_reset_db(mut self) -> Self {
+        self.reset_db = false;
+        self
+    }
+}
+
+/// Node settings are validated when the configuration is created,
+/// so that the in-process node and the daemon are started from
+/// the same validated configuration.
+impl TryFrom<NodeSettings> for Config {
+    type Error = Error;
+    fn try_from(node_settings: NodeSettings) -> Result<Self> {
+        node_settings
+            .kaspad_config
+            .validate()
+            .map_err(Error::custom)?;
+
+        Ok(Self {
             network: node_settings.network,
             enable_upnp: node_settings.enable_upnp,
             enable_grpc: node_settings.enable_grpc,
@@ -46,7 +72,10 @@ impl From<NodeSettings> for Config {
             kaspad_daemon_storage_folder_enable: node_settings.kaspad_daemon_storage_folder_enable,
             kaspad_daemon_storage_folder: node_settings.kaspad_daemon_storage_folder,
             memory_scale: node_settings.memory_scale,
-        }
+            kaspad_config: node_settings.kaspad_config,
+            enable_unsafe_rpc: node_settings.enable_unsafe_rpc,
+            reset_db: false,
+        })
     }
 }
 
@@ -69,16 +98,41 @@ cfg_if! {
                     }
                 }
 
-                args.perf_metrics = true;
+                let kaspad_config = config.kaspad_config;
+
+                args.perf_metrics = kaspad_config.perf_metrics;
                 args.perf_metrics_interval_sec = 1;
                 args.yes = true;
-                args.utxoindex = true;
+                args.utxoindex = kaspad_config.utxoindex;
+                args.archival = kaspad_config.archival;
+                args.outbound_target = kaspad_config.outbound_target;
+                args.inbound_limit = kaspad_config.inbound_limit;
+                args.rpc_max_clients = kaspad_config.rpc_max_clients;
+                args.log_level = kaspad_config.log_level.to_string();
+                args.connect_peers = kaspad_config
+                    .connect_peers
+                    .iter()
+                    .map(|peer| ContextualNetAddress::from_str(peer))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|err| Error::custom(format!("Invalid connect peer address: {err}")))?;
+                args.add_peers = kaspad_config
+                    .add_peers
+                    .iter()
+                    .map(|peer| ContextualNetAddress::from_str(peer))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|err| Error::custom(format!("Invalid peer address: {err}")))?;
                 args.disable_upnp = !config.enable_upnp;
+                args.reset_db = config.reset_db;
+                args.unsafe_rpc = config.enable_unsafe_rpc;
 
                 if config.enable_grpc {
                     args.rpclisten = Some(config.grpc_network_interface.into());
                 }
 
+                if config.kaspad_daemon_storage_folder_enable && !config.kaspad_daemon_storage_folder.is_empty() {
+                    args.appdir = Some(config.kaspad_daemon_storage_folder.clone());
+                }
+
                 args.user_agent_comments = vec![user_agent_comment()];
 
                 // TODO - parse custom args and overlap on top of the defaults
@@ -103,10 +157,40 @@ cfg_if! {
                     }
                 }
 
-                args.push("--perf-metrics");
-                args.push("--perf-metrics-interval-sec=1");
+                let kaspad_config = &config.kaspad_config;
+
+                if kaspad_config.perf_metrics {
+                    args.push("--perf-metrics");
+                    args.push("--perf-metrics-interval-sec=1");
+                }
                 args.push("--yes");
-                args.push("--utxoindex");
+                if config.reset_db {
+                    args.push("--reset-db");
+                }
+                if kaspad_config.utxoindex {
+                    args.push("--utxoindex");
+                }
+                if kaspad_config.archival {
+                    args.push("--archival");
+                }
+                if kaspad_config.outbound_target != KaspadConfig::DEFAULT_OUTBOUND_TARGET {
+                    args.push(format!("--outpeers={}", kaspad_config.outbound_target));
+                }
+                if kaspad_config.inbound_limit != KaspadConfig::DEFAULT_INBOUND_LIMIT {
+                    args.push(format!("--maxinpeers={}", kaspad_config.inbound_limit));
+                }
+                if kaspad_config.rpc_max_clients != KaspadConfig::DEFAULT_RPC_MAX_CLIENTS {
+                    args.push(format!("--rpcmaxclients={}", kaspad_config.rpc_max_clients));
+                }
+                kaspad_config.connect_peers.iter().for_each(|peer| {
+                    args.push(format!("--connect={peer}"));
+                });
+                kaspad_config.add_peers.iter().for_each(|peer| {
+                    args.push(format!("--addpeer={peer}"));
+                });
+                if kaspad_config.log_level != NodeLogLevel::default() {
+                    args.push(format!("--loglevel={}", kaspad_config.log_level));
+                }
 
                 match config.memory_scale {
                     NodeMemoryScale::Default => {},
@@ -127,6 +211,10 @@ cfg_if! {
 
                 args.push("--rpclisten-borsh=default");
 
+                if config.enable_unsafe_rpc && !(config.kaspad_daemon_args_enable && config.kaspad_daemon_args.contains("--unsaferpc")) {
+                    args.push("--unsaferpc");
+                }
+
                 args.push(format!("--uacomment={}", user_agent_comment()));
 
                 if config.kaspad_daemon_storage_folder_enable && !config.kaspad_daemon_storage_folder.is_empty() && !(config.kaspad_daemon_args_enable && config.kaspad_daemon_args.contains("--appdir")) {
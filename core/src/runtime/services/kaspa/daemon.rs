@@ -1,10 +1,44 @@
 use crate::imports::*;
 use crate::runtime::services::kaspa::{Config, KaspadServiceEvents};
+use std::collections::VecDeque;
 use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use tokio::io::{AsyncBufReadExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdout, Command};
 use workflow_core::prelude::DuplexChannel;
 
+/// Number of the most recent node log lines retained for the crash report.
+const CRASH_LOG_LINES: usize = 128;
+/// Number of consecutive crashes after which the node is no longer restarted.
+const MAX_CRASHES: usize = 5;
+/// Initial restart delay, doubled after each consecutive crash.
+const RESTART_BACKOFF_BASE: Duration = Duration::from_secs(2);
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(60);
+/// A node that has been running for this long before exiting is considered
+/// stable, resetting the consecutive crash counter.
+const STABLE_RUN_DURATION: Duration = Duration::from_secs(10 * 60);
+
+type Process = (Child, Lines<BufReader<ChildStdout>>);
+
+/// Report of an unexpected node process exit.
+#[derive(Debug, Clone)]
+pub struct DaemonCrash {
+    /// Number of consecutive crashes
+    pub count: usize,
+    /// Process exit status or the restart error
+    pub status: String,
+    /// Last log lines produced by the node before the crash
+    pub log: Vec<String>,
+    /// Delay before the next restart attempt; `None`
+    /// if the node will no longer be restarted.
+    pub restart_delay: Option<Duration>,
+}
+
+impl DaemonCrash {
+    pub fn is_final(&self) -> bool {
+        self.restart_delay.is_none()
+    }
+}
+
 /// Termination method with which to terminate the kaspad process.
 /// This should remain Sigkill until Kaspad learns to terminate
 /// rapidly during it's sync process.
@@ -22,6 +56,7 @@ struct Inner {
     service_events: Channel<KaspadServiceEvents>,
     task_ctl: DuplexChannel,
     termination_method: TerminationMethod,
+    crash_log: Mutex<VecDeque<String>>,
 }
 
 #[derive(Clone)]
@@ -39,6 +74,7 @@ impl Daemon {
                 service_events: (*service_events).clone(),
                 task_ctl: DuplexChannel::oneshot(),
                 termination_method: TerminationMethod::default(),
+                crash_log: Mutex::new(VecDeque::with_capacity(CRASH_LOG_LINES)),
             }),
         }
     }
@@ -59,11 +95,8 @@ impl Daemon {
             println!("kaspad sigterm error: {:?}", err);
         }
     }
-}
 
-#[async_trait]
-impl super::Kaspad for Daemon {
-    async fn start(self: Arc<Self>, config: Config) -> Result<()> {
+    fn spawn_process(&self, config: Config) -> Result<Process> {
         let mut cmd = if let Some(path) = self.inner().path.clone() {
             Command::new(path)
         } else {
@@ -76,17 +109,41 @@ impl super::Kaspad for Daemon {
             .env("KASPA_NG_DAEMON", "1")
             .stdout(Stdio::piped());
 
-        let is_running = self.inner().is_running.clone();
-        is_running.store(true, Ordering::SeqCst);
         let mut child = cmd.spawn().map_err(Error::NodeStartupError)?;
         let stdout = child.stdout.take().ok_or(Error::NodeStdoutHandleError)?;
         *self.inner.pid.lock().unwrap() = child.id();
+        self.inner.crash_log.lock().unwrap().clear();
 
-        let mut reader = BufReader::new(stdout).lines();
-        let stdout_relay_sender = self.inner.service_events.sender.clone();
-        let task_ctl = self.inner.task_ctl.clone();
+        Ok((child, BufReader::new(stdout).lines()))
+    }
 
-        let this = self.clone();
+    fn push_crash_log(&self, line: &str) {
+        let mut crash_log = self.inner.crash_log.lock().unwrap();
+        if crash_log.len() == CRASH_LOG_LINES {
+            crash_log.pop_front();
+        }
+        crash_log.push_back(line.to_string());
+    }
+
+    fn crash_log(&self) -> Vec<String> {
+        self.inner
+            .crash_log
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Relay the process output until the process exits. Returns `None`
+    /// if the process has been terminated on request, or the exit status
+    /// if the process has exited unexpectedly.
+    async fn run_process(&self, process: Process) -> Option<String> {
+        let (mut child, mut reader) = process;
+        let task_ctl = &self.inner.task_ctl;
+        let stdout_relay_sender = &self.inner.service_events.sender;
+        let mut terminate = false;
+        let mut is_relay_closed = false;
 
         cfg_if::cfg_if! {
             if #[cfg(unix)] {
@@ -96,43 +153,129 @@ impl super::Kaspad for Daemon {
             }
         }
 
-        tokio::spawn(async move {
-            loop {
-                select! {
-                    _ = task_ctl.request.recv().fuse() => {
-                        if this.inner.termination_method == TerminationMethod::Sigterm && is_unix {
-                            let pid = this.inner.pid.lock().unwrap();
-                            if let Some(_pid) = *pid {
-                                #[cfg(unix)]
-                                this.sigterm(_pid);
-                            }
-                        } else if let Err(err) = child.start_kill() {
-                            println!("kaspa daemon start_kill error: {:?}", err);
+        loop {
+            select! {
+                _ = task_ctl.request.recv().fuse() => {
+                    terminate = true;
+                    if self.inner.termination_method == TerminationMethod::Sigterm && is_unix {
+                        let pid = self.inner.pid.lock().unwrap();
+                        if let Some(_pid) = *pid {
+                            #[cfg(unix)]
+                            self.sigterm(_pid);
                         }
+                    } else if let Err(err) = child.start_kill() {
+                        println!("kaspa daemon start_kill error: {:?}", err);
                     }
-                    status = child.wait().fuse() => {
-                        match status {
-                            Ok(_status) => {
-                                // println!("kaspad shutdown: {:?}", _status);
-                            }
-                            Err(err) => {
-                                println!("kaspad shutdown error: {:?}", err);
-                            }
+                }
+                status = child.wait().fuse() => {
+                    return match status {
+                        Ok(_) if terminate => None,
+                        Err(err) if terminate => {
+                            println!("kaspad shutdown error: {:?}", err);
+                            None
                         }
-                        is_running.store(false,Ordering::SeqCst);
-                        break;
-                    }
+                        Ok(status) => Some(status.to_string()),
+                        Err(err) => Some(err.to_string()),
+                    };
+                }
 
-                    line = reader.next_line().fuse() => {
-                        if let Ok(Some(line)) = line {
-                            // println!("kaspad: {}", line);
-                            stdout_relay_sender.send(KaspadServiceEvents::Stdout { line }).await.unwrap();
+                line = reader.next_line().fuse() => {
+                    if let Ok(Some(line)) = line {
+                        // println!("kaspad: {}", line);
+                        self.push_crash_log(&line);
+                        if !is_relay_closed {
+                            if let Err(err) = stdout_relay_sender.send(KaspadServiceEvents::Stdout { line }).await {
+                                // the service has shut down; terminate the process
+                                log_warn!("kaspad stdout relay error: {err}");
+                                is_relay_closed = true;
+                                terminate = true;
+                                if let Err(err) = child.start_kill() {
+                                    println!("kaspa daemon start_kill error: {:?}", err);
+                                }
+                            }
                         }
                     }
                 }
             }
+        }
+    }
+}
+
+fn restart_delay(crashes: usize) -> Duration {
+    let factor = 1u32 << (crashes.saturating_sub(1).min(16) as u32);
+    RESTART_BACKOFF_BASE
+        .saturating_mul(factor)
+        .min(RESTART_BACKOFF_MAX)
+}
+
+#[async_trait]
+impl super::Kaspad for Daemon {
+    async fn start(self: Arc<Self>, config: Config) -> Result<()> {
+        let is_running = self.inner().is_running.clone();
+        is_running.store(true, Ordering::SeqCst);
+        let mut process = match self.spawn_process(config.clone()) {
+            Ok(process) => Ok(process),
+            Err(err) => {
+                is_running.store(false, Ordering::SeqCst);
+                return Err(err);
+            }
+        };
+
+        // the database reset applies only to the initial start
+        let config = config.without_reset_db();
+        let this = self.clone();
+
+        tokio::spawn(async move {
+            let mut crashes = 0;
+
+            loop {
+                let started = Instant::now();
+                let status = match process {
+                    Ok(process) => match this.run_process(process).await {
+                        Some(status) => status,
+                        None => break,
+                    },
+                    Err(err) => err.to_string(),
+                };
+
+                if started.elapsed() > STABLE_RUN_DURATION {
+                    crashes = 0;
+                }
+                crashes += 1;
+
+                let restart_delay = (crashes < MAX_CRASHES).then(|| restart_delay(crashes));
+                let crash = DaemonCrash {
+                    count: crashes,
+                    status,
+                    log: this.crash_log(),
+                    restart_delay,
+                };
+                if let Err(err) = this
+                    .inner
+                    .service_events
+                    .sender
+                    .send(KaspadServiceEvents::DaemonCrash { crash })
+                    .await
+                {
+                    // the service has shut down; do not restart the node
+                    log_warn!("kaspad crash report error: {err}");
+                    break;
+                }
+
+                let Some(restart_delay) = restart_delay else {
+                    break;
+                };
+
+                select! {
+                    _ = this.inner.task_ctl.request.recv().fuse() => break,
+                    _ = task::sleep(restart_delay).fuse() => {}
+                }
+
+                process = this.spawn_process(config.clone());
+            }
 
-            task_ctl.response.send(()).await.unwrap();
+            is_running.store(false, Ordering::SeqCst);
+            this.inner.task_ctl.response.send(()).await.unwrap();
         });
 
         Ok(())
@@ -0,0 +1,99 @@
+use crate::imports::*;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_utils_tower::counters::TowerConnectionCounters;
+use kaspa_wallet_core::rpc::{NotificationMode, Rpc, RpcCtl};
+use workflow_core::channel::unbounded;
+
+const GRPC_SCHEMA: &str = "grpc://";
+
+/// Normalize a user-supplied gRPC address into the `grpc://host:port`
+/// form expected by [`GrpcClient`]. The default RPC port of the given
+/// network is used if the address does not specify one.
+pub fn parse_grpc_url(url: &str, network: Network) -> Result<String> {
+    let address = url.trim();
+    let address = address.strip_prefix(GRPC_SCHEMA).unwrap_or(address);
+    let address = address.trim_end_matches('/');
+
+    if address.is_empty() || address.contains('/') {
+        return Err(Error::custom(format!("Invalid gRPC address: '{url}'")));
+    }
+
+    let default_port = NetworkType::from(network).default_rpc_port();
+    let address = match address.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+            port.parse::<u16>()
+                .map_err(|_| Error::custom(format!("Invalid gRPC port: '{port}'")))?;
+            address.to_string()
+        }
+        // bare IPv6 address without a port
+        Some(_) if address.parse::<std::net::Ipv6Addr>().is_ok() => {
+            format!("[{address}]:{default_port}")
+        }
+        Some(_) => return Err(Error::custom(format!("Invalid gRPC address: '{url}'"))),
+        None => format!("{address}:{default_port}"),
+    };
+
+    Ok(format!("{GRPC_SCHEMA}{address}"))
+}
+
+/// Connect to a gRPC node and create an [`Rpc`] binding for the wallet.
+/// The client is created in the multi-listener notification mode, which
+/// allows the wallet and the application services to register their own
+/// notification listeners just as they do with the wRPC client.
+pub async fn create_rpc(url: &str, network: Network) -> Result<Rpc> {
+    let url = parse_grpc_url(url, network)?;
+
+    let (connection_event_sender, connection_events) = unbounded();
+    let grpc_client = GrpcClient::connect(
+        NotificationMode::MultiListeners,
+        url.clone(),
+        true,
+        Some(connection_event_sender),
+        false,
+        None,
+        Arc::new(TowerConnectionCounters::default()),
+    )
+    .await
+    .map_err(|err| Error::custom(format!("Unable to connect to {url}: {err}")))?;
+    grpc_client.start(None).await;
+    // the initial connection is signaled once the wallet is bound to the client
+    while connection_events.try_recv().is_ok() {}
+
+    let grpc_client = Arc::new(grpc_client);
+    let rpc_ctl = RpcCtl::with_descriptor(url);
+    spawn_connection_monitor(&grpc_client, connection_events, rpc_ctl.clone());
+    let rpc_api: Arc<DynRpcApi> = grpc_client;
+    Ok(Rpc::new(rpc_api, rpc_ctl))
+}
+
+/// The gRPC client reconnects internally, posting connection events
+/// on each state change. The client state is relayed to the wallet
+/// via [`RpcCtl`] on each event. The monitor exits once the client
+/// is released, closing the event channel.
+fn spawn_connection_monitor<T>(
+    grpc_client: &Arc<GrpcClient>,
+    connection_events: Receiver<T>,
+    rpc_ctl: RpcCtl,
+) where
+    T: Send + 'static,
+{
+    let grpc_client = Arc::downgrade(grpc_client);
+    spawn(async move {
+        while connection_events.recv().await.is_ok() {
+            let Some(is_connected) = grpc_client.upgrade().map(|client| client.is_connected())
+            else {
+                break;
+            };
+
+            if is_connected != rpc_ctl.is_connected() {
+                if is_connected {
+                    rpc_ctl.signal_open().await?;
+                } else {
+                    rpc_ctl.signal_close().await?;
+                }
+            }
+        }
+
+        Ok(())
+    });
+}
@@ -1,5 +1,18 @@
 use crate::imports::*;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
 
+/// Name of the node log file within the log folder.
+pub const LOG_FILE_NAME: &str = "kaspad";
+const LOG_FILE_EXTENSION: &str = "log";
+/// Size at which the log file is rotated.
+const LOG_FILE_MAX_SIZE: u64 = 16 * 1024 * 1024;
+/// Number of rotated log files retained in addition to the current one.
+const LOG_FILE_HISTORY: usize = 5;
+/// Interval at which buffered log lines are flushed to disk.
+const LOG_FILE_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone)]
 pub enum Log {
     Debug(String),
     Trace(String),
@@ -23,6 +36,8 @@ impl From<&str> for Log {
                 match kind {
                     "WARN " => Log::Warning(format!("{time} {text}")),
                     "ERROR" => Log::Error(format!("{time} {text}")),
+                    "DEBUG" => Log::Debug(format!("{time} {text}")),
+                    "TRACE" => Log::Trace(format!("{time} {text}")),
                     _ => {
                         if text.starts_with("Processed") {
                             Log::Processed(format!("{time} {text}"))
@@ -53,17 +68,161 @@ impl From<&Log> for RichText {
     }
 }
 
+impl Log {
+    pub fn text(&self) -> &str {
+        match self {
+            Log::Info(text) => text,
+            Log::Error(text) => text,
+            Log::Warning(text) => text,
+            Log::Debug(text) => text,
+            Log::Trace(text) => text,
+            Log::Processed(text) => text,
+        }
+    }
+}
+
 impl std::fmt::Display for Log {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let text = match self {
-            Log::Info(text) => text.to_owned(),
-            Log::Error(text) => text.to_owned(),
-            Log::Warning(text) => text.to_owned(),
-            Log::Debug(text) => text.to_owned(),
-            Log::Trace(text) => text.to_owned(),
-            Log::Processed(text) => text.to_owned(),
+        write!(f, "{}", self.text())
+    }
+}
+
+/// Node log file writer. The log file is rotated when it reaches
+/// [`LOG_FILE_MAX_SIZE`] as well as at the start of each session,
+/// retaining [`LOG_FILE_HISTORY`] previous files (`kaspad.1.log` being
+/// the most recent one). Lines are buffered and flushed every
+/// [`LOG_FILE_FLUSH_INTERVAL`] as well as on warnings and errors.
+pub struct LogFile {
+    folder: PathBuf,
+    writer: Option<BufWriter<File>>,
+    size: u64,
+    max_size: u64,
+    last_flush: Instant,
+    /// Number of rotations performed during the current session
+    session_rotations: usize,
+}
+
+impl LogFile {
+    /// Log folder within the application data folder (`~/.kaspa-ng/logs`).
+    pub fn default_folder() -> Result<PathBuf> {
+        let storage_folder = kaspa_wallet_core::storage::local::default_storage_folder();
+        Ok(workflow_store::fs::resolve_path(storage_folder)?.join("logs"))
+    }
+
+    pub fn try_new() -> Result<Self> {
+        Self::try_new_with_folder(Self::default_folder()?, LOG_FILE_MAX_SIZE)
+    }
+
+    /// Create a log file within `folder`, rotated at `max_size` bytes.
+    pub(crate) fn try_new_with_folder(folder: PathBuf, max_size: u64) -> Result<Self> {
+        fs::create_dir_all(&folder)?;
+
+        let mut log_file = Self {
+            folder,
+            writer: None,
+            size: 0,
+            max_size,
+            last_flush: Instant::now(),
+            session_rotations: 0,
         };
 
-        write!(f, "{}", text)
+        // start each session with a new file
+        if fs::metadata(log_file.path(0))
+            .map(|m| m.len() > 0)
+            .unwrap_or(false)
+        {
+            log_file.rotate()?;
+        }
+        log_file.session_rotations = 0;
+        log_file.open()?;
+
+        Ok(log_file)
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    fn path(&self, index: usize) -> PathBuf {
+        if index == 0 {
+            self.folder
+                .join(format!("{LOG_FILE_NAME}.{LOG_FILE_EXTENSION}"))
+        } else {
+            self.folder
+                .join(format!("{LOG_FILE_NAME}.{index}.{LOG_FILE_EXTENSION}"))
+        }
+    }
+
+    fn open(&mut self) -> Result<()> {
+        let path = self.path(0);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.size = file.metadata()?.len();
+        self.writer = Some(BufWriter::new(file));
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.writer.take();
+
+        let oldest = self.path(LOG_FILE_HISTORY);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for index in (0..LOG_FILE_HISTORY).rev() {
+            let path = self.path(index);
+            if path.exists() {
+                fs::rename(path, self.path(index + 1))?;
+            }
+        }
+
+        self.session_rotations += 1;
+        Ok(())
+    }
+
+    pub fn write(&mut self, line: &str) -> Result<()> {
+        if self.size + line.len() as u64 + 1 > self.max_size {
+            self.rotate()?;
+            self.open()?;
+        }
+
+        if let Some(writer) = self.writer.as_mut() {
+            writeln!(writer, "{line}")?;
+            self.size += line.len() as u64 + 1;
+        }
+
+        if matches!(Log::from(line), Log::Warning(_) | Log::Error(_))
+            || self.last_flush.elapsed() >= LOG_FILE_FLUSH_INTERVAL
+        {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(writer) = self.writer.as_mut() {
+            writer.flush()?;
+        }
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+
+    /// Write logs produced during the current session into the target
+    /// file. Files rotated out of the history during a long session are
+    /// no longer available.
+    pub fn export_session(&mut self, target: &Path) -> Result<()> {
+        self.flush()?;
+
+        let mut output = File::create(target)?;
+        for index in (0..=self.session_rotations.min(LOG_FILE_HISTORY)).rev() {
+            let path = self.path(index);
+            if path.exists() {
+                let mut input = File::open(path)?;
+                std::io::copy(&mut input, &mut output)?;
+            }
+        }
+        output.flush()?;
+
+        Ok(())
     }
 }
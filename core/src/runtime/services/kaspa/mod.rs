@@ -15,6 +15,11 @@ cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
         #[cfg(not(target_arch = "wasm32"))]
         use kaspa_rpc_service::service::RpcCoreService;
+        use kaspa_grpc_client::GrpcClient;
+
+        /// Initial gRPC reconnection delay, doubled after each consecutive failure.
+        const GRPC_RECONNECT_DELAY: Duration = Duration::from_millis(5000);
+        const GRPC_RECONNECT_DELAY_MAX: Duration = Duration::from_secs(60);
 
         const LOG_BUFFER_LINES: usize = 4096;
         const LOG_BUFFER_MARGIN: usize = 128;
@@ -28,9 +33,11 @@ cfg_if! {
         pub mod config;
         pub use config::Config;
         pub mod daemon;
+        pub use daemon::DaemonCrash;
+        pub mod grpc;
         pub mod inproc;
         pub mod logs;
-        use logs::Log;
+        use logs::{Log, LogFile};
         pub use kaspad_lib::args::Args;
 
         #[async_trait]
@@ -46,6 +53,8 @@ cfg_if! {
             StartExternalAsDaemon { path: PathBuf, config: Config, network : Network },
             StartRemoteConnection { rpc_config : RpcConfig, network : Network },
             Stdout { line : String },
+            DaemonCrash { crash : DaemonCrash },
+            StopLocalNode { sender : Sender<()> },
             Disable { network : Network },
             Exit,
         }
@@ -82,10 +91,24 @@ pub struct KaspaService {
     pub network: Mutex<Network>,
     pub wallet: Arc<dyn WalletApi>,
     pub services_start_instant: Mutex<Option<Instant>>,
+    pub connection_epoch: AtomicU64,
     #[cfg(not(target_arch = "wasm32"))]
     pub kaspad: Mutex<Option<Arc<dyn Kaspad + Send + Sync + 'static>>>,
     #[cfg(not(target_arch = "wasm32"))]
     pub logs: Mutex<Vec<Log>>,
+    /// Incremented each time a line is added to the log buffer
+    #[cfg(not(target_arch = "wasm32"))]
+    pub logs_generation: AtomicU64,
+    /// Created when the first log line is received
+    #[cfg(not(target_arch = "wasm32"))]
+    pub log_file: Mutex<Option<LogFile>>,
+    /// Set if the log file could not be created or written
+    #[cfg(not(target_arch = "wasm32"))]
+    pub is_log_file_disabled: AtomicBool,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub daemon_crash: Mutex<Option<DaemonCrash>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub remote_connection_failures: AtomicUsize,
     pub connect_on_startup: Option<NodeSettings>,
 }
 
@@ -125,10 +148,21 @@ impl KaspaService {
             network: Mutex::new(settings.node.network),
             wallet,
             services_start_instant: Mutex::new(None),
+            connection_epoch: AtomicU64::new(0),
             #[cfg(not(target_arch = "wasm32"))]
             kaspad: Mutex::new(None),
             #[cfg(not(target_arch = "wasm32"))]
             logs: Mutex::new(Vec::new()),
+            #[cfg(not(target_arch = "wasm32"))]
+            logs_generation: AtomicU64::new(0),
+            #[cfg(not(target_arch = "wasm32"))]
+            log_file: Mutex::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            is_log_file_disabled: AtomicBool::new(false),
+            #[cfg(not(target_arch = "wasm32"))]
+            daemon_crash: Mutex::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            remote_connection_failures: AtomicUsize::new(0),
         }
     }
 
@@ -155,7 +189,7 @@ impl KaspaService {
         self.kaspad.lock().unwrap().replace(kaspad);
     }
 
-    pub fn create_rpc_client(config: &RpcConfig, network: Network) -> Result<Rpc> {
+    pub async fn create_rpc_client(config: &RpcConfig, network: Network) -> Result<Rpc> {
         match config {
             RpcConfig::Wrpc { url, encoding } => {
                 // log_warn!("create_rpc_client - RPC URL: {:?}", url);
@@ -175,8 +209,16 @@ impl KaspaService {
                 let rpc_api: Arc<DynRpcApi> = wrpc_client;
                 Ok(Rpc::new(rpc_api, rpc_ctl))
             }
-            RpcConfig::Grpc { url: _ } => {
-                unimplemented!("GPRC is not currently supported")
+            RpcConfig::Grpc { url } => {
+                cfg_if! {
+                    if #[cfg(not(target_arch = "wasm32"))] {
+                        let url = url.clone().unwrap_or_else(|| "127.0.0.1".to_string());
+                        grpc::create_rpc(url.as_str(), network).await
+                    } else {
+                        let _ = url;
+                        Err(Error::custom("gRPC connections are not supported in the browser"))
+                    }
+                }
             }
         }
     }
@@ -202,6 +244,14 @@ impl KaspaService {
                         .is_ok()
                     {
                         wallet.rpc_ctl().signal_open().await?;
+                    } else if let Ok(grpc_client) =
+                        wallet.rpc_api().clone().downcast_arc::<GrpcClient>()
+                    {
+                        // subsequent connection changes are relayed by the
+                        // connection monitor created with the client
+                        if grpc_client.is_connected() {
+                            wallet.rpc_ctl().signal_open().await?;
+                        }
                     } else {
                         unimplemented!("connect_rpc_client(): RPC client is not supported")
                     }
@@ -224,6 +274,63 @@ impl KaspaService {
         self.logs.lock().unwrap()
     }
 
+    /// Log buffer generation, changes each time a line is added.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn logs_generation(&self) -> u64 {
+        self.logs_generation.load(Ordering::SeqCst)
+    }
+
+    /// Last unexpected exit of the node daemon, if any. Cleared
+    /// when the node settings are applied or the node is stopped.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn daemon_crash(&self) -> Option<DaemonCrash> {
+        self.daemon_crash.lock().unwrap().clone()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn clear_daemon_crash(&self) {
+        self.daemon_crash.lock().unwrap().take();
+    }
+
+    /// Folder containing node log files, available once
+    /// the node has produced logs written to disk.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn log_folder(&self) -> Option<PathBuf> {
+        self.log_file
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|file| file.folder().to_path_buf())
+    }
+
+    /// Flush buffered node log lines to the log file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn flush_logs(&self) {
+        if let Some(file) = self.log_file.lock().unwrap().as_mut() {
+            if let Err(err) = file.flush() {
+                log_warn!("Unable to flush node log file: {err}");
+            }
+        }
+    }
+
+    /// Export node logs of the current session into the target file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn export_logs(&self, target: &Path) -> Result<()> {
+        if let Some(file) = self.log_file.lock().unwrap().as_mut() {
+            return file.export_session(target);
+        }
+
+        // log file is not available, export the log buffer
+        let logs = self
+            .logs()
+            .iter()
+            .map(|log| log.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(target, logs)?;
+        Ok(())
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn update_logs(&self, line: String) {
         {
@@ -232,6 +339,29 @@ impl KaspaService {
                 logs.drain(0..LOG_BUFFER_MARGIN);
             }
             logs.push(line.as_str().into());
+            self.logs_generation.fetch_add(1, Ordering::SeqCst);
+        }
+
+        if !self.is_log_file_disabled.load(Ordering::SeqCst) {
+            let mut log_file = self.log_file.lock().unwrap();
+            if log_file.is_none() {
+                match LogFile::try_new() {
+                    Ok(file) => {
+                        log_file.replace(file);
+                    }
+                    Err(err) => {
+                        log_warn!("Unable to create node log file: {err}");
+                        self.is_log_file_disabled.store(true, Ordering::SeqCst);
+                    }
+                }
+            }
+            if let Some(file) = log_file.as_mut() {
+                if let Err(err) = file.write(line.as_str()) {
+                    log_warn!("Unable to write node log file: {err}");
+                    log_file.take();
+                    self.is_log_file_disabled.store(true, Ordering::SeqCst);
+                }
+            }
         }
 
         if update_logs_flag().load(Ordering::SeqCst) {
@@ -252,13 +382,20 @@ impl KaspaService {
             {
                 wrpc_client.url()
             } else {
-                None
+                wallet.rpc_ctl().descriptor()
             }
         } else {
             None
         }
     }
 
+    /// RPC API of the connected node, if any.
+    pub fn rpc_api(&self) -> Option<Arc<dyn RpcApi>> {
+        self.core_wallet()
+            .filter(|wallet| wallet.has_rpc() && wallet.is_connected())
+            .map(|wallet| wallet.rpc_api())
+    }
+
     fn is_wrpc_client(&self) -> bool {
         if let Some(wallet) = self.core_wallet() {
             wallet.has_rpc()
@@ -277,6 +414,10 @@ impl KaspaService {
             if let Ok(wrpc_client) = wallet.rpc_api().clone().downcast_arc::<KaspaRpcClient>() {
                 wrpc_client.disconnect().await?;
             } else {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Ok(grpc_client) = wallet.rpc_api().clone().downcast_arc::<GrpcClient>() {
+                    grpc_client.disconnect().await?;
+                }
                 wallet.rpc_ctl().signal_close().await?;
             }
         }
@@ -285,6 +426,9 @@ impl KaspaService {
 
     pub async fn stop_all_services(&self) -> Result<()> {
         self.services_start_instant.lock().unwrap().take();
+        self.connection_epoch.fetch_add(1, Ordering::SeqCst);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.clear_daemon_crash();
 
         if let Some(wallet) = self.core_wallet() {
             if !wallet.has_rpc() {
@@ -374,6 +518,19 @@ impl KaspaService {
     }
 
     pub fn update_services(&self, node_settings: &NodeSettings, options: Option<RpcOptions>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.remote_connection_failures.store(0, Ordering::SeqCst);
+
+        // the runtime is not registered while the application is starting up
+        if let Some(runtime) = crate::runtime::try_runtime() {
+            runtime
+                .server_monitor_service()
+                .update_node_settings(node_settings);
+            runtime
+                .peer_monitor_service()
+                .update_node_settings(node_settings);
+        }
+
         match KaspadServiceEvents::from_node_settings(node_settings, options) {
             Ok(event) => {
                 self.service_events
@@ -384,15 +541,129 @@ impl KaspaService {
                     });
             }
             Err(err) => {
-                println!("KaspadServiceEvents::try_from() error: {}", err);
+                log_error!("Unable to apply node settings: {err}");
+                self.application_events
+                    .sender
+                    .try_send(Events::Error(Box::new(format!(
+                        "{} {err}",
+                        i18n("Unable to apply node settings:")
+                    ))))
+                    .ok();
             }
         }
     }
 
+    /// Stop the local node (if any) without changing node settings,
+    /// resolving once the node has shut down. The node can be restarted
+    /// using [`update_services`](Self::update_services).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn stop_local_node(&self) -> Result<()> {
+        let (sender, receiver) = oneshot();
+        self.service_events
+            .sender
+            .send(KaspadServiceEvents::StopLocalNode { sender })
+            .await?;
+        receiver
+            .recv()
+            .await
+            .map_err(|_| Error::custom("Unable to stop the node"))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn is_local_node_running(&self) -> bool {
+        self.kaspad.lock().unwrap().is_some()
+    }
+
+    /// Restart the local node with a request to reset its database,
+    /// triggering a full resync. Has no effect for remote nodes.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resync(&self, node_settings: &NodeSettings) -> Result<()> {
+        let event = match KaspadServiceEvents::from_node_settings(node_settings, None)? {
+            KaspadServiceEvents::StartInternalInProc { config, network } => {
+                KaspadServiceEvents::StartInternalInProc {
+                    config: config.with_reset_db(),
+                    network,
+                }
+            }
+            KaspadServiceEvents::StartInternalAsDaemon { config, network } => {
+                KaspadServiceEvents::StartInternalAsDaemon {
+                    config: config.with_reset_db(),
+                    network,
+                }
+            }
+            KaspadServiceEvents::StartExternalAsDaemon {
+                path,
+                config,
+                network,
+            } => KaspadServiceEvents::StartExternalAsDaemon {
+                path,
+                config: config.with_reset_db(),
+                network,
+            },
+            _ => {
+                return Err(Error::custom(
+                    "Database resync is only available for a local node",
+                ))
+            }
+        };
+
+        self.service_events.sender.try_send(event)?;
+        Ok(())
+    }
+
     fn network(&self) -> Network {
         *self.network.lock().unwrap()
     }
 
+    /// Unlike the wRPC client, the gRPC client can only be created once
+    /// the node is reachable. Report the first failure and schedule
+    /// another attempt with an increasing delay, unless the connection
+    /// settings change in the meantime.
+    fn retry_remote_connection(
+        self: &Arc<Self>,
+        rpc_config: RpcConfig,
+        network: Network,
+        err: Error,
+    ) -> Result<()> {
+        cfg_if! {
+            if #[cfg(not(target_arch = "wasm32"))] {
+                if !matches!(rpc_config, RpcConfig::Grpc { .. }) {
+                    return Err(err);
+                }
+
+                log_warn!("{err}");
+                let failures = self.remote_connection_failures.fetch_add(1, Ordering::SeqCst) + 1;
+                if failures == 1 {
+                    self.notify(Events::Notify {
+                        user_notification: UserNotification::warning(err.to_string()).short(),
+                    })?;
+                }
+
+                let factor = 1u32 << (failures - 1).min(16) as u32;
+                let delay = GRPC_RECONNECT_DELAY
+                    .saturating_mul(factor)
+                    .min(GRPC_RECONNECT_DELAY_MAX);
+                let this = self.clone();
+                let epoch = self.connection_epoch.load(Ordering::SeqCst);
+                spawn(async move {
+                    task::sleep(delay).await;
+                    if this.connection_epoch.load(Ordering::SeqCst) == epoch {
+                        this.service_events
+                            .sender
+                            .send(KaspadServiceEvents::StartRemoteConnection { rpc_config, network })
+                            .await?;
+                    }
+                    Ok(())
+                });
+
+                Ok(())
+            } else {
+                let _ = (rpc_config, network);
+                Err(err)
+            }
+        }
+    }
+
     async fn handle_network_change(&self, network: Network) -> Result<()> {
         if network != self.network() {
             self.application_events
@@ -446,6 +717,43 @@ impl KaspaService {
                 self.update_logs(line).await;
             }
 
+            #[cfg(not(target_arch = "wasm32"))]
+            KaspadServiceEvents::DaemonCrash { crash } => {
+                let user_notification = if let Some(delay) = crash.restart_delay {
+                    UserNotification::warning(format!(
+                        "{} ({} #{}), {} {} sec",
+                        i18n("Kaspa node has exited unexpectedly"),
+                        i18n("crash"),
+                        crash.count,
+                        i18n("restarting in"),
+                        delay.as_secs()
+                    ))
+                } else {
+                    UserNotification::error(format!(
+                        "{} {} {}",
+                        i18n("Kaspa node has crashed"),
+                        crash.count,
+                        i18n(
+                            "times in a row and will not be restarted. Please check the node logs."
+                        )
+                    ))
+                };
+
+                log_warn!("kaspad exited unexpectedly: {}", crash.status);
+                self.update_logs(format!(
+                    "kaspad exited unexpectedly ({}): {}",
+                    crash.count, crash.status
+                ))
+                .await;
+                if crash.is_final() {
+                    // the daemon will not be restarted, so the
+                    // local node is no longer considered running
+                    self.kaspad.lock().unwrap().take();
+                }
+                *self.daemon_crash.lock().unwrap() = Some(crash);
+                self.notify(Events::Notify { user_notification })?;
+            }
+
             #[cfg(not(target_arch = "wasm32"))]
             KaspadServiceEvents::StartInternalInProc { config, network } => {
                 self.stop_all_services().await?;
@@ -484,6 +792,7 @@ impl KaspaService {
                 };
 
                 let rpc = Self::create_rpc_client(&rpc_config, network)
+                    .await
                     .expect("Kaspad Service - unable to create wRPC client");
                 self.start_all_services(Some(rpc), network).await?;
                 self.connect_rpc_client().await?;
@@ -511,12 +820,18 @@ impl KaspaService {
                 };
 
                 let rpc = Self::create_rpc_client(&rpc_config, network)
+                    .await
                     .expect("Kaspad Service - unable to create wRPC client");
                 self.start_all_services(Some(rpc), network).await?;
                 self.connect_rpc_client().await?;
 
                 self.update_storage();
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            KaspadServiceEvents::StopLocalNode { sender } => {
+                self.stop_all_services().await?;
+                sender.try_send(()).ok();
+            }
             KaspadServiceEvents::StartRemoteConnection {
                 rpc_config,
                 network,
@@ -534,10 +849,17 @@ impl KaspaService {
 
                     self.handle_network_change(network).await?;
 
-                    let rpc = Self::create_rpc_client(&rpc_config, network)
-                        .expect("Kaspad Service - unable to create wRPC client");
-                    self.start_all_services(Some(rpc), network).await?;
-                    self.connect_rpc_client().await?;
+                    match Self::create_rpc_client(&rpc_config, network).await {
+                        Ok(rpc) => {
+                            #[cfg(not(target_arch = "wasm32"))]
+                            self.remote_connection_failures.store(0, Ordering::SeqCst);
+                            self.start_all_services(Some(rpc), network).await?;
+                            self.connect_rpc_client().await?;
+                        }
+                        Err(err) => {
+                            self.retry_remote_connection(rpc_config, network, err)?;
+                        }
+                    }
                 }
             }
 
@@ -833,14 +1155,14 @@ impl KaspadServiceEvents {
                     }
                     KaspadNodeKind::IntegratedInProc => {
                         // let config = ;
-                        Ok(KaspadServiceEvents::StartInternalInProc { config : Config::from(node_settings.clone()), network : node_settings.network })
+                        Ok(KaspadServiceEvents::StartInternalInProc { config : Config::try_from(node_settings.clone())?, network : node_settings.network })
                     }
                     KaspadNodeKind::IntegratedAsDaemon => {
-                        Ok(KaspadServiceEvents::StartInternalAsDaemon { config : Config::from(node_settings.clone()), network : node_settings.network })
+                        Ok(KaspadServiceEvents::StartInternalAsDaemon { config : Config::try_from(node_settings.clone())?, network : node_settings.network })
                     }
                     KaspadNodeKind::ExternalAsDaemon => {
                         let path = node_settings.kaspad_daemon_binary.clone();
-                        Ok(KaspadServiceEvents::StartExternalAsDaemon { path : PathBuf::from(path), config : Config::from(node_settings.clone()), network : node_settings.network })
+                        Ok(KaspadServiceEvents::StartExternalAsDaemon { path : PathBuf::from(path), config : Config::try_from(node_settings.clone())?, network : node_settings.network })
                     }
                     KaspadNodeKind::Remote => {
                         Ok(KaspadServiceEvents::StartRemoteConnection { rpc_config : RpcConfig::from_node_settings(node_settings,options), network : node_settings.network })
@@ -15,13 +15,23 @@ use std::collections::hash_map::Entry;
 //     }
 //   }
 
-// https://api.coingecko.com/api/v3/coins/list
+// https://api.coingecko.com/api/v3/simple/supported_vs_currencies
 // [
-//   {
-//     "id": "01coin",
-//     "symbol": "zoc",
-//     "name": "01coin"
-//   },
+//   "btc",
+//   "eth",
+//   "usd",
+//   ...
+// ]
+
+// https://api.coingecko.com/api/v3/coins/kaspa/ohlc?vs_currency=usd&days=7
+// [
+//   [
+//     1709395200000,   // time (ms)
+//     0.1296,          // open
+//     0.1352,          // high
+//     0.1291,          // low
+//     0.1342           // close
+//   ],
 
 #[derive(Default, Debug, Serialize, Deserialize)]
 struct CoinGeckoSimplePrice {
@@ -52,8 +62,16 @@ impl From<CoinGeckoSimplePrice> for MarketDataMap {
 }
 
 pub async fn fetch_available_currencies() -> Result<CurrencyDescriptorList> {
-    let url = "https://api.coingecko.com/api/v3/coins/list";
-    let available_currencies = http::get_json::<CurrencyDescriptorList>(url).await?;
+    let url = "https://api.coingecko.com/api/v3/simple/supported_vs_currencies";
+    let symbols = http::get_json::<Vec<String>>(url).await?;
+    let available_currencies = symbols
+        .into_iter()
+        .map(|symbol| CurrencyDescriptor {
+            id: symbol.clone(),
+            name: symbol.to_uppercase(),
+            symbol,
+        })
+        .collect();
     Ok(available_currencies)
 }
 
@@ -64,6 +82,17 @@ pub async fn fetch_market_price_list(currencies: &[&str]) -> Result<MarketDataMa
     Ok(market_data.into())
 }
 
+pub async fn fetch_ohlc(currency: &str, range: OhlcRange) -> Result<Ohlc> {
+    let vs_currency = currency.to_lowercase();
+    let days = range.days();
+    let url = format!(
+        "https://api.coingecko.com/api/v3/coins/kaspa/ohlc?vs_currency={vs_currency}&days={days}"
+    );
+    let data = http::get_json::<Vec<[f64; 5]>>(url).await?;
+    let points = data.into_iter().map(OhlcPoint::from).collect::<Vec<_>>();
+    Ok(Ohlc::new(currency, range, points))
+}
+
 fn group_by_currency_prefix(data: &AHashMap<String, f64>) -> MarketDataMap {
     let mut grouped_data: MarketDataMap = AHashMap::new();
 
@@ -1,66 +1,146 @@
 use super::*;
-use std::collections::hash_map::Entry;
-use workflow_http::get_json;
 
-#[derive(Default, Serialize, Deserialize)]
-struct CoinGeckoSimplePrice {
-    kaspa: Option<AHashMap<String, f64>>,
+// CoinMarketCap Pro API, requires an API key (https://coinmarketcap.com/api/).
+// The API key is sent in the `X-CMC_PRO_API_KEY` request header.
+//
+// https://pro-api.coinmarketcap.com/v2/cryptocurrency/quotes/latest?id=20396&convert=USD,BTC
+// {
+//     "data": {
+//       "20396": {
+//         "symbol": "KAS",
+//         "quote": {
+//           "USD": {
+//             "price": 0.137395,
+//             "volume_24h": 138844602.78193888,
+//             "percent_change_24h": 16.77712212157855,
+//             "market_cap": 2954668910.049152,
+//             ...
+//           },
+//           ...
+//         }
+//       }
+//     }
+// }
+//
+// https://pro-api.coinmarketcap.com/v1/fiat/map
+// {
+//     "data": [
+//       { "id": 2781, "name": "United States Dollar", "sign": "$", "symbol": "USD" },
+//       ...
+//     ]
+// }
+
+const API_URL: &str = "https://pro-api.coinmarketcap.com";
+/// CoinMarketCap id of Kaspa
+const KASPA_ID: &str = "20396";
+/// Cryptocurrencies offered in addition to the fiat currency map
+const CRYPTO_CURRENCIES: &[(&str, &str)] = &[("btc", "Bitcoin"), ("eth", "Ethereum")];
+
+#[derive(Deserialize)]
+struct CoinMarketCapResponse<T> {
+    data: T,
 }
 
-impl CoinGeckoSimplePrice {
-    pub async fn get(currencies: &[&str]) -> Result<Self> {
-        let ids = "kaspa";
-        let currencies = currencies
-            .iter()
-            .map(|currency| currency.to_lowercase())
-            .collect::<Vec<_>>()
-            .join("%2C");
-        let url = format!("https://api.coingecko.com/api/v3/simple/price?ids={ids}&vs_currencies={currencies}&include_market_cap=true&include_24hr_vol=true&include_24hr_change=true");
-        Ok(get_json::<Self>(url).await?)
-    }
+#[derive(Deserialize)]
+struct CoinMarketCapFiat {
+    name: String,
+    symbol: String,
 }
 
-impl From<CoinGeckoSimplePrice> for MarketDataMap {
-    fn from(data: CoinGeckoSimplePrice) -> Self {
-        let mut prices = AHashMap::new();
-        if let Some(kaspa) = data.kaspa {
-            prices = group_by_currency_prefix(&kaspa);
-        }
-        prices
-    }
+#[derive(Deserialize)]
+struct CoinMarketCapQuotes {
+    quote: AHashMap<String, CoinMarketCapQuote>,
 }
 
-pub async fn fetch_available_currencies() -> Result<CurrencyDescriptorList> {
-    let url = "https://api.coingecko.com/api/v3/coins/list";
-    let available_currencies = http::get_json::<CurrencyDescriptorList>(url).await?;
-    Ok(available_currencies)
+#[derive(Deserialize)]
+struct CoinMarketCapQuote {
+    price: Option<f64>,
+    volume_24h: Option<f64>,
+    percent_change_24h: Option<f64>,
+    market_cap: Option<f64>,
+}
+
+fn api_key(settings: &MarketSettings) -> Result<&str> {
+    let api_key = settings.coinmarketcap_api_key.trim();
+    if api_key.is_empty() {
+        Err(Error::custom("CoinMarketCap API key is not configured"))
+    } else {
+        Ok(api_key)
+    }
 }
 
-pub async fn fetch_market_price_list(currencies: &[&str]) -> Result<MarketDataMap> {
-    let market_data = CoinGeckoSimplePrice::get(currencies).await?;
-    Ok(market_data.into())
+/// Fetch an API endpoint passing the API key in the request header, so
+/// that the key is not part of the URL reported in request errors.
+async fn get_json<T: serde::de::DeserializeOwned>(
+    settings: &MarketSettings,
+    url: String,
+) -> Result<T> {
+    let api_key = api_key(settings)?;
+    let response = reqwest::Client::new()
+        .get(url)
+        .header("X-CMC_PRO_API_KEY", api_key)
+        .header("Accept", "application/json")
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json::<T>().await?)
 }
 
-fn group_by_currency_prefix(data: &AHashMap<String, f64>) -> MarketDataMap {
-    let mut grouped_data: MarketDataMap = AHashMap::new();
+pub async fn fetch_available_currencies(
+    settings: &MarketSettings,
+) -> Result<CurrencyDescriptorList> {
+    let url = format!("{API_URL}/v1/fiat/map");
+    let fiat = get_json::<CoinMarketCapResponse<Vec<CoinMarketCapFiat>>>(settings, url).await?;
+
+    let available_currencies = fiat
+        .data
+        .into_iter()
+        .map(|fiat| (fiat.symbol.to_lowercase(), fiat.name))
+        .chain(
+            CRYPTO_CURRENCIES
+                .iter()
+                .map(|(symbol, name)| (symbol.to_string(), name.to_string())),
+        )
+        .map(|(symbol, name)| CurrencyDescriptor {
+            id: symbol.clone(),
+            symbol,
+            name,
+        })
+        .collect();
+    Ok(available_currencies)
+}
 
-    for (symbol, info) in data.iter() {
-        let parts: Vec<&str> = symbol.split('_').collect();
-        let currency_prefix = parts[0].to_lowercase();
-        let suffix = parts.last().map(|suffix| suffix.to_lowercase());
-        let data = match grouped_data.entry(currency_prefix.clone()) {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(MarketData::new(symbol.as_str())),
-        };
+pub async fn fetch_market_price_list(
+    settings: &MarketSettings,
+    currencies: &[&str],
+) -> Result<MarketDataMap> {
+    let convert = currencies
+        .iter()
+        .map(|currency| currency.to_uppercase())
+        .collect::<Vec<_>>()
+        .join(",");
+    let url = format!("{API_URL}/v2/cryptocurrency/quotes/latest?id={KASPA_ID}&convert={convert}");
+    let mut response =
+        get_json::<CoinMarketCapResponse<AHashMap<String, CoinMarketCapQuotes>>>(settings, url)
+            .await?;
+    let quotes = response
+        .data
+        .remove(KASPA_ID)
+        .ok_or_else(|| Error::custom("CoinMarketCap response does not contain Kaspa quotes"))?;
 
-        match suffix.as_deref() {
-            None => data.price = *info,
-            Some("market_cap") => data.market_cap = *info,
-            Some("24h_vol") => data.volume = *info,
-            Some("24h_change") => data.change = *info,
-            _ => (),
-        }
-    }
+    let market_data_map = quotes
+        .quote
+        .into_iter()
+        .filter_map(|(currency, quote)| {
+            let currency = currency.to_lowercase();
+            let mut market_data = MarketData::new(currency.as_str());
+            market_data.price = quote.price?;
+            market_data.volume = quote.volume_24h.unwrap_or_default();
+            market_data.change = quote.percent_change_24h.unwrap_or_default();
+            market_data.market_cap = quote.market_cap.unwrap_or_default();
+            Some((currency, market_data))
+        })
+        .collect();
 
-    grouped_data
+    Ok(market_data_map)
 }
@@ -0,0 +1,101 @@
+use super::*;
+
+// Generic JSON-over-HTTP market data provider.
+//
+// The default field mapping follows the CoinGecko simple price format,
+// which makes it easy to run a local stand-in for testing:
+//
+// http://127.0.0.1:8080/price?currencies=usd,btc
+// {
+//     "kaspa": {
+//       "usd": 0.137395,
+//       "usd_market_cap": 2954668910.049152,
+//       "usd_24h_vol": 138844602.78193888,
+//       "usd_24h_change": 16.77712212157855,
+//       ...
+//     }
+// }
+
+pub async fn fetch_available_currencies() -> Result<CurrencyDescriptorList> {
+    // there is no standard way to query a custom provider,
+    // so we offer the currencies known to the application
+    let available_currencies = FIAT_CURRENCIES
+        .iter()
+        .chain(["btc"].iter())
+        .map(|symbol| CurrencyDescriptor {
+            id: symbol.to_string(),
+            symbol: symbol.to_string(),
+            name: symbol.to_uppercase(),
+        })
+        .collect();
+    Ok(available_currencies)
+}
+
+pub async fn fetch_market_price_list(
+    settings: &JsonMarketDataProviderSettings,
+    currencies: &[&str],
+) -> Result<MarketDataMap> {
+    if settings.url.trim().is_empty() {
+        return Err(Error::custom("Market data provider URL is not configured"));
+    }
+
+    let currencies = currencies
+        .iter()
+        .map(|currency| currency.to_lowercase())
+        .collect::<Vec<_>>();
+    let url = settings
+        .url
+        .replace("{currencies}", currencies.join(",").as_str());
+    let data = http::get_json::<serde_json::Value>(url).await?;
+
+    let mut market_data_map = MarketDataMap::default();
+    for currency in currencies {
+        let Some(price) = resolve(&data, settings.price_path.as_str(), currency.as_str()) else {
+            continue;
+        };
+
+        let mut market_data = MarketData::new(currency.as_str());
+        market_data.price = price;
+        if let Some(market_cap) = resolve(&data, settings.market_cap_path.as_str(), &currency) {
+            market_data.market_cap = market_cap;
+        }
+        if let Some(volume) = resolve(&data, settings.volume_path.as_str(), &currency) {
+            market_data.volume = volume;
+        }
+        if let Some(change) = resolve(&data, settings.change_path.as_str(), &currency) {
+            market_data.change = change;
+        }
+        market_data_map.insert(currency, market_data);
+    }
+
+    if market_data_map.is_empty() {
+        Err(Error::custom(
+            "Market data provider response does not match the field mapping",
+        ))
+    } else {
+        Ok(market_data_map)
+    }
+}
+
+/// Resolve a dot-separated field path against the JSON response,
+/// substituting the `{currency}` placeholder. Numeric values as well
+/// as numeric strings are accepted.
+fn resolve(data: &serde_json::Value, path: &str, currency: &str) -> Option<f64> {
+    let path = path.trim();
+    if path.is_empty() {
+        return None;
+    }
+
+    let path = path.replace("{currency}", currency);
+    let value = path.split('.').try_fold(data, |value, key| match value {
+        serde_json::Value::Array(list) => key.parse::<usize>().ok().and_then(|i| list.get(i)),
+        serde_json::Value::Object(map) => map.get(key),
+        _ => None,
+    })?;
+
+    match value {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(string) => string.parse::<f64>().ok(),
+        _ => None,
+    }
+}
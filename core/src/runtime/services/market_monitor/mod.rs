@@ -1,41 +1,81 @@
+use crate::alerts::dispatch_alerts;
 use crate::imports::*;
 use crate::market::*;
+use kaspa_wallet_core::storage::local::storage::Storage;
 
 mod coingecko;
 mod coinmarketcap;
+mod json;
 
 pub const POLLING_INTERVAL_SECONDS: u64 = 60;
 
-#[derive(Default, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
-pub enum MarketDataProvider {
-    #[default]
-    CoinGecko,
-    CoinMarketCap,
-}
-
 impl MarketDataProvider {
-    #[allow(dead_code)]
-    async fn fetch_available_currencies(&self) -> Result<CurrencyDescriptorList> {
+    async fn fetch_available_currencies(
+        &self,
+        settings: &MarketSettings,
+    ) -> Result<CurrencyDescriptorList> {
         match self {
             Self::CoinGecko => coingecko::fetch_available_currencies().await,
-            Self::CoinMarketCap => coinmarketcap::fetch_available_currencies().await,
+            Self::CoinMarketCap => coinmarketcap::fetch_available_currencies(settings).await,
+            Self::Json => json::fetch_available_currencies().await,
         }
     }
 
-    async fn fetch_market_price_list(&self, currencies: &[&str]) -> Result<MarketDataMap> {
+    async fn fetch_market_price_list(
+        &self,
+        settings: &MarketSettings,
+        currencies: &[&str],
+    ) -> Result<MarketDataMap> {
         match self {
             Self::CoinGecko => coingecko::fetch_market_price_list(currencies).await,
-            Self::CoinMarketCap => coinmarketcap::fetch_market_price_list(currencies).await,
+            Self::CoinMarketCap => {
+                coinmarketcap::fetch_market_price_list(settings, currencies).await
+            }
+            Self::Json => json::fetch_market_price_list(&settings.json_provider, currencies).await,
         }
     }
+
+    async fn fetch_ohlc(&self, currency: &str, range: OhlcRange) -> Result<Ohlc> {
+        match self {
+            Self::CoinGecko => coingecko::fetch_ohlc(currency, range).await,
+            _ => Err(Error::custom(format!(
+                "OHLC market history is not supported by {self}"
+            ))),
+        }
+    }
+}
+
+/// Locally cached OHLC market history keyed by [`ohlc_key`].
+#[derive(Default, Clone, Serialize, Deserialize)]
+struct OhlcCache {
+    entries: AHashMap<String, Ohlc>,
 }
 
-#[derive(Default, Serialize, Deserialize)]
-struct MarketMonitorSettings {
-    enabled: bool,
-    provider: MarketDataProvider,
-    currencies: Vec<String>,
+fn ohlc_cache_storage() -> Result<Storage> {
+    Ok(Storage::try_new("kaspa-ng.ohlc")?)
+}
+
+impl OhlcCache {
+    async fn load() -> Self {
+        match ohlc_cache_storage() {
+            Ok(storage) if storage.exists().await.unwrap_or(false) => {
+                workflow_store::fs::read_json::<Self>(storage.filename())
+                    .await
+                    .unwrap_or_else(|err| {
+                        log_warn!("OhlcCache::load() error: {}", err);
+                        Self::default()
+                    })
+            }
+            _ => Self::default(),
+        }
+    }
+
+    async fn store(&self) -> Result<()> {
+        let storage = ohlc_cache_storage()?;
+        storage.ensure_dir().await?;
+        workflow_store::fs::write_json(storage.filename(), self).await?;
+        Ok(())
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -50,6 +90,9 @@ pub type CurrencyDescriptorList = Vec<CurrencyDescriptor>;
 pub enum MarketMonitorEvents {
     Enable,
     Disable,
+    Update,
+    AvailableCurrencies,
+    Ohlc,
     Exit,
 }
 
@@ -59,36 +102,124 @@ pub struct MarketMonitorService {
     pub task_ctl: Channel<()>,
     pub is_enabled: AtomicBool,
     pub currencies: Mutex<Option<Vec<String>>>,
-    pub provider: Mutex<MarketDataProvider>,
+    pub settings: Mutex<MarketSettings>,
+    pub alerts: Mutex<AlertSettings>,
     pub available_currencies: Mutex<Option<Vec<CurrencyDescriptor>>>,
     pub market_price_list: Mutex<Option<Arc<MarketDataMap>>>,
+    pub ohlc_request: Mutex<Option<(String, OhlcRange)>>,
+    ohlc_error: Mutex<Option<(String, OhlcRange, String)>>,
+    ohlc_cache: Mutex<Option<OhlcCache>>,
 }
 
 impl MarketMonitorService {
     pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
-        let currencies = ["usd", "btc"]
-            .into_iter()
-            .map(String::from)
-            .collect::<Vec<_>>();
+        let currencies = Self::currencies_from_settings(settings);
 
         Self {
             application_events,
             service_events: Channel::unbounded(),
             task_ctl: Channel::oneshot(),
             is_enabled: AtomicBool::new(settings.market_monitor),
-            provider: Mutex::new(MarketDataProvider::default()),
+            settings: Mutex::new(settings.market.clone()),
+            alerts: Mutex::new(settings.alerts.clone()),
             currencies: Mutex::new(Some(currencies)),
             available_currencies: Mutex::new(None),
             market_price_list: Mutex::new(None),
+            ohlc_request: Mutex::new(None),
+            ohlc_error: Mutex::new(None),
+            ohlc_cache: Mutex::new(None),
         }
     }
 
+    fn currencies_from_settings(settings: &Settings) -> Vec<String> {
+        let mut currencies = settings
+            .market
+            .currencies
+            .iter()
+            .map(|currency| currency.to_lowercase())
+            .collect::<Vec<_>>();
+
+        let fiat_currency = settings.market.fiat_currency.to_lowercase();
+        for currency in std::iter::once(fiat_currency).chain(settings.alerts.currencies()) {
+            if !currencies.contains(&currency) {
+                currencies.push(currency);
+            }
+        }
+
+        currencies
+    }
+
+    /// Update the market data provider, the list of monitored
+    /// currencies and price alerts from the application settings
+    /// and post an immediate market price update request.
+    pub fn update_settings(&self, settings: &Settings) {
+        let provider_changed = {
+            let mut market_settings = self.settings.lock().unwrap();
+            let provider_changed = market_settings.provider != settings.market.provider
+                || market_settings.coinmarketcap_api_key != settings.market.coinmarketcap_api_key;
+            *market_settings = settings.market.clone();
+            provider_changed
+        };
+        if provider_changed {
+            self.available_currencies.lock().unwrap().take();
+        }
+        *self.alerts.lock().unwrap() = settings.alerts.clone();
+
+        self.currencies
+            .lock()
+            .unwrap()
+            .replace(Self::currencies_from_settings(settings));
+        self.service_events
+            .try_send(MarketMonitorEvents::Update)
+            .unwrap();
+    }
+
+    /// Request OHLC market history for the given currency and range.
+    /// The data is served from the local cache if it is still fresh,
+    /// otherwise it is fetched from the market data provider. The
+    /// requested series is kept up to date while the monitor is enabled.
+    pub fn request_ohlc(&self, currency: &str, range: OhlcRange) {
+        self.ohlc_request
+            .lock()
+            .unwrap()
+            .replace((currency.to_lowercase(), range));
+        self.service_events
+            .try_send(MarketMonitorEvents::Ohlc)
+            .unwrap();
+    }
+
+    /// Error of the last OHLC request for the given currency and
+    /// range if the market history could not be obtained.
+    pub fn ohlc_error(&self, currency: &str, range: OhlcRange) -> Option<String> {
+        self.ohlc_error
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|(error_currency, error_range, _)| {
+                error_currency.as_str() == currency && *error_range == range
+            })
+            .map(|(_, _, error)| error.clone())
+    }
+
     pub fn currencies(&self) -> Option<Vec<String>> {
         self.currencies.lock().unwrap().clone()
     }
 
     pub fn provider(&self) -> MarketDataProvider {
-        self.provider.lock().unwrap().clone()
+        self.settings.lock().unwrap().provider.clone()
+    }
+
+    /// Currencies supported by the current market data provider.
+    /// Returns `None` until [`request_available_currencies`](Self::request_available_currencies)
+    /// has completed.
+    pub fn available_currencies(&self) -> Option<Vec<CurrencyDescriptor>> {
+        self.available_currencies.lock().unwrap().clone()
+    }
+
+    pub fn request_available_currencies(&self) {
+        self.service_events
+            .try_send(MarketMonitorEvents::AvailableCurrencies)
+            .unwrap();
     }
 
     pub fn enable(&self, enable: bool) {
@@ -103,32 +234,118 @@ impl MarketMonitorService {
         }
     }
 
-    #[allow(dead_code)]
     async fn update_available_currencies(&self) -> Result<()> {
-        let available_currencies = self.provider().fetch_available_currencies().await?;
-        self.available_currencies
-            .lock()
-            .unwrap()
-            .replace(available_currencies);
+        let settings = self.settings.lock().unwrap().clone();
+        match settings
+            .provider
+            .fetch_available_currencies(&settings)
+            .await
+        {
+            Ok(mut available_currencies) => {
+                available_currencies.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+                self.available_currencies
+                    .lock()
+                    .unwrap()
+                    .replace(available_currencies);
+            }
+            Err(err) => {
+                log_warn!("Unable to fetch available market currencies: {}", err);
+            }
+        }
         Ok(())
     }
 
     async fn update_market_price_list(&self) -> Result<()> {
         if let Some(currencies) = self.currencies() {
             let currencies = currencies.iter().map(String::as_str).collect::<Vec<_>>();
-            if let Ok(market_price_list) =
-                self.provider().fetch_market_price_list(&currencies).await
+            let settings = self.settings.lock().unwrap().clone();
+            if let Ok(market_price_list) = settings
+                .provider
+                .fetch_market_price_list(&settings, &currencies)
+                .await
             {
+                let market_price_list = Arc::new(market_price_list);
+                let previous = self
+                    .market_price_list
+                    .lock()
+                    .unwrap()
+                    .replace(market_price_list.clone());
+                if let Some(previous) = previous {
+                    let alert_settings = self.alerts.lock().unwrap().clone();
+                    let alerts = alert_settings.evaluate_price(&previous, &market_price_list);
+                    dispatch_alerts(&self.application_events, alerts);
+                }
+
                 self.application_events
                     .sender
-                    .try_send(Events::Market(MarketUpdate::Price(Arc::new(
-                        market_price_list,
-                    ))))
+                    .try_send(Events::Market(MarketUpdate::Price(market_price_list)))
                     .unwrap();
             }
         }
         Ok(())
     }
+
+    async fn update_ohlc(&self) -> Result<()> {
+        let Some((currency, range)) = self.ohlc_request.lock().unwrap().clone() else {
+            return Ok(());
+        };
+
+        if self.ohlc_cache.lock().unwrap().is_none() {
+            let cache = OhlcCache::load().await;
+            self.ohlc_cache.lock().unwrap().replace(cache);
+        }
+
+        let key = ohlc_key(currency.as_str(), range);
+        let cached = self
+            .ohlc_cache
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|cache| cache.entries.get(&key).cloned());
+
+        let ohlc = match cached {
+            Some(ohlc) if !ohlc.is_stale() => ohlc,
+            cached => match self.provider().fetch_ohlc(currency.as_str(), range).await {
+                Ok(ohlc) => {
+                    let cache = {
+                        let mut cache = self.ohlc_cache.lock().unwrap();
+                        let cache = cache.get_or_insert_with(OhlcCache::default);
+                        cache.entries.insert(key, ohlc.clone());
+                        cache.clone()
+                    };
+                    if let Err(err) = cache.store().await {
+                        log_warn!("Unable to store OHLC cache: {}", err);
+                    }
+                    ohlc
+                }
+                Err(err) => {
+                    log_warn!("Unable to fetch OHLC market data: {}", err);
+                    match cached {
+                        Some(ohlc) => ohlc,
+                        None => {
+                            self.ohlc_error.lock().unwrap().replace((
+                                currency,
+                                range,
+                                err.to_string(),
+                            ));
+                            runtime().request_repaint();
+                            return Ok(());
+                        }
+                    }
+                }
+            },
+        };
+        self.ohlc_error.lock().unwrap().take();
+
+        let mut ohlc_map = OhlcMap::default();
+        ohlc_map.insert(currency, ohlc);
+        self.application_events
+            .sender
+            .try_send(Events::Market(MarketUpdate::Ohlc(Arc::new(ohlc_map))))
+            .unwrap();
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -147,6 +364,9 @@ impl Service for MarketMonitorService {
             select! {
                 _ = interval.next().fuse() => {
                     this.update_market_price_list().await?;
+                    if this.is_enabled.load(Ordering::SeqCst) {
+                        this.update_ohlc().await?;
+                    }
                 },
 
                 msg = this.as_ref().service_events.receiver.recv().fuse() => {
@@ -161,6 +381,19 @@ impl Service for MarketMonitorService {
                             MarketMonitorEvents::Disable => {
                                 this.is_enabled.store(false, Ordering::SeqCst);
                             }
+                            MarketMonitorEvents::Update => {
+                                if this.is_enabled.load(Ordering::SeqCst) {
+                                    this.update_market_price_list().await?;
+                                }
+                            }
+                            MarketMonitorEvents::AvailableCurrencies => {
+                                this.update_available_currencies().await?;
+                            }
+                            MarketMonitorEvents::Ohlc => {
+                                if this.is_enabled.load(Ordering::SeqCst) {
+                                    this.update_ohlc().await?;
+                                }
+                            }
                             MarketMonitorEvents::Exit => {
                                 break;
                             }
@@ -0,0 +1,400 @@
+use crate::imports::*;
+use kaspa_rpc_core::{RpcMempoolEntry, RpcTransactionId};
+
+pub const MEMPOOL_POLLING_INTERVAL_SECONDS: u64 = 2;
+
+/// Mempool entry with values derived from the node-provided transaction.
+pub struct MempoolEntry {
+    pub id: RpcTransactionId,
+    pub entry: RpcMempoolEntry,
+    pub mass: u64,
+    /// Fee rate in SOMPI per gram of mass
+    pub fee_rate: f64,
+    /// Unixtime in milliseconds when the entry was first observed
+    /// by the monitor (the node does not expose the entry age).
+    pub first_seen: f64,
+    /// The transaction belongs to or pays to one of the wallet accounts
+    pub is_wallet: bool,
+}
+
+impl MempoolEntry {
+    fn try_new(
+        entry: RpcMempoolEntry,
+        first_seen: &mut AHashMap<RpcTransactionId, f64>,
+        now: f64,
+    ) -> Result<Self> {
+        let (id, mass) = match entry.transaction.verbose_data.as_ref() {
+            Some(verbose_data) => (verbose_data.transaction_id, verbose_data.mass),
+            None => {
+                let transaction =
+                    kaspa_consensus_core::tx::Transaction::try_from(&entry.transaction)?;
+                (transaction.id(), entry.transaction.mass)
+            }
+        };
+
+        let fee_rate = if mass > 0 {
+            entry.fee as f64 / mass as f64
+        } else {
+            0.0
+        };
+        let first_seen = *first_seen.entry(id).or_insert(now);
+
+        Ok(Self {
+            id,
+            entry,
+            mass,
+            fee_rate,
+            first_seen,
+            is_wallet: false,
+        })
+    }
+
+    /// Addresses of the transaction outputs (inputs carry no address information)
+    pub fn addresses(&self) -> impl Iterator<Item = &Address> {
+        self.entry
+            .transaction
+            .outputs
+            .iter()
+            .filter_map(|output| output.verbose_data.as_ref())
+            .map(|verbose_data| &verbose_data.script_public_key_address)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MempoolSort {
+    FeeRate,
+    Fee,
+    Mass,
+    Age,
+}
+
+/// Mempool table filter and sort order selected in the UI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MempoolFilter {
+    pub search: String,
+    pub sort: MempoolSort,
+    pub descending: bool,
+    pub show_orphans: bool,
+    pub wallet_only: bool,
+}
+
+impl Default for MempoolFilter {
+    fn default() -> Self {
+        Self {
+            search: String::new(),
+            sort: MempoolSort::FeeRate,
+            descending: true,
+            show_orphans: true,
+            wallet_only: false,
+        }
+    }
+}
+
+impl MempoolFilter {
+    fn matches(&self, entry: &MempoolEntry, search: &str) -> bool {
+        (self.show_orphans || !entry.entry.is_orphan)
+            && (!self.wallet_only || entry.is_wallet)
+            && (search.is_empty()
+                || entry.id.to_string().contains(search)
+                || entry
+                    .addresses()
+                    .any(|address| address.to_string().contains(search)))
+    }
+
+    fn compare(&self, a: &MempoolEntry, b: &MempoolEntry) -> std::cmp::Ordering {
+        let ordering = match self.sort {
+            MempoolSort::FeeRate => a.fee_rate.total_cmp(&b.fee_rate),
+            MempoolSort::Fee => a.entry.fee.cmp(&b.entry.fee),
+            MempoolSort::Mass => a.mass.cmp(&b.mass),
+            // older entries have a lower first-seen time
+            MempoolSort::Age => b.first_seen.total_cmp(&a.first_seen),
+        };
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Mempool entries filtered and sorted for display, computed once per
+/// poll or when the filter changes.
+pub struct MempoolView {
+    pub entries: Arc<Vec<MempoolEntry>>,
+    /// Indexes of the matching entries in display order
+    pub visible: Vec<usize>,
+    pub orphans: usize,
+    pub total_fees: u64,
+    /// Number of matching wallet transactions
+    pub wallet: usize,
+}
+
+impl MempoolView {
+    fn new(entries: Arc<Vec<MempoolEntry>>, filter: &MempoolFilter) -> Self {
+        let search = filter.search.trim().to_lowercase();
+        let mut visible = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| filter.matches(entry, &search))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        visible.sort_by(|a, b| filter.compare(&entries[*a], &entries[*b]));
+
+        let orphans = entries.iter().filter(|entry| entry.entry.is_orphan).count();
+        let total_fees = entries.iter().map(|entry| entry.entry.fee).sum::<u64>();
+        let wallet = visible
+            .iter()
+            .filter(|index| entries[**index].is_wallet)
+            .count();
+
+        Self {
+            entries,
+            visible,
+            orphans,
+            total_fees,
+            wallet,
+        }
+    }
+
+    pub fn visible(&self) -> impl Iterator<Item = &MempoolEntry> {
+        self.visible.iter().map(|index| &self.entries[*index])
+    }
+
+    pub fn get(&self, id: &RpcTransactionId) -> Option<&MempoolEntry> {
+        self.entries.iter().find(|entry| entry.id == *id)
+    }
+}
+
+pub enum MempoolMonitorEvents {
+    Enable,
+    Disable,
+    Exit,
+}
+
+pub struct MempoolMonitorService {
+    pub application_events: ApplicationEventsChannel,
+    pub service_events: Channel<MempoolMonitorEvents>,
+    pub task_ctl: Channel<()>,
+    pub rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
+    pub entries: Mutex<Option<Arc<Vec<MempoolEntry>>>>,
+    pub view: Mutex<Option<Arc<MempoolView>>>,
+    pub filter: Mutex<MempoolFilter>,
+    pub is_enabled: Arc<AtomicBool>,
+    first_seen: Mutex<AHashMap<RpcTransactionId, f64>>,
+    /// Pending wallet transactions reported by the wallet
+    wallet_transactions: Mutex<AHashSet<RpcTransactionId>>,
+}
+
+impl MempoolMonitorService {
+    pub fn new(application_events: ApplicationEventsChannel, _settings: &Settings) -> Self {
+        Self {
+            application_events,
+            service_events: Channel::unbounded(),
+            task_ctl: Channel::oneshot(),
+            rpc_api: Mutex::new(None),
+            entries: Mutex::new(None),
+            view: Mutex::new(None),
+            filter: Mutex::new(MempoolFilter::default()),
+            is_enabled: Arc::new(AtomicBool::new(false)),
+            first_seen: Mutex::new(AHashMap::default()),
+            wallet_transactions: Mutex::new(AHashSet::default()),
+        }
+    }
+
+    pub fn rpc_api(&self) -> Option<Arc<dyn RpcApi>> {
+        self.rpc_api.lock().unwrap().clone()
+    }
+
+    /// Mempool entries, available while the mempool monitor is enabled.
+    pub fn entries(&self) -> Option<Arc<Vec<MempoolEntry>>> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Filtered and sorted mempool entries, available while the
+    /// mempool monitor is enabled.
+    pub fn view(&self) -> Option<Arc<MempoolView>> {
+        self.view.lock().unwrap().clone()
+    }
+
+    /// Update the filter and rebuild the view from the current entries.
+    pub fn set_filter(&self, filter: MempoolFilter) {
+        let mut current = self.filter.lock().unwrap();
+        if *current != filter {
+            *current = filter;
+            self.update_view(&current);
+        }
+    }
+
+    /// Register a pending transaction of one of the wallet accounts.
+    pub fn register_wallet_transaction(&self, id: RpcTransactionId) {
+        self.wallet_transactions.lock().unwrap().insert(id);
+    }
+
+    pub fn clear_wallet_transactions(&self) {
+        self.wallet_transactions.lock().unwrap().clear();
+    }
+
+    fn update_view(&self, filter: &MempoolFilter) {
+        let view = self
+            .entries()
+            .map(|entries| Arc::new(MempoolView::new(entries, filter)));
+        *self.view.lock().unwrap() = view;
+    }
+
+    /// All addresses monitored by the active wallet accounts.
+    fn wallet_addresses(&self) -> AHashSet<Address> {
+        let mut addresses = AHashSet::default();
+        if let Some(wallet) =
+            crate::runtime::try_runtime().and_then(|runtime| runtime.kaspa_service().core_wallet())
+        {
+            for account in wallet.active_accounts().inner().values() {
+                addresses.extend(
+                    account
+                        .utxo_context()
+                        .addresses()
+                        .iter()
+                        .map(|address| address.as_ref().clone()),
+                );
+            }
+        }
+        addresses
+    }
+
+    pub fn enable(&self) {
+        self.service_events
+            .sender
+            .try_send(MempoolMonitorEvents::Enable)
+            .unwrap();
+    }
+
+    pub fn disable(&self) {
+        self.service_events
+            .sender
+            .try_send(MempoolMonitorEvents::Disable)
+            .unwrap();
+    }
+
+    async fn update_entries(&self, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+        let entries = rpc_api.get_mempool_entries(true, false).await?;
+
+        let now = unixtime_as_millis_f64();
+        let wallet_addresses = self.wallet_addresses();
+        let mut first_seen = self.first_seen.lock().unwrap();
+        let mut wallet_transactions = self.wallet_transactions.lock().unwrap();
+        let entries = entries
+            .into_iter()
+            .filter_map(
+                |entry| match MempoolEntry::try_new(entry, &mut first_seen, now) {
+                    Ok(mut entry) => {
+                        entry.is_wallet = wallet_transactions.contains(&entry.id)
+                            || entry
+                                .addresses()
+                                .any(|address| wallet_addresses.contains(address));
+                        Some(entry)
+                    }
+                    Err(err) => {
+                        log_warn!("Unable to process mempool entry: {err}");
+                        None
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+
+        // forget transactions that have left the mempool
+        let ids = entries
+            .iter()
+            .map(|entry| entry.id)
+            .collect::<AHashSet<_>>();
+        first_seen.retain(|id, _| ids.contains(id));
+        wallet_transactions.retain(|id| ids.contains(id));
+        drop(wallet_transactions);
+        drop(first_seen);
+
+        self.entries.lock().unwrap().replace(Arc::new(entries));
+        let filter = self.filter.lock().unwrap();
+        self.update_view(&filter);
+        Ok(())
+    }
+
+    fn reset(&self) {
+        self.entries.lock().unwrap().take();
+        self.view.lock().unwrap().take();
+        self.first_seen.lock().unwrap().clear();
+    }
+}
+
+#[async_trait]
+impl Service for MempoolMonitorService {
+    fn name(&self) -> &'static str {
+        "mempool-monitor"
+    }
+
+    async fn attach_rpc(self: Arc<Self>, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+        self.rpc_api.lock().unwrap().replace(rpc_api.clone());
+        Ok(())
+    }
+
+    async fn detach_rpc(self: Arc<Self>) -> Result<()> {
+        self.rpc_api.lock().unwrap().take();
+        self.reset();
+
+        Ok(())
+    }
+
+    async fn spawn(self: Arc<Self>) -> Result<()> {
+        let this = self.clone();
+
+        let interval = task::interval(Duration::from_secs(MEMPOOL_POLLING_INTERVAL_SECONDS));
+        pin_mut!(interval);
+
+        loop {
+            select! {
+                _ = interval.next().fuse() => {
+                    if !self.is_enabled.load(Ordering::Relaxed) {
+                        continue;
+                    }
+
+                    if let Some(rpc_api) = this.rpc_api() {
+                        if let Err(err) = this.update_entries(&rpc_api).await {
+                            log_trace!("Unable to fetch mempool entries: {err}");
+                        }
+                        runtime().request_repaint();
+                    }
+                },
+                msg = this.as_ref().service_events.receiver.recv().fuse() => {
+                    if let Ok(event) = msg {
+                        match event {
+                            MempoolMonitorEvents::Enable => {
+                                self.is_enabled.store(true, Ordering::Relaxed);
+                            }
+                            MempoolMonitorEvents::Disable => {
+                                self.is_enabled.store(false, Ordering::Relaxed);
+                                this.reset();
+                            }
+                            MempoolMonitorEvents::Exit => {
+                                break;
+                            }
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+
+        this.task_ctl.send(()).await.unwrap();
+        Ok(())
+    }
+
+    fn terminate(self: Arc<Self>) {
+        self.service_events
+            .sender
+            .try_send(MempoolMonitorEvents::Exit)
+            .unwrap();
+    }
+
+    async fn join(self: Arc<Self>) -> Result<()> {
+        self.task_ctl.recv().await.unwrap();
+        Ok(())
+    }
+}
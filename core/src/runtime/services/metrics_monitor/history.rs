@@ -0,0 +1,437 @@
+//!
+//! On-disk metrics history.
+//!
+//! Metrics snapshots are stored in three tiers of decreasing resolution
+//! (per second, per minute and per hour), each tier averaging the samples
+//! received during its resolution interval. Each tier is split into
+//! time-based segment files so that expired data can be removed by
+//! deleting whole segments.
+//!
+//! A segment file contains a header (magic, format version and the list
+//! of metric names) followed by fixed-size records consisting of the
+//! unixtime in seconds (`u64`), the number of averaged samples (`u32`)
+//! and one `f32` value per metric.
+//!
+//! Partially filled buckets are written when the history is closed, so a
+//! segment can contain consecutive records with the same timestamp; these
+//! are merged when the segment is read, weighted by their sample counts.
+//!
+
+use super::MetricsHistoryData;
+use crate::imports::*;
+use kaspa_metrics_core::{Metric, MetricsSnapshot};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Write};
+
+const HISTORY_MAGIC: &[u8; 4] = b"KNGM";
+const HISTORY_FORMAT_VERSION: u16 = 2;
+const HISTORY_FILE_EXTENSION: &str = "bin";
+
+/// Interval at which buffered records are written to the segment files
+const SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricsTier {
+    Second,
+    Minute,
+    Hour,
+}
+
+impl MetricsTier {
+    pub fn list() -> [MetricsTier; 3] {
+        [MetricsTier::Second, MetricsTier::Minute, MetricsTier::Hour]
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            MetricsTier::Second => "second",
+            MetricsTier::Minute => "minute",
+            MetricsTier::Hour => "hour",
+        }
+    }
+
+    /// Sample resolution in seconds
+    pub(crate) fn resolution(&self) -> u64 {
+        match self {
+            MetricsTier::Second => 1,
+            MetricsTier::Minute => MINUTE,
+            MetricsTier::Hour => HOUR,
+        }
+    }
+
+    /// Time span covered by a single segment file in seconds
+    pub(crate) fn segment(&self) -> u64 {
+        match self {
+            MetricsTier::Second => HOUR,
+            MetricsTier::Minute => DAY,
+            MetricsTier::Hour => 30 * DAY,
+        }
+    }
+
+    /// Data retention period in seconds
+    fn retention(&self) -> u64 {
+        match self {
+            MetricsTier::Second => DAY,
+            MetricsTier::Minute => 8 * DAY,
+            MetricsTier::Hour => 400 * DAY,
+        }
+    }
+}
+
+impl MetricsHistoryRange {
+    /// Tier used to display the range; `None` for the live view.
+    pub fn tier(&self) -> Option<MetricsTier> {
+        match self {
+            MetricsHistoryRange::Live => None,
+            MetricsHistoryRange::Day | MetricsHistoryRange::Week => Some(MetricsTier::Minute),
+            MetricsHistoryRange::Month => Some(MetricsTier::Hour),
+        }
+    }
+}
+
+struct Bucket {
+    start: u64,
+    sums: Vec<f64>,
+    count: usize,
+}
+
+impl Bucket {
+    fn new(start: u64, values: &[f64]) -> Self {
+        Self {
+            start,
+            sums: values.to_vec(),
+            count: 1,
+        }
+    }
+
+    fn add(&mut self, values: &[f64]) {
+        self.sums
+            .iter_mut()
+            .zip(values.iter())
+            .for_each(|(sum, value)| *sum += value);
+        self.count += 1;
+    }
+
+    fn average(&self) -> impl Iterator<Item = f32> + '_ {
+        self.sums.iter().map(|sum| (sum / self.count as f64) as f32)
+    }
+}
+
+pub(crate) struct TierWriter {
+    tier: MetricsTier,
+    folder: PathBuf,
+    segment: Option<(u64, BufWriter<File>)>,
+    bucket: Option<Bucket>,
+}
+
+impl TierWriter {
+    pub(crate) fn try_new(tier: MetricsTier, folder: &Path) -> Result<Self> {
+        let folder = folder.join(tier.as_str());
+        fs::create_dir_all(&folder)?;
+        Ok(Self {
+            tier,
+            folder,
+            segment: None,
+            bucket: None,
+        })
+    }
+
+    pub(crate) fn ingest(&mut self, timestamp: u64, values: &[f64]) -> Result<()> {
+        let start = timestamp - timestamp % self.tier.resolution();
+        match self.bucket.as_mut() {
+            Some(bucket) if bucket.start == start => bucket.add(values),
+            _ => {
+                if let Some(bucket) = self.bucket.replace(Bucket::new(start, values)) {
+                    self.write(&bucket)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the current (partially filled) bucket and
+    /// the buffered records to the segment file.
+    pub(crate) fn flush(&mut self) -> Result<()> {
+        if let Some(bucket) = self.bucket.take() {
+            self.write(&bucket)?;
+        }
+        self.sync()
+    }
+
+    /// Write the buffered records to the segment file.
+    fn sync(&mut self) -> Result<()> {
+        if let Some((_, writer)) = self.segment.as_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, bucket: &Bucket) -> Result<()> {
+        let segment_start = bucket.start - bucket.start % self.tier.segment();
+        if self.segment.as_ref().map(|(start, _)| *start) != Some(segment_start) {
+            if let Some((_, mut writer)) = self.segment.take() {
+                writer.flush()?;
+            }
+            let path = segment_path(&self.folder, segment_start);
+            if path.exists() && !is_current_segment(&path) {
+                log_warn!(
+                    "Replacing metrics history segment {} recorded in a different format",
+                    path.display()
+                );
+                fs::remove_file(&path)?;
+            }
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            let is_new = file.metadata()?.len() == 0;
+            let mut writer = BufWriter::new(file);
+            if is_new {
+                write_header(&mut writer)?;
+            }
+            self.segment = Some((segment_start, writer));
+            self.prune(bucket.start)?;
+        }
+
+        let (_, writer) = self.segment.as_mut().unwrap();
+        writer.write_all(&bucket.start.to_le_bytes())?;
+        writer.write_all(&(bucket.count as u32).to_le_bytes())?;
+        for value in bucket.average() {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove segments that no longer contain data within the retention period.
+    fn prune(&self, now: u64) -> Result<()> {
+        let expired = now.saturating_sub(self.tier.retention() + self.tier.segment());
+        for (segment_start, path) in segments(&self.folder)? {
+            if segment_start < expired {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Records are buffered and written to the segment files every
+/// [`SYNC_INTERVAL`], when a segment is complete and when the
+/// history is dropped.
+pub struct MetricsHistory {
+    writers: Vec<TierWriter>,
+    last_sync: Instant,
+}
+
+impl MetricsHistory {
+    /// Metrics history folder of the given network (`~/.kaspa-ng/metrics/<network>`).
+    pub fn folder(network: Network) -> Result<PathBuf> {
+        let storage_folder = kaspa_wallet_core::storage::local::default_storage_folder();
+        Ok(workflow_store::fs::resolve_path(storage_folder)?
+            .join("metrics")
+            .join(network.to_string()))
+    }
+
+    pub fn try_new(network: Network) -> Result<Self> {
+        let folder = Self::folder(network)?;
+        let writers = MetricsTier::list()
+            .into_iter()
+            .map(|tier| TierWriter::try_new(tier, &folder))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            writers,
+            last_sync: Instant::now(),
+        })
+    }
+
+    pub fn ingest(&mut self, snapshot: &MetricsSnapshot) -> Result<()> {
+        let timestamp = (snapshot.unixtime_millis / 1000.0) as u64;
+        let values = Metric::list()
+            .into_iter()
+            .map(|metric| snapshot.get(&metric))
+            .collect::<Vec<_>>();
+        for writer in self.writers.iter_mut() {
+            writer.ingest(timestamp, &values)?;
+        }
+
+        if self.last_sync.elapsed() >= SYNC_INTERVAL {
+            self.last_sync = Instant::now();
+            for writer in self.writers.iter_mut() {
+                writer.sync()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write partially filled buckets of all tiers. Called when
+    /// the history is dropped (i.e. when the node disconnects).
+    pub fn flush(&mut self) -> Result<()> {
+        for writer in self.writers.iter_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Load samples of the given tier recorded during the last `duration`.
+    /// Plot point timestamps are in milliseconds.
+    pub fn load(
+        network: Network,
+        tier: MetricsTier,
+        duration: Duration,
+    ) -> Result<MetricsHistoryData> {
+        let folder = Self::folder(network)?.join(tier.as_str());
+        let mut data = Metric::list()
+            .into_iter()
+            .map(|metric| (metric, Vec::new()))
+            .collect::<MetricsHistoryData>();
+
+        if !folder.exists() {
+            return Ok(data);
+        }
+
+        let from = (unixtime_as_millis_f64() / 1000.0) as u64 - duration.as_secs();
+        let mut segments = segments(&folder)?;
+        segments.sort_by_key(|(segment_start, _)| *segment_start);
+        for (segment_start, path) in segments {
+            if segment_start + tier.segment() < from {
+                continue;
+            }
+            if let Err(err) = read_segment(&path, from, &mut data) {
+                log_warn!("Unable to read metrics history {}: {err}", path.display());
+            }
+        }
+
+        Ok(data)
+    }
+}
+
+impl Drop for MetricsHistory {
+    fn drop(&mut self) {
+        if let Err(err) = self.flush() {
+            log_warn!("Unable to flush metrics history: {err}");
+        }
+    }
+}
+
+fn segment_path(folder: &Path, segment_start: u64) -> PathBuf {
+    folder.join(format!("{segment_start}.{HISTORY_FILE_EXTENSION}"))
+}
+
+pub(crate) fn segments(folder: &Path) -> Result<Vec<(u64, PathBuf)>> {
+    let mut segments = vec![];
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(HISTORY_FILE_EXTENSION) {
+            continue;
+        }
+        if let Some(segment_start) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok())
+        {
+            segments.push((segment_start, path));
+        }
+    }
+    Ok(segments)
+}
+
+fn write_header(writer: &mut impl Write) -> Result<()> {
+    let metrics = Metric::list();
+    writer.write_all(HISTORY_MAGIC)?;
+    writer.write_all(&HISTORY_FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(&(metrics.len() as u16).to_le_bytes())?;
+    for metric in metrics {
+        let name = metric.as_str().as_bytes();
+        writer.write_all(&[name.len() as u8])?;
+        writer.write_all(name)?;
+    }
+    Ok(())
+}
+
+/// Read the segment header, returning the stored metrics, in the order of
+/// record values. Metrics that are no longer known are returned as `None`.
+fn read_header(reader: &mut impl Read) -> Result<Vec<Option<Metric>>> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != HISTORY_MAGIC {
+        return Err(Error::custom("Invalid metrics history file"));
+    }
+
+    let mut buffer = [0u8; 2];
+    reader.read_exact(&mut buffer)?;
+    let version = u16::from_le_bytes(buffer);
+    if version != HISTORY_FORMAT_VERSION {
+        return Err(Error::custom(format!(
+            "Unsupported metrics history format version {version}"
+        )));
+    }
+
+    reader.read_exact(&mut buffer)?;
+    let count = u16::from_le_bytes(buffer) as usize;
+    let known = Metric::list();
+    let mut metrics = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut len = [0u8; 1];
+        reader.read_exact(&mut len)?;
+        let mut name = vec![0u8; len[0] as usize];
+        reader.read_exact(&mut name)?;
+        let name = String::from_utf8_lossy(&name);
+        metrics.push(known.iter().find(|metric| metric.as_str() == name).cloned());
+    }
+
+    Ok(metrics)
+}
+
+/// Check if the segment has been recorded in the current format with the
+/// current list of metrics, so that new records can be appended to it.
+fn is_current_segment(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    read_header(&mut BufReader::new(file))
+        .map(|metrics| metrics.into_iter().eq(Metric::list().into_iter().map(Some)))
+        .unwrap_or(false)
+}
+
+pub(crate) fn read_segment(path: &Path, from: u64, data: &mut MetricsHistoryData) -> Result<()> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let metrics = read_header(&mut reader)?;
+
+    let mut record = vec![0u8; 12 + metrics.len() * 4];
+    // timestamp and sample count of the previous record
+    let mut previous: Option<(u64, u32)> = None;
+    // a partially written trailing record is ignored
+    while reader.read_exact(&mut record).is_ok() {
+        let timestamp = u64::from_le_bytes(record[0..8].try_into().unwrap());
+        let count = u32::from_le_bytes(record[8..12].try_into().unwrap()).max(1);
+        if timestamp < from {
+            continue;
+        }
+
+        // merge a bucket flushed before the history was closed
+        // with the remainder of the bucket recorded after reopening
+        let merged = previous
+            .filter(|(previous, _)| *previous == timestamp)
+            .map(|(_, count)| count);
+        previous = Some((timestamp, count + merged.unwrap_or_default()));
+
+        let x = (timestamp * 1000) as f64;
+        for (metric, value) in metrics.iter().zip(record[12..].chunks_exact(4)) {
+            if let Some(metric) = metric {
+                let y = f32::from_le_bytes(value.try_into().unwrap()) as f64;
+                let points = data.entry(*metric).or_default();
+                match (merged, points.last_mut()) {
+                    (Some(merged), Some(last)) if last.x == x => {
+                        last.y =
+                            (last.y * merged as f64 + y * count as f64) / (merged + count) as f64;
+                    }
+                    _ => points.push(PlotPoint { x, y }),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,518 @@
+use crate::alerts::{dispatch_alerts, AlertEvent, AlertHistory, MetricAlertMonitor};
+use crate::imports::*;
+use crate::runtime::Service;
+pub use futures::{future::FutureExt, select, Future};
+use kaspa_metrics_core::{Metric, Metrics, MetricsSnapshot};
+#[allow(unused_imports)]
+use kaspa_wallet_core::rpc::{NotificationMode, Rpc, RpcCtl, WrpcEncoding};
+
+cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        pub mod history;
+        pub use history::{MetricsHistory, MetricsTier};
+        pub mod prometheus;
+        pub use prometheus::{PrometheusServer, PROMETHEUS_DEFAULT_PORT};
+        use std::net::SocketAddr;
+
+        /// Interval at which history data displayed by the UI is reloaded from disk.
+        const HISTORY_RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+    }
+}
+
+/// Metrics samples loaded from the metrics history, with timestamps in milliseconds.
+pub type MetricsHistoryData = AHashMap<Metric, Vec<PlotPoint>>;
+
+#[allow(clippy::identity_op)]
+pub const MAX_METRICS_SAMPLES: usize = 60 * 60 * 24 * 1; // 1 day
+
+pub struct MetricsService {
+    pub application_events: ApplicationEventsChannel,
+    pub task_ctl: Channel<()>,
+    pub metrics: Arc<Metrics>,
+    pub metrics_data: Mutex<HashMap<Metric, Vec<PlotPoint>>>,
+    pub samples_since_connection: Arc<AtomicUsize>,
+    pub alerts: Mutex<AlertSettings>,
+    pub alert_monitor: Mutex<MetricAlertMonitor>,
+    pub alert_history: Mutex<AlertHistory>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub history: Mutex<Option<MetricsHistory>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::type_complexity)]
+    pub history_cache:
+        Mutex<AHashMap<(Network, MetricsHistoryRange), (Instant, Arc<MetricsHistoryData>)>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub history_requests: Mutex<AHashSet<(Network, MetricsHistoryRange)>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub snapshot: Mutex<Option<MetricsSnapshot>>,
+    /// Network of the connected node
+    #[cfg(not(target_arch = "wasm32"))]
+    pub network: Mutex<Network>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub prometheus_settings: Mutex<PrometheusSettings>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub prometheus: Mutex<Option<PrometheusServer>>,
+}
+
+impl MetricsService {
+    pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+        let metrics = Arc::new(Metrics::default());
+        let metrics_data = Metric::list()
+            .into_iter()
+            .map(|metric| (metric, Vec::new()))
+            .collect::<HashMap<Metric, Vec<_>>>();
+
+        Self {
+            application_events,
+            task_ctl: Channel::oneshot(),
+            metrics,
+            metrics_data: Mutex::new(metrics_data),
+            samples_since_connection: Arc::new(AtomicUsize::new(0)),
+            alerts: Mutex::new(settings.alerts.clone()),
+            alert_monitor: Mutex::new(MetricAlertMonitor::default()),
+            alert_history: Mutex::new(AlertHistory::default()),
+            #[cfg(not(target_arch = "wasm32"))]
+            history: Mutex::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            history_cache: Mutex::new(AHashMap::new()),
+            #[cfg(not(target_arch = "wasm32"))]
+            history_requests: Mutex::new(AHashSet::new()),
+            #[cfg(not(target_arch = "wasm32"))]
+            snapshot: Mutex::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            network: Mutex::new(settings.node.network),
+            #[cfg(not(target_arch = "wasm32"))]
+            prometheus_settings: Mutex::new(settings.prometheus.clone()),
+            #[cfg(not(target_arch = "wasm32"))]
+            prometheus: Mutex::new(None),
+        }
+    }
+
+    pub fn metrics_data(&self) -> MutexGuard<'_, HashMap<Metric, Vec<PlotPoint>>> {
+        self.metrics_data.lock().unwrap()
+    }
+
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    /// Triggered alerts, most recent first.
+    pub fn alert_history(&self) -> Vec<AlertEvent> {
+        self.alert_history
+            .lock()
+            .unwrap()
+            .events
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    }
+
+    /// Record triggered alerts in the alert history and persist it.
+    pub fn record_alerts(&self, alerts: &[String]) {
+        let history = {
+            let mut history = self.alert_history.lock().unwrap();
+            history.record(alerts);
+            history.clone()
+        };
+        Self::store_alert_history(history);
+    }
+
+    pub fn clear_alert_history(&self) {
+        let history = {
+            let mut history = self.alert_history.lock().unwrap();
+            history.events.clear();
+            history.clone()
+        };
+        Self::store_alert_history(history);
+    }
+
+    fn store_alert_history(history: AlertHistory) {
+        spawn(async move {
+            if let Err(err) = history.store().await {
+                log_warn!("Unable to store alert history: {}", err);
+            }
+            Ok(())
+        });
+    }
+
+    pub fn reset_metrics_data(&self) -> Result<()> {
+        let mut metrics_data = self.metrics_data.lock().unwrap();
+        for metric in Metric::list().into_iter() {
+            metrics_data.insert(metric, Vec::with_capacity(MAX_METRICS_SAMPLES));
+        }
+        Ok(())
+    }
+
+    /// Start recording metrics history for the given network. The
+    /// in-memory samples recorded during the last day are restored in
+    /// a background task and merged with samples received meanwhile.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_history(self: &Arc<Self>, network: Network) {
+        match MetricsHistory::try_new(network) {
+            Ok(history) => {
+                self.history.lock().unwrap().replace(history);
+            }
+            Err(err) => {
+                log_warn!("Unable to record metrics history: {err}");
+            }
+        }
+
+        let this = self.clone();
+        spawn(async move {
+            let data = match load_history(
+                network,
+                MetricsTier::Second,
+                Duration::from_secs(MAX_METRICS_SAMPLES as u64),
+            )
+            .await
+            {
+                Ok(data) => data,
+                Err(err) => {
+                    log_warn!("Unable to load metrics history: {err}");
+                    return Ok(());
+                }
+            };
+
+            let mut metrics_data = this.metrics_data.lock().unwrap();
+            for (metric, mut points) in data.into_iter() {
+                let dest = metrics_data.entry(metric).or_default();
+                let last = points.last().map(|point| point.x).unwrap_or_default();
+                points.extend(dest.drain(..).filter(|point| point.x > last));
+                if points.len() > MAX_METRICS_SAMPLES {
+                    points.drain(0..points.len() - MAX_METRICS_SAMPLES);
+                }
+                *dest = points;
+            }
+
+            Ok(())
+        });
+    }
+
+    /// Metrics history for the given range, reloaded from disk in a
+    /// background task at most once per [`HISTORY_RELOAD_INTERVAL`].
+    /// Each range is cached separately, so that panels displaying
+    /// different ranges do not evict each other. Returns the cached
+    /// (possibly stale) data, or an empty data set while the history
+    /// is loaded for the first time.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn history(
+        self: &Arc<Self>,
+        network: Network,
+        range: MetricsHistoryRange,
+    ) -> Option<Arc<MetricsHistoryData>> {
+        let (tier, duration) = (range.tier()?, range.duration()?);
+
+        let key = (network, range);
+        let cached = self.history_cache.lock().unwrap().get(&key).cloned();
+        let is_stale = cached
+            .as_ref()
+            .map(|(instant, _)| instant.elapsed() >= HISTORY_RELOAD_INTERVAL)
+            .unwrap_or(true);
+
+        if is_stale && self.history_requests.lock().unwrap().insert(key) {
+            let this = self.clone();
+            spawn(async move {
+                let data = load_history(network, tier, duration)
+                    .await
+                    .unwrap_or_else(|err| {
+                        log_warn!("Unable to load metrics history: {err}");
+                        MetricsHistoryData::default()
+                    });
+                this.history_cache
+                    .lock()
+                    .unwrap()
+                    .insert(key, (Instant::now(), Arc::new(data)));
+                this.history_requests.lock().unwrap().remove(&key);
+                runtime().request_repaint();
+                Ok(())
+            });
+        }
+
+        Some(cached.map(|(_, data)| data).unwrap_or_default())
+    }
+
+    /// Metrics history is not recorded in the browser.
+    #[cfg(target_arch = "wasm32")]
+    pub fn history(
+        self: &Arc<Self>,
+        _network: Network,
+        _range: MetricsHistoryRange,
+    ) -> Option<Arc<MetricsHistoryData>> {
+        None
+    }
+
+    /// Update node alert rules evaluated on each metrics snapshot.
+    pub fn update_alert_settings(&self, settings: &AlertSettings) {
+        *self.alerts.lock().unwrap() = settings.clone();
+    }
+
+    /// Latest metrics snapshot received from the connected node.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn snapshot(&self) -> Option<MetricsSnapshot> {
+        self.snapshot.lock().unwrap().clone()
+    }
+
+    /// Network of the connected (or last connected) node.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn network(&self) -> Network {
+        *self.network.lock().unwrap()
+    }
+
+    /// In-memory samples of the metric within the given range, expressed
+    /// as sample offsets relative to the latest sample (e.g. `-60..0`).
+    pub fn metrics_data_range(
+        &self,
+        metric: &Metric,
+        range: std::ops::Range<isize>,
+    ) -> Vec<PlotPoint> {
+        let metrics_data = self.metrics_data();
+        let Some(data) = metrics_data.get(metric) else {
+            return Vec::new();
+        };
+        let start = range.start.min(0).unsigned_abs().min(data.len());
+        let end = range.end.min(0).unsigned_abs().min(start);
+        data[data.len() - start..data.len() - end].to_vec()
+    }
+
+    /// Export the given metrics as CSV, one row per sample timestamp and
+    /// one column per metric. Exports the history data if supplied, or the
+    /// in-memory samples within the given range otherwise.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn export_csv(
+        &self,
+        metrics: &[Metric],
+        history: Option<&MetricsHistoryData>,
+        range: std::ops::Range<isize>,
+        target: &Path,
+    ) -> Result<()> {
+        use std::io::Write;
+
+        let mut rows = std::collections::BTreeMap::<u64, Vec<Option<f64>>>::new();
+        for (index, metric) in metrics.iter().enumerate() {
+            let points = match history {
+                Some(history) => history.get(metric).cloned().unwrap_or_default(),
+                None => self.metrics_data_range(metric, range.clone()),
+            };
+            for point in points {
+                rows.entry(point.x as u64)
+                    .or_insert_with(|| vec![None; metrics.len()])[index] = Some(point.y);
+            }
+        }
+
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(target)?);
+        let header = metrics
+            .iter()
+            .map(|metric| metric.as_str())
+            .collect::<Vec<_>>();
+        writeln!(writer, "timestamp,{}", header.join(","))?;
+        for (timestamp, values) in rows {
+            let values = values
+                .into_iter()
+                .map(|value| value.map(|value| value.to_string()).unwrap_or_default())
+                .collect::<Vec<_>>();
+            writeln!(writer, "{timestamp},{}", values.join(","))?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Address of the running Prometheus endpoint.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn prometheus_address(&self) -> Option<SocketAddr> {
+        self.prometheus
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|server| server.address())
+    }
+
+    /// Apply Prometheus endpoint settings, restarting the endpoint if enabled.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn update_prometheus_settings(self: &Arc<Self>, settings: &PrometheusSettings) {
+        *self.prometheus_settings.lock().unwrap() = settings.clone();
+
+        let this = self.clone();
+        spawn(async move {
+            let server = this.prometheus.lock().unwrap().take();
+            if let Some(server) = server {
+                server.stop().await?;
+            }
+            this.start_prometheus().await;
+            Ok(())
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn start_prometheus(self: &Arc<Self>) {
+        let settings = self.prometheus_settings.lock().unwrap().clone();
+        if !settings.enable {
+            return;
+        }
+
+        match PrometheusServer::try_start(&settings, self.clone()).await {
+            Ok(server) => {
+                log_info!("Prometheus endpoint listening on {}", server.address());
+                self.prometheus.lock().unwrap().replace(server);
+            }
+            Err(err) => {
+                crate::runtime::runtime().error(format!(
+                    "{} {err}",
+                    i18n("Unable to start the Prometheus endpoint:")
+                ));
+            }
+        }
+    }
+
+    pub fn ingest_metrics_snapshot(&self, snapshot: Box<MetricsSnapshot>) -> Result<()> {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.snapshot.lock().unwrap().replace((*snapshot).clone());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut history = self.history.lock().unwrap();
+            if let Some(writer) = history.as_mut() {
+                if let Err(err) = writer.ingest(&snapshot) {
+                    log_warn!("Unable to record metrics history: {err}");
+                    history.take();
+                }
+            }
+        }
+
+        let timestamp = snapshot.unixtime_millis;
+        let mut metrics_data = self.metrics_data.lock().unwrap();
+        for metric in Metric::list().into_iter() {
+            let dest = metrics_data.get_mut(&metric).unwrap();
+            if dest.is_empty() {
+                let y = snapshot.get(&metric);
+                let mut timestamp = timestamp - MAX_METRICS_SAMPLES as f64 * 1000.0;
+                for _ in 0..(MAX_METRICS_SAMPLES - 1) {
+                    dest.push(PlotPoint { x: timestamp, y });
+
+                    timestamp += 1000.0;
+                }
+            }
+            if dest.len() > MAX_METRICS_SAMPLES {
+                dest.drain(0..dest.len() - MAX_METRICS_SAMPLES);
+            }
+            dest.push(PlotPoint {
+                x: timestamp,
+                y: snapshot.get(&metric),
+            });
+        }
+
+        if snapshot.node_cpu_cores > 0.0 {
+            let alert_settings = self.alerts.lock().unwrap().clone();
+            let alerts = self
+                .alert_monitor
+                .lock()
+                .unwrap()
+                .evaluate(&alert_settings, &snapshot);
+            dispatch_alerts(&self.application_events, alerts);
+
+            self.application_events
+                .sender
+                .try_send(crate::events::Events::MempoolSize {
+                    mempool_size: snapshot.get(&Metric::NetworkMempoolSize) as usize,
+                })
+                .unwrap();
+
+            self.application_events
+                .sender
+                .try_send(crate::events::Events::Metrics { snapshot })
+                .unwrap();
+        }
+
+        self.samples_since_connection.fetch_add(1, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    pub fn samples_since_connection(&self) -> usize {
+        self.samples_since_connection.load(Ordering::SeqCst)
+    }
+}
+
+/// Load the metrics history on the blocking thread pool,
+/// reading segment files can take a while for long ranges.
+#[cfg(not(target_arch = "wasm32"))]
+async fn load_history(
+    network: Network,
+    tier: MetricsTier,
+    duration: Duration,
+) -> Result<MetricsHistoryData> {
+    tokio::task::spawn_blocking(move || MetricsHistory::load(network, tier, duration))
+        .await
+        .map_err(|err| Error::custom(err.to_string()))?
+}
+
+#[async_trait]
+impl Service for MetricsService {
+    fn name(&self) -> &'static str {
+        "metrics-service"
+    }
+
+    async fn attach_rpc(self: Arc<Self>, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+        let this = self.clone();
+        self.metrics
+            .register_sink(Arc::new(Box::new(move |snapshot: MetricsSnapshot| {
+                if let Err(err) = this.ingest_metrics_snapshot(Box::new(snapshot)) {
+                    println!("Error ingesting metrics snapshot: {}", err);
+                }
+                None
+            })));
+
+        self.reset_metrics_data()?;
+        self.alert_monitor.lock().unwrap().reset();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let network = *crate::runtime::runtime()
+                .kaspa_service()
+                .network
+                .lock()
+                .unwrap();
+            *self.network.lock().unwrap() = network;
+            self.start_history(network);
+        }
+        self.metrics.start_task().await?;
+        self.metrics.bind_rpc(Some(rpc_api.clone()));
+        Ok(())
+    }
+    async fn detach_rpc(self: Arc<Self>) -> Result<()> {
+        self.metrics.unregister_sink();
+        self.metrics.stop_task().await?;
+        self.metrics.bind_rpc(None);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.history.lock().unwrap().take();
+            self.snapshot.lock().unwrap().take();
+        }
+
+        Ok(())
+    }
+
+    async fn connect_rpc(self: Arc<Self>) -> Result<()> {
+        self.samples_since_connection.store(0, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn spawn(self: Arc<Self>) -> Result<()> {
+        let history = AlertHistory::load().await;
+        self.alert_history.lock().unwrap().merge(history);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.start_prometheus().await;
+        Ok(())
+    }
+
+    fn terminate(self: Arc<Self>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(server) = self.prometheus.lock().unwrap().as_ref() {
+            server.signal_stop();
+        }
+    }
+
+    async fn join(self: Arc<Self>) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+//!
+//! Prometheus metrics endpoint.
+//!
+//! Serves the latest [`MetricsSnapshot`] in the Prometheus text exposition
+//! format at `http://<address>/metrics`. Each [`Metric`] is published as a
+//! gauge named after the metric (e.g. `kaspa_node_cpu_usage`) and labeled
+//! with the current network.
+//!
+
+use super::MetricsService;
+use crate::imports::*;
+use futures::{select, FutureExt};
+use kaspa_metrics_core::{Metric, MetricsSnapshot};
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+pub const PROMETHEUS_DEFAULT_PORT: u16 = 9110;
+
+const METRICS_PATH: &str = "/metrics";
+const METRICS_PREFIX: &str = "kaspa";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+/// Time allowed for a client to send the request headers
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct PrometheusServer {
+    address: SocketAddr,
+    shutdown: Channel<()>,
+    done: Channel<()>,
+}
+
+impl PrometheusServer {
+    /// Bind the endpoint to the configured network interface
+    /// and start serving requests.
+    pub async fn try_start(
+        settings: &PrometheusSettings,
+        metrics_service: Arc<MetricsService>,
+    ) -> Result<Self> {
+        let address: SocketAddr = ContextualNetAddress::from(settings.network_interface.clone())
+            .normalize(PROMETHEUS_DEFAULT_PORT)
+            .into();
+        let listener = TcpListener::bind(address).await?;
+
+        let shutdown = Channel::oneshot();
+        let done = Channel::oneshot();
+        let shutdown_receiver = shutdown.receiver.clone();
+        let done_sender = done.sender.clone();
+
+        spawn(async move {
+            loop {
+                select! {
+                    _ = shutdown_receiver.recv().fuse() => break,
+                    connection = listener.accept().fuse() => {
+                        match connection {
+                            Ok((stream, _)) => {
+                                let metrics_service = metrics_service.clone();
+                                let network = metrics_service.network();
+                                tokio::spawn(async move {
+                                    if let Err(err) = handle_connection(stream, &metrics_service, network).await {
+                                        log_trace!("Prometheus endpoint request error: {err}");
+                                    }
+                                });
+                            }
+                            Err(err) => {
+                                log_warn!("Prometheus endpoint error: {err}");
+                            }
+                        }
+                    }
+                }
+            }
+
+            drop(listener);
+            done_sender.send(()).await?;
+            Ok(())
+        });
+
+        Ok(Self {
+            address,
+            shutdown,
+            done,
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Signal the server to stop without waiting for the listener to close.
+    pub fn signal_stop(&self) {
+        self.shutdown.sender.try_send(()).ok();
+    }
+
+    /// Stop the server, returning once the listening socket is released.
+    pub async fn stop(self) -> Result<()> {
+        self.signal_stop();
+        self.done.receiver.recv().await?;
+        Ok(())
+    }
+}
+
+/// Read the request headers, returning `None` if the client
+/// closed the connection before completing the request.
+async fn read_request(stream: &mut TcpStream) -> Result<Option<Vec<u8>>> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let len = stream.read(&mut buffer).await?;
+        if len == 0 {
+            return Ok(None);
+        }
+        request.extend_from_slice(&buffer[..len]);
+        if request.len() > MAX_REQUEST_SIZE {
+            return Err(Error::custom("Request too large"));
+        }
+    }
+    Ok(Some(request))
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    metrics_service: &MetricsService,
+    network: Network,
+) -> Result<()> {
+    let request = select! {
+        request = read_request(&mut stream).fuse() => request?,
+        _ = task::sleep(REQUEST_TIMEOUT).fuse() => {
+            return Err(Error::custom("Request timeout"));
+        }
+    };
+    let Some(request) = request else {
+        return Ok(());
+    };
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.split_whitespace();
+    let response = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(METRICS_PATH)) => {
+            let body = render_metrics(metrics_service.snapshot().as_ref(), network);
+            http_response("200 OK", CONTENT_TYPE, &body)
+        }
+        (Some("GET"), _) => http_response("404 Not Found", "text/plain", "Not Found\n"),
+        _ => http_response(
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method Not Allowed\n",
+        ),
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn http_response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Prometheus metric name (`NodeCpuUsage` becomes `kaspa_node_cpu_usage`).
+pub(crate) fn metric_name(metric: &Metric) -> String {
+    let mut name = METRICS_PREFIX.to_string();
+    for c in metric.as_str().chars() {
+        if c.is_ascii_uppercase() {
+            name.push('_');
+            name.push(c.to_ascii_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+pub(crate) fn render_metrics(snapshot: Option<&MetricsSnapshot>, network: Network) -> String {
+    let mut text = String::new();
+
+    let connected = format!("{METRICS_PREFIX}_node_connected");
+    writeln!(text, "# HELP {connected} Node connection state").ok();
+    writeln!(text, "# TYPE {connected} gauge").ok();
+    writeln!(
+        text,
+        "{connected}{{network=\"{network}\"}} {}",
+        snapshot.is_some() as u8
+    )
+    .ok();
+
+    if let Some(snapshot) = snapshot {
+        for metric in Metric::list() {
+            let name = metric_name(&metric);
+            writeln!(text, "# HELP {name} {}", metric.title().0).ok();
+            writeln!(text, "# TYPE {name} gauge").ok();
+            writeln!(
+                text,
+                "{name}{{network=\"{network}\"}} {}",
+                snapshot.get(&metric)
+            )
+            .ok();
+        }
+    }
+
+    text
+}
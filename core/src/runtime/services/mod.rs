@@ -9,6 +9,9 @@ pub use kaspa::KaspaService;
 pub mod peer_monitor;
 pub use peer_monitor::PeerMonitorService;
 
+pub mod mempool_monitor;
+pub use mempool_monitor::MempoolMonitorService;
+
 pub mod market_monitor;
 pub use market_monitor::MarketMonitorService;
 
@@ -17,6 +20,9 @@ pub use update_monitor::UpdateMonitorService;
 
 pub mod metrics_monitor;
 pub use metrics_monitor::MetricsService;
+
+pub mod server_monitor;
+pub use server_monitor::ServerMonitorService;
 cfg_if! {
     if #[cfg(not(feature = "lean"))] {
 
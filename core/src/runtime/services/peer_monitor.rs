@@ -1,11 +1,29 @@
 use crate::imports::*;
-use kaspa_rpc_core::RpcPeerInfo;
+use kaspa_rpc_core::{RpcContextualPeerAddress, RpcIpAddress, RpcPeerInfo};
 
 pub const PEER_POLLING_INTERVAL_SECONDS: u64 = 1; // 1 sec
 
+/// Peer management operations executed against the connected node.
+/// These require the node to run with unsafe RPC enabled.
+#[derive(Debug, Clone)]
+pub enum PeerAction {
+    Ban(RpcIpAddress),
+    Unban(RpcIpAddress),
+    /// The node RPC has no direct disconnect call, so the
+    /// peer is disconnected by banning and unbanning it
+    /// (unless the address is in the persistent ban list).
+    Disconnect(RpcIpAddress),
+    AddPeer {
+        address: RpcContextualPeerAddress,
+        is_permanent: bool,
+    },
+}
+
 pub enum PeerMonitorEvents {
     Enable,
     Disable,
+    Action(PeerAction),
+    ApplyBanList,
     Exit,
 }
 
@@ -15,21 +33,111 @@ pub struct PeerMonitorService {
     pub task_ctl: Channel<()>,
     pub rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
     pub peer_info: Mutex<Option<Arc<Vec<RpcPeerInfo>>>>,
+    pub banned_addresses: Mutex<Option<Arc<Vec<RpcIpAddress>>>>,
+    pub ban_list: Mutex<Vec<RpcIpAddress>>,
+    pub is_peer_management_available: AtomicBool,
     pub is_enabled: Arc<AtomicBool>,
 }
 
 impl PeerMonitorService {
-    pub fn new(application_events: ApplicationEventsChannel, _settings: &Settings) -> Self {
+    pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
         Self {
             application_events,
             service_events: Channel::unbounded(),
             task_ctl: Channel::oneshot(),
             rpc_api: Mutex::new(None),
             peer_info: Mutex::new(None),
+            banned_addresses: Mutex::new(None),
+            ban_list: Mutex::new(Self::parse_ban_list(&settings.peers)),
+            is_peer_management_available: AtomicBool::new(
+                settings.node.is_peer_management_available(),
+            ),
             is_enabled: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    fn parse_ban_list(settings: &PeerSettings) -> Vec<RpcIpAddress> {
+        settings
+            .banned
+            .iter()
+            .filter_map(|ip| ip.parse().ok())
+            .collect()
+    }
+
+    /// Update the persistent ban list; the list is applied
+    /// to the node each time the node is connected.
+    pub fn update_settings(&self, settings: &PeerSettings) {
+        *self.ban_list.lock().unwrap() = Self::parse_ban_list(settings);
+    }
+
+    /// Track whether the node is local and accepts unsafe RPC calls;
+    /// the ban list is applied only to such nodes.
+    pub fn update_node_settings(&self, node_settings: &NodeSettings) {
+        self.is_peer_management_available.store(
+            node_settings.is_peer_management_available(),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Addresses currently banned by the node, available
+    /// while the peer monitor is enabled.
+    pub fn banned_addresses(&self) -> Option<Arc<Vec<RpcIpAddress>>> {
+        self.banned_addresses.lock().unwrap().clone()
+    }
+
+    pub fn action(&self, action: PeerAction) {
+        self.service_events
+            .sender
+            .try_send(PeerMonitorEvents::Action(action))
+            .unwrap();
+    }
+
+    async fn update_banned_addresses(&self, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+        let response = rpc_api.get_peer_addresses().await?;
+        self.banned_addresses
+            .lock()
+            .unwrap()
+            .replace(Arc::new(response.banned_addresses));
+        Ok(())
+    }
+
+    async fn handle_action(&self, action: PeerAction) -> Result<()> {
+        let rpc_api = self
+            .rpc_api()
+            .ok_or_else(|| Error::custom("The node is not connected"))?;
+
+        match action {
+            PeerAction::Ban(ip) => rpc_api.ban(ip).await?,
+            PeerAction::Unban(ip) => rpc_api.unban(ip).await?,
+            PeerAction::Disconnect(ip) => {
+                rpc_api.ban(ip).await?;
+                if !self.ban_list.lock().unwrap().contains(&ip) {
+                    rpc_api.unban(ip).await?;
+                }
+            }
+            PeerAction::AddPeer {
+                address,
+                is_permanent,
+            } => rpc_api.add_peer(address, is_permanent).await?,
+        }
+
+        self.update_banned_addresses(&rpc_api).await?;
+        Ok(())
+    }
+
+    async fn apply_ban_list(&self) {
+        let Some(rpc_api) = self.rpc_api() else {
+            return;
+        };
+
+        let ban_list = self.ban_list.lock().unwrap().clone();
+        for ip in ban_list {
+            if let Err(err) = rpc_api.ban(ip).await {
+                log_warn!("Unable to ban {ip}: {err}");
+            }
+        }
+    }
+
     pub fn rpc_api(&self) -> Option<Arc<dyn RpcApi>> {
         self.rpc_api.lock().unwrap().clone()
     }
@@ -67,10 +175,22 @@ impl Service for PeerMonitorService {
     async fn detach_rpc(self: Arc<Self>) -> Result<()> {
         self.rpc_api.lock().unwrap().take();
         self.peer_info.lock().unwrap().take();
+        self.banned_addresses.lock().unwrap().take();
 
         Ok(())
     }
 
+    async fn connect_rpc(self: Arc<Self>) -> Result<()> {
+        if self.is_peer_management_available.load(Ordering::Relaxed)
+            && !self.ban_list.lock().unwrap().is_empty()
+        {
+            self.service_events
+                .sender
+                .try_send(PeerMonitorEvents::ApplyBanList)?;
+        }
+        Ok(())
+    }
+
     async fn spawn(self: Arc<Self>) -> Result<()> {
         let this = self.clone();
         let _application_events_sender = self.application_events.sender.clone();
@@ -96,10 +216,28 @@ impl Service for PeerMonitorService {
                         match event {
                             PeerMonitorEvents::Enable => {
                                 self.is_enabled.store(true, Ordering::Relaxed);
+                                if let Some(rpc_api) = this.rpc_api() {
+                                    // banned addresses are not available if unsafe RPC is disabled
+                                    this.update_banned_addresses(&rpc_api).await.ok();
+                                }
                             }
                             PeerMonitorEvents::Disable => {
                                 self.is_enabled.store(false, Ordering::Relaxed);
                                 this.peer_info.lock().unwrap().take();
+                                this.banned_addresses.lock().unwrap().take();
+                            }
+                            PeerMonitorEvents::Action(action) => {
+                                if let Err(err) = this.handle_action(action).await {
+                                    this.application_events
+                                        .sender
+                                        .try_send(Events::Notify {
+                                            user_notification: UserNotification::error(err.to_string()),
+                                        })
+                                        .ok();
+                                }
+                            }
+                            PeerMonitorEvents::ApplyBanList => {
+                                this.apply_ban_list().await;
                             }
                             PeerMonitorEvents::Exit => {
                                 break;
@@ -0,0 +1,497 @@
+use crate::imports::*;
+use crate::servers::{random_public_server, selectable_public_servers};
+use kaspa_wallet_core::rpc::{ConnectOptions, ConnectStrategy};
+
+pub const SERVER_PROBE_INTERVAL_SECONDS: u64 = 60 * 5;
+pub const SERVER_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Duration of the current server disconnect before switching to another server
+pub const FAILOVER_DISCONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Duration without DAA score progress before the server is considered stalled
+pub const FAILOVER_STALL_TIMEOUT: Duration = Duration::from_secs(90);
+const STALL_CHECK_INTERVAL_SECONDS: u64 = 10;
+/// Maximum number of public servers probed simultaneously
+const MAX_CONCURRENT_PROBES: usize = 8;
+
+pub enum ServerMonitorEvents {
+    Enable,
+    Disable,
+    Probe,
+    NodeSettings(Box<NodeSettings>),
+    Exit,
+}
+
+/// Result of a public server probe.
+#[derive(Debug, Clone)]
+pub struct ServerHealth {
+    pub server: Server,
+    /// Round-trip time of the `get_server_info()` call
+    pub latency: Option<Duration>,
+    pub server_version: Option<String>,
+    pub is_synced: Option<bool>,
+    pub has_utxo_index: Option<bool>,
+    pub virtual_daa_score: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl ServerHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+            && self.latency.is_some()
+            && self.is_synced.unwrap_or(false)
+            && self.has_utxo_index.unwrap_or(false)
+    }
+
+    /// Selection score (lower is better); latency weighted by the server bias.
+    pub fn score(&self) -> Option<f64> {
+        self.is_healthy()
+            .then(|| self.latency)
+            .flatten()
+            .map(|latency| latency.as_secs_f64() / self.server.bias.unwrap_or(1.).max(0.01) as f64)
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ServerHealthTable {
+    pub network: Option<Network>,
+    pub timestamp: Option<Instant>,
+    pub servers: Vec<ServerHealth>,
+}
+
+impl ServerHealthTable {
+    /// Select the best scoring server among `candidates`
+    /// if the table holds probe results for the network.
+    pub fn best_server(&self, network: &Network, candidates: &[Server]) -> Option<Server> {
+        if self.network != Some(*network) {
+            return None;
+        }
+
+        self.servers
+            .iter()
+            .filter(|health| candidates.contains(&health.server))
+            .filter_map(|health| health.score().map(|score| (score, &health.server)))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, server)| server.clone())
+    }
+}
+
+#[derive(Default)]
+struct ConnectionTracker {
+    /// Set when the RPC has been attached but is not connected
+    disconnected: Option<Instant>,
+    /// Last observed DAA score and the time it was observed
+    progress: Option<(u64, Instant)>,
+}
+
+pub struct ServerMonitorService {
+    pub application_events: ApplicationEventsChannel,
+    pub service_events: Channel<ServerMonitorEvents>,
+    pub task_ctl: Channel<()>,
+    pub rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
+    pub is_enabled: Arc<AtomicBool>,
+    pub is_probing: Arc<AtomicBool>,
+    pub automatic_failover: Arc<AtomicBool>,
+    node_settings: Mutex<NodeSettings>,
+    health: Mutex<Arc<ServerHealthTable>>,
+    tracker: Mutex<ConnectionTracker>,
+    is_checking_progress: AtomicBool,
+}
+
+impl ServerMonitorService {
+    pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+        Self {
+            application_events,
+            service_events: Channel::unbounded(),
+            task_ctl: Channel::oneshot(),
+            rpc_api: Mutex::new(None),
+            is_enabled: Arc::new(AtomicBool::new(settings.server_monitor.enable)),
+            is_probing: Arc::new(AtomicBool::new(false)),
+            automatic_failover: Arc::new(AtomicBool::new(
+                settings.server_monitor.automatic_failover,
+            )),
+            node_settings: Mutex::new(settings.node.clone()),
+            health: Mutex::new(Arc::new(ServerHealthTable::default())),
+            tracker: Mutex::new(ConnectionTracker::default()),
+            is_checking_progress: AtomicBool::new(false),
+        }
+    }
+
+    pub fn rpc_api(&self) -> Option<Arc<dyn RpcApi>> {
+        self.rpc_api.lock().unwrap().clone()
+    }
+
+    pub fn health(&self) -> Arc<ServerHealthTable> {
+        self.health.lock().unwrap().clone()
+    }
+
+    pub fn is_probing(&self) -> bool {
+        self.is_probing.load(Ordering::Relaxed)
+    }
+
+    pub fn update_settings(&self, settings: &ServerMonitorSettings) {
+        self.automatic_failover
+            .store(settings.automatic_failover, Ordering::Relaxed);
+        let event = if settings.enable {
+            ServerMonitorEvents::Enable
+        } else {
+            ServerMonitorEvents::Disable
+        };
+        self.service_events.try_send(event).unwrap();
+    }
+
+    /// Track node settings applied by the [`KaspaService`](super::KaspaService).
+    pub fn update_node_settings(&self, node_settings: &NodeSettings) {
+        self.service_events
+            .try_send(ServerMonitorEvents::NodeSettings(Box::new(
+                node_settings.clone(),
+            )))
+            .unwrap();
+    }
+
+    /// Request an immediate probe of public servers.
+    pub fn probe(&self) {
+        self.service_events
+            .try_send(ServerMonitorEvents::Probe)
+            .unwrap();
+    }
+
+    /// Select the best healthy server among `candidates` based on
+    /// the latest probe results, if available for the network.
+    pub fn best_server(&self, network: &Network, candidates: &[Server]) -> Option<Server> {
+        if !self.is_enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        self.health().best_server(network, candidates)
+    }
+
+    fn is_public_connection(node_settings: &NodeSettings) -> bool {
+        node_settings.node_kind == KaspadNodeKind::Remote
+            && node_settings.connection_config_kind != NodeConnectionConfigKind::Custom
+    }
+
+    fn is_failover_applicable(&self) -> bool {
+        let node_settings = self.node_settings.lock().unwrap();
+        self.automatic_failover.load(Ordering::Relaxed)
+            && node_settings.node_kind == KaspadNodeKind::Remote
+            && node_settings.connection_config_kind == NodeConnectionConfigKind::PublicServerRandom
+    }
+
+    fn is_probe_due(&self) -> bool {
+        let node_settings = self.node_settings.lock().unwrap();
+        if !self.is_enabled.load(Ordering::Relaxed) || !Self::is_public_connection(&node_settings) {
+            return false;
+        }
+
+        let health = self.health();
+        health.network != Some(node_settings.network)
+            || health
+                .timestamp
+                .map(|timestamp| {
+                    timestamp.elapsed() > Duration::from_secs(SERVER_PROBE_INTERVAL_SECONDS)
+                })
+                .unwrap_or(true)
+    }
+
+    fn spawn_probe(self: &Arc<Self>) {
+        if self.is_probing.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let this = self.clone();
+        spawn(async move {
+            let network = this.node_settings.lock().unwrap().network;
+            let servers = selectable_public_servers(&network, &[]);
+            let servers = futures::stream::iter(servers)
+                .map(|server| probe_server(server, network))
+                .buffered(MAX_CONCURRENT_PROBES)
+                .collect::<Vec<_>>()
+                .await;
+
+            // discard the results if the network has changed during the probe
+            if this.node_settings.lock().unwrap().network == network {
+                *this.health.lock().unwrap() = Arc::new(ServerHealthTable {
+                    network: Some(network),
+                    timestamp: Some(Instant::now()),
+                    servers,
+                });
+            }
+
+            this.is_probing.store(false, Ordering::SeqCst);
+            runtime().request_repaint();
+            Ok(())
+        });
+    }
+
+    /// Check the progress of the current server in a separate task, so
+    /// that the service events are handled during the RPC call.
+    fn spawn_progress_check(self: &Arc<Self>) {
+        if self.is_checking_progress.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let this = self.clone();
+        spawn(async move {
+            this.check_progress().await;
+            this.is_checking_progress.store(false, Ordering::SeqCst);
+            Ok(())
+        });
+    }
+
+    async fn check_progress(self: &Arc<Self>) {
+        let Some(rpc_api) = self.rpc_api() else {
+            return;
+        };
+
+        if self.tracker.lock().unwrap().disconnected.is_some() {
+            return;
+        }
+
+        if let Some(Ok(server_info)) =
+            with_timeout(rpc_api.get_server_info(), SERVER_PROBE_TIMEOUT).await
+        {
+            let mut tracker = self.tracker.lock().unwrap();
+            match tracker.progress {
+                Some((daa_score, _)) if daa_score >= server_info.virtual_daa_score => {}
+                _ => {
+                    tracker.progress = Some((server_info.virtual_daa_score, Instant::now()));
+                }
+            }
+        }
+    }
+
+    /// Check if the current server has been disconnected
+    /// or stalled for too long and should be replaced.
+    fn failover_reason(&self) -> Option<&'static str> {
+        let tracker = self.tracker.lock().unwrap();
+        if let Some(disconnected) = tracker.disconnected {
+            (disconnected.elapsed() > FAILOVER_DISCONNECT_TIMEOUT)
+                .then_some(i18n("Unable to connect to the public node"))
+        } else if let Some((_, instant)) = tracker.progress {
+            (instant.elapsed() > FAILOVER_STALL_TIMEOUT)
+                .then_some(i18n("The public node has stopped advancing"))
+        } else {
+            None
+        }
+    }
+
+    fn failover(&self, reason: &str) {
+        let node_settings = self.node_settings.lock().unwrap().clone();
+        let kaspa_service = runtime().kaspa_service().clone();
+        let current = kaspa_service.rpc_url();
+
+        // the current server is excluded from the selection, which prefers
+        // the best healthy server and otherwise falls back to a random
+        // server weighted by the server bias
+        let blacklist = || {
+            current.iter().fold(RpcOptions::new(), |options, url| {
+                options.blacklist(url.clone())
+            })
+        };
+        let server = random_public_server(&node_settings.network, Some(blacklist()));
+
+        let Some(server) = server else {
+            log_warn!("Server monitor: no alternative public node available");
+            // postpone the next attempt
+            let mut tracker = self.tracker.lock().unwrap();
+            tracker.disconnected = tracker.disconnected.map(|_| Instant::now());
+            tracker.progress = None;
+            return;
+        };
+
+        self.application_events
+            .sender
+            .try_send(Events::Notify {
+                user_notification: UserNotification::warning(format!(
+                    "{reason}, {} {server}",
+                    i18n("switching to")
+                ))
+                .as_toast(),
+            })
+            .ok();
+
+        *self.tracker.lock().unwrap() = ConnectionTracker::default();
+        kaspa_service.update_services(&node_settings, Some(blacklist().force(server)));
+    }
+}
+
+async fn with_timeout<T>(future: impl Future<Output = T>, duration: Duration) -> Option<T> {
+    let future = future.fuse();
+    let delay = task::sleep(duration).fuse();
+    pin_mut!(future, delay);
+    select! {
+        result = future => Some(result),
+        _ = delay => None,
+    }
+}
+
+async fn probe_server(server: Server, network: Network) -> ServerHealth {
+    let mut health = ServerHealth {
+        server: server.clone(),
+        latency: None,
+        server_version: None,
+        is_synced: None,
+        has_utxo_index: None,
+        virtual_daa_score: None,
+        error: None,
+    };
+
+    let result = async {
+        let encoding = server.wrpc_encoding();
+        let url =
+            KaspaRpcClient::parse_url(server.address(), encoding, NetworkId::from(network).into())?;
+        let client = Arc::new(KaspaRpcClient::new_with_args(
+            encoding,
+            Some(url.as_str()),
+            None,
+            None,
+            None,
+        )?);
+
+        let options = ConnectOptions {
+            block_async_connect: true,
+            strategy: ConnectStrategy::Fallback,
+            url: None,
+            connect_timeout: Some(SERVER_PROBE_TIMEOUT),
+            retry_interval: None,
+        };
+
+        let result = async {
+            client.connect(Some(options)).await?;
+            let instant = Instant::now();
+            let server_info = with_timeout(client.get_server_info(), SERVER_PROBE_TIMEOUT)
+                .await
+                .ok_or(Error::custom("Timeout"))??;
+            Ok::<_, Error>((server_info, instant.elapsed()))
+        }
+        .await;
+
+        client.disconnect().await.ok();
+        result
+    }
+    .await;
+
+    match result {
+        Ok((server_info, latency)) => {
+            health.latency = Some(latency);
+            health.server_version = Some(server_info.server_version);
+            health.is_synced = Some(server_info.is_synced);
+            health.has_utxo_index = Some(server_info.has_utxo_index);
+            health.virtual_daa_score = Some(server_info.virtual_daa_score);
+        }
+        Err(err) => {
+            health.error = Some(err.to_string());
+        }
+    }
+
+    health
+}
+
+#[async_trait]
+impl Service for ServerMonitorService {
+    fn name(&self) -> &'static str {
+        "server-monitor"
+    }
+
+    async fn attach_rpc(self: Arc<Self>, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+        self.rpc_api.lock().unwrap().replace(rpc_api.clone());
+        *self.tracker.lock().unwrap() = ConnectionTracker {
+            disconnected: Some(Instant::now()),
+            progress: None,
+        };
+        Ok(())
+    }
+
+    async fn detach_rpc(self: Arc<Self>) -> Result<()> {
+        self.rpc_api.lock().unwrap().take();
+        *self.tracker.lock().unwrap() = ConnectionTracker::default();
+        Ok(())
+    }
+
+    async fn connect_rpc(self: Arc<Self>) -> Result<()> {
+        let mut tracker = self.tracker.lock().unwrap();
+        tracker.disconnected = None;
+        tracker.progress = None;
+        Ok(())
+    }
+
+    async fn disconnect_rpc(self: Arc<Self>) -> Result<()> {
+        let mut tracker = self.tracker.lock().unwrap();
+        if tracker.disconnected.is_none() {
+            tracker.disconnected = Some(Instant::now());
+        }
+        tracker.progress = None;
+        Ok(())
+    }
+
+    async fn spawn(self: Arc<Self>) -> Result<()> {
+        let this = self.clone();
+
+        let interval = task::interval(Duration::from_secs(1));
+        pin_mut!(interval);
+
+        let mut ticks = 0u64;
+        loop {
+            select! {
+                _ = interval.next().fuse() => {
+                    ticks += 1;
+
+                    if this.is_probe_due() {
+                        this.spawn_probe();
+                    }
+
+                    if this.is_failover_applicable() {
+                        if ticks % STALL_CHECK_INTERVAL_SECONDS == 0 {
+                            this.spawn_progress_check();
+                        }
+
+                        if let Some(reason) = this.failover_reason() {
+                            this.failover(reason);
+                        }
+                    }
+                },
+                msg = this.as_ref().service_events.receiver.recv().fuse() => {
+                    if let Ok(event) = msg {
+                        match event {
+                            ServerMonitorEvents::Enable => {
+                                this.is_enabled.store(true, Ordering::Relaxed);
+                            }
+                            ServerMonitorEvents::Disable => {
+                                this.is_enabled.store(false, Ordering::Relaxed);
+                            }
+                            ServerMonitorEvents::Probe => {
+                                this.spawn_probe();
+                            }
+                            ServerMonitorEvents::NodeSettings(node_settings) => {
+                                let mut current = this.node_settings.lock().unwrap();
+                                if current.network != node_settings.network {
+                                    *this.health.lock().unwrap() = Arc::new(ServerHealthTable::default());
+                                }
+                                *current = *node_settings;
+                            }
+                            ServerMonitorEvents::Exit => {
+                                break;
+                            }
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+
+        this.task_ctl.send(()).await.unwrap();
+        Ok(())
+    }
+
+    fn terminate(self: Arc<Self>) {
+        self.service_events
+            .sender
+            .try_send(ServerMonitorEvents::Exit)
+            .unwrap();
+    }
+
+    async fn join(self: Arc<Self>) -> Result<()> {
+        self.task_ctl.recv().await.unwrap();
+        Ok(())
+    }
+}
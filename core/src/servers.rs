@@ -1,12 +1,30 @@
 use crate::imports::*;
+use kaspa_wallet_core::storage::local::storage::Storage;
 
 type ServerCollection = Arc<Mutex<Arc<HashMap<Network, Vec<Server>>>>>;
 
+/// Name of the file containing user-defined servers (in the `Servers.toml` format).
+pub const USER_SERVERS_STORAGE: &str = "kaspa-ng.servers.toml";
+
+/// Servers available to the application: the bundled (or fetched)
+/// server list merged with the user-defined servers.
 pub fn public_server_config() -> &'static ServerCollection {
     static SERVERS: OnceLock<ServerCollection> = OnceLock::new();
     SERVERS.get_or_init(|| Arc::new(Mutex::new(parse_default_servers().clone())))
 }
 
+/// Server list before merging user-defined servers.
+fn default_server_config() -> &'static ServerCollection {
+    static SERVERS: OnceLock<ServerCollection> = OnceLock::new();
+    SERVERS.get_or_init(|| Arc::new(Mutex::new(parse_default_servers().clone())))
+}
+
+/// User-defined servers, including disabled entries.
+pub fn user_servers() -> &'static Arc<Mutex<Vec<Server>>> {
+    static SERVERS: OnceLock<Arc<Mutex<Vec<Server>>>> = OnceLock::new();
+    SERVERS.get_or_init(|| Arc::new(Mutex::new(vec![])))
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Server {
     pub name: Option<String>,
@@ -51,13 +69,46 @@ impl Server {
     pub fn wrpc_encoding(&self) -> WrpcEncoding {
         self.protocol
     }
+
+    /// Check if `url` (as reported by the RPC client) refers to this server.
+    /// The server address is expanded to the URL used by the client (scheme
+    /// and default port) before the comparison.
+    pub fn matches_url(&self, url: &str) -> bool {
+        let address = KaspaRpcClient::parse_url(
+            self.address(),
+            self.wrpc_encoding(),
+            NetworkId::from(self.network).into(),
+        )
+        .unwrap_or_else(|_| self.address());
+        normalize_url(&address) == normalize_url(url)
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+fn normalize_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_lowercase()
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ServerConfig {
+    #[serde(default)]
     server: Vec<Server>,
 }
 
+impl ServerConfig {
+    pub fn new(server: Vec<Server>) -> Self {
+        Self { server }
+    }
+
+    /// Parse a server list without discarding disabled entries.
+    pub fn try_parse(toml: &str) -> Result<Vec<Server>> {
+        Ok(toml::from_str::<ServerConfig>(toml)?.server)
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|err| Error::custom(err.to_string()))
+    }
+}
+
 fn try_parse_servers(toml: &str) -> Result<Arc<HashMap<Network, Vec<Server>>>> {
     let servers: Vec<Server> = toml::from_str::<ServerConfig>(toml)?
         .server
@@ -93,14 +144,128 @@ pub fn parse_default_servers() -> &'static Arc<HashMap<Network, Vec<Server>>> {
     EMBEDDED_SERVERS.get_or_init(|| parse_servers(include_str!("../resources/Servers.toml")))
 }
 
+/// Load the user-defined servers and fetch the public server list.
+/// Both are merged into the server collection independently, so
+/// user servers are available even if the public list can not be
+/// fetched.
 pub fn update_public_servers() {
+    spawn(async move {
+        match load_user_servers().await {
+            Ok(servers) => {
+                *user_servers().lock().unwrap() = servers;
+                merge_user_servers();
+            }
+            Err(err) => log_error!("Unable to load user servers: {}", err),
+        }
+        Ok(())
+    });
+
     spawn(async move {
         let servers = fetch_public_servers().await?;
-        *public_server_config().lock().unwrap() = servers;
+        *default_server_config().lock().unwrap() = servers;
+        merge_user_servers();
         Ok(())
     });
 }
 
+/// Rebuild the server collection from the default
+/// server list and the enabled user-defined servers.
+fn merge_user_servers() {
+    // the collection is locked for the duration of the merge so
+    // that concurrent merges can not store an outdated result
+    let mut public_servers = public_server_config().lock().unwrap();
+    let servers = merge_servers(
+        &default_server_config().lock().unwrap(),
+        &user_servers().lock().unwrap(),
+    );
+    *public_servers = Arc::new(servers);
+}
+
+/// Add enabled user-defined servers to the default server list.
+/// User-defined servers replace default servers with the same address.
+pub(crate) fn merge_servers(
+    defaults: &HashMap<Network, Vec<Server>>,
+    user_servers: &[Server],
+) -> HashMap<Network, Vec<Server>> {
+    let mut servers = defaults.clone();
+
+    for server in user_servers.iter() {
+        if !server.enable.unwrap_or(true) {
+            continue;
+        }
+
+        let list = servers.entry(server.network).or_default();
+        list.retain(|existing| existing.address != server.address);
+        list.push(server.clone());
+    }
+
+    servers
+}
+
+fn user_servers_storage() -> Result<Storage> {
+    Ok(Storage::try_new(USER_SERVERS_STORAGE)?)
+}
+
+async fn load_user_servers() -> Result<Vec<Server>> {
+    let storage = user_servers_storage()?;
+    if storage.exists().await.unwrap_or(false) {
+        let toml = workflow_store::fs::read_to_string(storage.filename()).await?;
+        ServerConfig::try_parse(&toml)
+    } else {
+        Ok(vec![])
+    }
+}
+
+/// Replace the user-defined server list, apply it
+/// to the server collection and persist it.
+pub fn update_user_servers(servers: Vec<Server>) {
+    *user_servers().lock().unwrap() = servers.clone();
+    merge_user_servers();
+
+    spawn(async move {
+        let storage = user_servers_storage()?;
+        storage.ensure_dir().await?;
+        let toml = ServerConfig::new(servers).to_toml()?;
+        workflow_store::fs::write_string(storage.filename(), &toml).await?;
+        Ok(())
+    });
+}
+
+/// Merge imported servers into the user-defined server list,
+/// replacing entries with the same address. Returns the
+/// number of imported servers.
+pub fn import_user_servers(toml: &str) -> Result<usize> {
+    let imported = ServerConfig::try_parse(toml)?;
+    let count = imported.len();
+
+    let servers = merge_imported_servers(user_servers().lock().unwrap().clone(), imported);
+    update_user_servers(servers);
+    Ok(count)
+}
+
+/// Merge `imported` into `servers`, replacing entries with the same address.
+pub(crate) fn merge_imported_servers(
+    mut servers: Vec<Server>,
+    imported: Vec<Server>,
+) -> Vec<Server> {
+    for server in imported {
+        if let Some(existing) = servers
+            .iter_mut()
+            .find(|existing| existing.address == server.address)
+        {
+            *existing = server;
+        } else {
+            servers.push(server);
+        }
+    }
+    servers
+}
+
+/// Serialize the user-defined server list in the `Servers.toml` format.
+pub fn export_user_servers() -> Result<String> {
+    ServerConfig::new(user_servers().lock().unwrap().clone()).to_toml()
+}
+
 pub fn load_public_servers() {
     parse_default_servers();
     update_public_servers();
@@ -154,9 +319,40 @@ pub fn public_servers(network: &Network) -> Vec<Server> {
         .collect::<Vec<_>>()
 }
 
-pub fn random_public_server(network: &Network, options: Option<RpcOptions>) -> Option<Server> {
+/// Public servers eligible for automatic selection, excluding
+/// manually selectable, local and blacklisted servers.
+pub fn selectable_public_servers(network: &Network, blacklist_servers: &[String]) -> Vec<Server> {
     let servers = public_server_config().lock().unwrap().clone();
 
+    servers
+        .get(network)
+        .map(|servers| {
+            #[allow(clippy::nonminimal_bool)]
+            servers
+                .iter()
+                .filter(|server| {
+                    server.enable.unwrap_or(true)
+                        && !server.manual.unwrap_or(false)
+                        && !server.address.contains("localhost")
+                        && !server.address.contains("127.0.0.1")
+                        && !(tls()
+                            && !(server.address.starts_with("wss://")
+                                || server.address.starts_with("wrpcs://")))
+                        && !blacklist_servers
+                            .iter()
+                            .any(|url| *url == server.address || server.matches_url(url))
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+}
+
+/// Select a public server for the given network. If server health
+/// information is available, the best performing healthy server is
+/// selected, otherwise a random server is selected taking the server
+/// `bias` into account.
+pub fn random_public_server(network: &Network, options: Option<RpcOptions>) -> Option<Server> {
     let RpcOptions {
         force_server,
         blacklist_servers,
@@ -166,50 +362,39 @@ pub fn random_public_server(network: &Network, options: Option<RpcOptions>) -> O
         return Some(server);
     }
 
-    if let Some(servers) = servers.get(network) {
-        #[allow(clippy::nonminimal_bool)]
-        let mut servers = servers
-            .iter()
-            .filter(|server| {
-                server.enable.unwrap_or(true)
-                    && !server.manual.unwrap_or(false)
-                    && !server.address.contains("localhost")
-                    && !server.address.contains("127.0.0.1")
-                    && !(tls()
-                        && !(server.address.starts_with("wss://")
-                            || server.address.starts_with("wrpcs://")))
-                    && !blacklist_servers.contains(&server.address)
-            })
-            .cloned()
-            .collect::<Vec<_>>();
-
-        let max = servers
-            .iter()
-            .map(|server| server.bias.unwrap_or(1.))
-            .max_by(|a, b| a.total_cmp(b))
-            .unwrap_or(1.);
-        servers.iter_mut().for_each(|server| {
-            server.bias = Some(server.bias.unwrap_or(1.) / max);
-        });
-
-        if servers.is_empty() {
-            log_error!("Unable to select random public server: no servers available");
-            None
-        } else {
-            let mut server = None;
-            while server.is_none() {
-                let selected = &servers[rand::thread_rng().gen::<usize>() % servers.len()];
-                let f = rand::thread_rng().gen_range(0.0..1.0);
-                if f < selected.bias.unwrap_or(1.) {
-                    server = Some(selected.clone());
-                }
-            }
-            server
+    let mut servers = selectable_public_servers(network, &blacklist_servers);
+
+    if servers.is_empty() {
+        log_error!("Unable to select random public server: no servers available");
+        return None;
+    }
+
+    if let Some(server) = crate::runtime::try_runtime().and_then(|runtime| {
+        runtime
+            .server_monitor_service()
+            .best_server(network, &servers)
+    }) {
+        return Some(server);
+    }
+
+    let max = servers
+        .iter()
+        .map(|server| server.bias.unwrap_or(1.))
+        .max_by(|a, b| a.total_cmp(b))
+        .unwrap_or(1.);
+    servers.iter_mut().for_each(|server| {
+        server.bias = Some(server.bias.unwrap_or(1.) / max);
+    });
+
+    let mut server = None;
+    while server.is_none() {
+        let selected = &servers[rand::thread_rng().gen::<usize>() % servers.len()];
+        let f = rand::thread_rng().gen_range(0.0..1.0);
+        if f < selected.bias.unwrap_or(1.) {
+            server = Some(selected.clone());
         }
-    } else {
-        log_error!("Unable to select random public server: no servers available for this network");
-        None
     }
+    server
 }
 
 pub fn render_public_server_selector(
@@ -131,13 +131,22 @@ impl RpcOptions {
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
 pub enum RpcKind {
     #[default]
     Wrpc,
     Grpc,
 }
 
+impl std::fmt::Display for RpcKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcKind::Wrpc => write!(f, "wRPC"),
+            RpcKind::Grpc => write!(f, "gRPC"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum RpcConfig {
@@ -146,7 +155,7 @@ pub enum RpcConfig {
         encoding: WrpcEncoding,
     },
     Grpc {
-        url: Option<NetworkInterfaceConfig>,
+        url: Option<String>,
     },
 }
 
@@ -254,6 +263,123 @@ impl NodeConnectionConfigKind {
     }
 }
 
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NodeLogLevel {
+    Off,
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl NodeLogLevel {
+    pub fn iter() -> impl Iterator<Item = &'static NodeLogLevel> {
+        [
+            NodeLogLevel::Off,
+            NodeLogLevel::Error,
+            NodeLogLevel::Warn,
+            NodeLogLevel::Info,
+            NodeLogLevel::Debug,
+            NodeLogLevel::Trace,
+        ]
+        .iter()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeLogLevel::Off => "off",
+            NodeLogLevel::Error => "error",
+            NodeLogLevel::Warn => "warn",
+            NodeLogLevel::Info => "info",
+            NodeLogLevel::Debug => "debug",
+            NodeLogLevel::Trace => "trace",
+        }
+    }
+}
+
+impl std::fmt::Display for NodeLogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Structured configuration of the managed Rusty Kaspa node.
+/// These settings are rendered into the kaspad `Args` (or the
+/// daemon command line) and take effect after a node restart.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct KaspadConfig {
+    pub utxoindex: bool,
+    pub archival: bool,
+    pub outbound_target: usize,
+    pub inbound_limit: usize,
+    pub rpc_max_clients: usize,
+    pub connect_peers: Vec<String>,
+    pub add_peers: Vec<String>,
+    pub perf_metrics: bool,
+    pub log_level: NodeLogLevel,
+}
+
+impl Default for KaspadConfig {
+    fn default() -> Self {
+        Self {
+            utxoindex: true,
+            archival: false,
+            outbound_target: Self::DEFAULT_OUTBOUND_TARGET,
+            inbound_limit: Self::DEFAULT_INBOUND_LIMIT,
+            rpc_max_clients: Self::DEFAULT_RPC_MAX_CLIENTS,
+            connect_peers: vec![],
+            add_peers: vec![],
+            perf_metrics: true,
+            log_level: NodeLogLevel::default(),
+        }
+    }
+}
+
+impl KaspadConfig {
+    pub const DEFAULT_OUTBOUND_TARGET: usize = 8;
+    pub const DEFAULT_INBOUND_LIMIT: usize = 128;
+    pub const DEFAULT_RPC_MAX_CLIENTS: usize = 128;
+    pub const MAX_PEERS: usize = 1024;
+    pub const MAX_RPC_CLIENTS: usize = 4096;
+
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.outbound_target > Self::MAX_PEERS {
+            return Err(format!(
+                "{} {}",
+                i18n("Outbound peer target must not exceed"),
+                Self::MAX_PEERS
+            ));
+        }
+        if self.inbound_limit > Self::MAX_PEERS {
+            return Err(format!(
+                "{} {}",
+                i18n("Inbound peer limit must not exceed"),
+                Self::MAX_PEERS
+            ));
+        }
+        if self.rpc_max_clients == 0 || self.rpc_max_clients > Self::MAX_RPC_CLIENTS {
+            return Err(format!(
+                "{} 1..{}",
+                i18n("RPC max clients must be in the range"),
+                Self::MAX_RPC_CLIENTS
+            ));
+        }
+        if !self.connect_peers.is_empty() && self.outbound_target == 0 {
+            return Err(i18n("Connect peers require a non-zero outbound peer target").to_string());
+        }
+        for peer in self.connect_peers.iter().chain(self.add_peers.iter()) {
+            if ContextualNetAddress::from_str(peer).is_err() {
+                return Err(format!("{} '{}'", i18n("Invalid peer address"), peer));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub enum NodeMemoryScale {
@@ -359,6 +485,8 @@ pub struct NodeSettings {
     pub rpc_kind: RpcKind,
     pub wrpc_url: String,
     pub wrpc_encoding: WrpcEncoding,
+    #[serde(default = "default_grpc_url")]
+    pub grpc_url: String,
     pub enable_wrpc_json: bool,
     pub wrpc_json_network_interface: NetworkInterfaceConfig,
     pub enable_grpc: bool,
@@ -375,6 +503,14 @@ pub struct NodeSettings {
     pub kaspad_daemon_storage_folder_enable: bool,
     #[serde(default)]
     pub kaspad_daemon_storage_folder: String,
+    #[serde(default)]
+    pub kaspad_config: KaspadConfig,
+    #[serde(default)]
+    pub enable_unsafe_rpc: bool,
+}
+
+fn default_grpc_url() -> String {
+    "127.0.0.1".to_string()
 }
 
 impl Default for NodeSettings {
@@ -385,6 +521,7 @@ impl Default for NodeSettings {
             rpc_kind: RpcKind::Wrpc,
             wrpc_url: "127.0.0.1".to_string(),
             wrpc_encoding: WrpcEncoding::Borsh,
+            grpc_url: default_grpc_url(),
             enable_wrpc_json: false,
             wrpc_json_network_interface: NetworkInterfaceConfig::default(),
             enable_grpc: false,
@@ -398,11 +535,19 @@ impl Default for NodeSettings {
             kaspad_daemon_args_enable: false,
             kaspad_daemon_storage_folder_enable: false,
             kaspad_daemon_storage_folder: String::default(),
+            kaspad_config: KaspadConfig::default(),
+            enable_unsafe_rpc: false,
         }
     }
 }
 
 impl NodeSettings {
+    /// Peer management (ban, unban, add peer) requires a local node
+    /// started with unsafe RPC calls enabled by the user.
+    pub fn is_peer_management_available(&self) -> bool {
+        self.node_kind.is_local() && self.enable_unsafe_rpc
+    }
+
     cfg_if! {
         if #[cfg(not(target_arch = "wasm32"))] {
             #[allow(clippy::if_same_then_else)]
@@ -423,6 +568,8 @@ impl NodeSettings {
                     Some(true)
                 } else if self.enable_grpc != other.enable_grpc
                     || self.grpc_network_interface != other.grpc_network_interface
+                    || self.rpc_kind != other.rpc_kind
+                    || self.grpc_url != other.grpc_url
                     || self.wrpc_url != other.wrpc_url
                     || self.wrpc_encoding != other.wrpc_encoding
                     || self.enable_wrpc_json != other.enable_wrpc_json
@@ -432,6 +579,8 @@ impl NodeSettings {
                     Some(self.node_kind != KaspadNodeKind::IntegratedInProc)
                 } else if self.kaspad_daemon_args != other.kaspad_daemon_args
                     || self.kaspad_daemon_args_enable != other.kaspad_daemon_args_enable
+                    || self.kaspad_config != other.kaspad_config
+                    || self.enable_unsafe_rpc != other.enable_unsafe_rpc
                 {
                     Some(self.node_kind.is_config_capable())
                 } else if self.kaspad_daemon_binary != other.kaspad_daemon_binary {
@@ -465,6 +614,133 @@ impl NodeSettings {
     }
 }
 
+/// Named set of node connection parameters that can be
+/// applied to [`NodeSettings`] in a single step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConnectionProfile {
+    pub name: String,
+    pub node_kind: KaspadNodeKind,
+    pub network: Network,
+    pub connection_config_kind: NodeConnectionConfigKind,
+    #[serde(default)]
+    pub rpc_kind: RpcKind,
+    pub wrpc_url: String,
+    pub wrpc_encoding: WrpcEncoding,
+    #[serde(default = "default_grpc_url")]
+    pub grpc_url: String,
+    pub kaspad_daemon_args: String,
+    pub kaspad_daemon_args_enable: bool,
+    /// Public server selected for the profile network
+    #[serde(default)]
+    pub public_server: Option<Server>,
+}
+
+impl ConnectionProfile {
+    pub fn new(name: &str, settings: &NodeSettings) -> Self {
+        Self {
+            name: name.trim().to_string(),
+            node_kind: settings.node_kind,
+            network: settings.network,
+            connection_config_kind: settings.connection_config_kind,
+            rpc_kind: settings.rpc_kind,
+            wrpc_url: settings.wrpc_url.clone(),
+            wrpc_encoding: settings.wrpc_encoding,
+            grpc_url: settings.grpc_url.clone(),
+            kaspad_daemon_args: settings.kaspad_daemon_args.clone(),
+            kaspad_daemon_args_enable: settings.kaspad_daemon_args_enable,
+            public_server: settings.public_servers.get(&settings.network).cloned(),
+        }
+    }
+
+    pub fn apply(&self, settings: &mut NodeSettings) {
+        settings.node_kind = self.node_kind;
+        settings.network = self.network;
+        settings.connection_config_kind = self.connection_config_kind;
+        settings.rpc_kind = self.rpc_kind;
+        settings.wrpc_url = self.wrpc_url.clone();
+        settings.wrpc_encoding = self.wrpc_encoding;
+        settings.grpc_url = self.grpc_url.clone();
+        settings.kaspad_daemon_args = self.kaspad_daemon_args.clone();
+        settings.kaspad_daemon_args_enable = self.kaspad_daemon_args_enable;
+        if let Some(public_server) = &self.public_server {
+            settings
+                .public_servers
+                .insert(self.network, public_server.clone());
+        }
+    }
+
+    /// Check if the profile is currently in effect, ignoring
+    /// parameters that are not relevant to the profile node kind.
+    pub fn matches(&self, settings: &NodeSettings) -> bool {
+        if self.node_kind != settings.node_kind || self.network != settings.network {
+            return false;
+        }
+
+        if self.node_kind == KaspadNodeKind::Remote {
+            self.connection_config_kind == settings.connection_config_kind
+                && match self.connection_config_kind {
+                    NodeConnectionConfigKind::Custom => {
+                        self.rpc_kind == settings.rpc_kind
+                            && match self.rpc_kind {
+                                RpcKind::Wrpc => {
+                                    self.wrpc_url == settings.wrpc_url
+                                        && self.wrpc_encoding == settings.wrpc_encoding
+                                }
+                                RpcKind::Grpc => self.grpc_url == settings.grpc_url,
+                            }
+                    }
+                    NodeConnectionConfigKind::PublicServerCustom => {
+                        self.public_server.as_ref() == settings.public_servers.get(&self.network)
+                    }
+                    _ => true,
+                }
+        } else {
+            self.kaspad_daemon_args_enable == settings.kaspad_daemon_args_enable
+                && (!self.kaspad_daemon_args_enable
+                    || self.kaspad_daemon_args == settings.kaspad_daemon_args)
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let network = self.network.to_string().to_uppercase();
+        match self.node_kind {
+            KaspadNodeKind::Remote
+                if self.connection_config_kind == NodeConnectionConfigKind::Custom =>
+            {
+                match self.rpc_kind {
+                    RpcKind::Wrpc => format!(
+                        "{} · {network} · {} ({})",
+                        self.node_kind, self.wrpc_url, self.wrpc_encoding
+                    ),
+                    RpcKind::Grpc => format!(
+                        "{} · {network} · {} ({})",
+                        self.node_kind, self.grpc_url, self.rpc_kind
+                    ),
+                }
+            }
+            KaspadNodeKind::Remote
+                if self.connection_config_kind == NodeConnectionConfigKind::PublicServerCustom =>
+            {
+                match &self.public_server {
+                    Some(server) => format!("{} · {network} · {server}", self.node_kind),
+                    None => format!(
+                        "{} · {network} · {}",
+                        self.node_kind, self.connection_config_kind
+                    ),
+                }
+            }
+            KaspadNodeKind::Remote => {
+                format!(
+                    "{} · {network} · {}",
+                    self.node_kind, self.connection_config_kind
+                )
+            }
+            _ => format!("{} · {network}", self.node_kind),
+        }
+    }
+}
+
 impl RpcConfig {
     pub fn from_node_settings(settings: &NodeSettings, options: Option<RpcOptions>) -> Self {
         match settings.connection_config_kind {
@@ -474,7 +750,7 @@ impl RpcConfig {
                     encoding: settings.wrpc_encoding,
                 },
                 RpcKind::Grpc => RpcConfig::Grpc {
-                    url: Some(settings.grpc_network_interface.clone()),
+                    url: Some(settings.grpc_url.clone()),
                 },
             },
             NodeConnectionConfigKind::PublicServerCustom => {
@@ -501,6 +777,140 @@ impl RpcConfig {
     }
 }
 
+/// Time range displayed by the metrics graphs. Ranges other than
+/// [`Live`](MetricsHistoryRange::Live) are loaded from the metrics history.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetricsHistoryRange {
+    #[default]
+    Live,
+    Day,
+    Week,
+    Month,
+}
+
+impl MetricsHistoryRange {
+    pub fn list() -> [MetricsHistoryRange; 4] {
+        [
+            MetricsHistoryRange::Live,
+            MetricsHistoryRange::Day,
+            MetricsHistoryRange::Week,
+            MetricsHistoryRange::Month,
+        ]
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        const DAY: u64 = 24 * 60 * 60;
+        match self {
+            MetricsHistoryRange::Live => None,
+            MetricsHistoryRange::Day => Some(Duration::from_secs(DAY)),
+            MetricsHistoryRange::Week => Some(Duration::from_secs(7 * DAY)),
+            MetricsHistoryRange::Month => Some(Duration::from_secs(30 * DAY)),
+        }
+    }
+}
+
+impl std::fmt::Display for MetricsHistoryRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetricsHistoryRange::Live => write!(f, "{}", i18n("Live")),
+            MetricsHistoryRange::Day => write!(f, "{}", i18n("Day")),
+            MetricsHistoryRange::Week => write!(f, "{}", i18n("Week")),
+            MetricsHistoryRange::Month => write!(f, "{}", i18n("Month")),
+        }
+    }
+}
+
+/// Time range displayed by a dashboard panel. Ranges of up to a day are
+/// served from the in-memory samples, longer ranges from the metrics history.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetricsPanelRange {
+    Minute,
+    FiveMinutes,
+    #[default]
+    FifteenMinutes,
+    Hour,
+    SixHours,
+    Day,
+    Week,
+    Month,
+}
+
+impl MetricsPanelRange {
+    pub fn list() -> [MetricsPanelRange; 8] {
+        [
+            MetricsPanelRange::Minute,
+            MetricsPanelRange::FiveMinutes,
+            MetricsPanelRange::FifteenMinutes,
+            MetricsPanelRange::Hour,
+            MetricsPanelRange::SixHours,
+            MetricsPanelRange::Day,
+            MetricsPanelRange::Week,
+            MetricsPanelRange::Month,
+        ]
+    }
+
+    pub fn seconds(&self) -> u64 {
+        const MINUTE: u64 = 60;
+        const HOUR: u64 = 60 * MINUTE;
+        const DAY: u64 = 24 * HOUR;
+        match self {
+            MetricsPanelRange::Minute => MINUTE,
+            MetricsPanelRange::FiveMinutes => 5 * MINUTE,
+            MetricsPanelRange::FifteenMinutes => 15 * MINUTE,
+            MetricsPanelRange::Hour => HOUR,
+            MetricsPanelRange::SixHours => 6 * HOUR,
+            MetricsPanelRange::Day => DAY,
+            MetricsPanelRange::Week => 7 * DAY,
+            MetricsPanelRange::Month => 30 * DAY,
+        }
+    }
+
+    /// History range used to display the panel; `None` for in-memory samples.
+    pub fn history_range(&self) -> Option<MetricsHistoryRange> {
+        match self {
+            MetricsPanelRange::Week => Some(MetricsHistoryRange::Week),
+            MetricsPanelRange::Month => Some(MetricsHistoryRange::Month),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for MetricsPanelRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetricsPanelRange::Minute => write!(f, "{}", i18n("1 minute")),
+            MetricsPanelRange::FiveMinutes => write!(f, "{}", i18n("5 minutes")),
+            MetricsPanelRange::FifteenMinutes => write!(f, "{}", i18n("15 minutes")),
+            MetricsPanelRange::Hour => write!(f, "{}", i18n("1 hour")),
+            MetricsPanelRange::SixHours => write!(f, "{}", i18n("6 hours")),
+            MetricsPanelRange::Day => write!(f, "{}", i18n("1 day")),
+            MetricsPanelRange::Week => write!(f, "{}", i18n("1 week")),
+            MetricsPanelRange::Month => write!(f, "{}", i18n("1 month")),
+        }
+    }
+}
+
+/// Dashboard panel plotting one or more metrics. The first metric is
+/// plotted against the left axis; additional metrics are overlaid and
+/// scaled to the panel, with the second metric shown on the right axis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MetricsPanel {
+    pub metrics: Vec<Metric>,
+    #[serde(default)]
+    pub range: MetricsPanelRange,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MetricsDashboard {
+    pub name: String,
+    #[serde(default)]
+    pub panels: Vec<MetricsPanel>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct MetricsSettings {
@@ -509,6 +919,13 @@ pub struct MetricsSettings {
     pub graph_range_from: isize,
     pub graph_range_to: isize,
     pub disabled: AHashSet<Metric>,
+    #[serde(default)]
+    pub history_range: MetricsHistoryRange,
+    #[serde(default)]
+    pub dashboards: Vec<MetricsDashboard>,
+    /// Index of the displayed dashboard; `None` shows all enabled metrics
+    #[serde(default)]
+    pub dashboard: Option<usize>,
 }
 
 impl Default for MetricsSettings {
@@ -519,6 +936,9 @@ impl Default for MetricsSettings {
             graph_range_from: -15 * 60,
             graph_range_to: 0,
             disabled: AHashSet::default(),
+            history_range: MetricsHistoryRange::default(),
+            dashboards: vec![],
+            dashboard: None,
         }
     }
 }
@@ -545,6 +965,137 @@ impl Default for UserInterfaceSettings {
     }
 }
 
+#[derive(Default, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MarketDataProvider {
+    #[default]
+    CoinGecko,
+    CoinMarketCap,
+    Json,
+}
+
+impl std::fmt::Display for MarketDataProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarketDataProvider::CoinGecko => write!(f, "CoinGecko"),
+            MarketDataProvider::CoinMarketCap => write!(f, "CoinMarketCap"),
+            MarketDataProvider::Json => write!(f, "{}", i18n("Custom (JSON over HTTP)")),
+        }
+    }
+}
+
+impl MarketDataProvider {
+    pub fn iter() -> impl Iterator<Item = &'static MarketDataProvider> {
+        [
+            MarketDataProvider::CoinGecko,
+            MarketDataProvider::CoinMarketCap,
+            MarketDataProvider::Json,
+        ]
+        .iter()
+    }
+
+    /// Whether the provider offers OHLC market history.
+    pub fn supports_ohlc(&self) -> bool {
+        matches!(self, MarketDataProvider::CoinGecko)
+    }
+}
+
+/// Configuration of the generic JSON-over-HTTP market data provider.
+///
+/// The `url` may contain a `{currencies}` placeholder that is replaced
+/// with a comma-separated list of the monitored currencies. Field paths
+/// are dot-separated (array elements are addressed by index) and may
+/// contain a `{currency}` placeholder. Empty paths are ignored; the
+/// price path is mandatory.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonMarketDataProviderSettings {
+    pub url: String,
+    pub price_path: String,
+    pub market_cap_path: String,
+    pub volume_path: String,
+    pub change_path: String,
+}
+
+impl Default for JsonMarketDataProviderSettings {
+    fn default() -> Self {
+        Self {
+            url: "http://127.0.0.1:8080/price?currencies={currencies}".to_string(),
+            price_path: "kaspa.{currency}".to_string(),
+            market_cap_path: "kaspa.{currency}_market_cap".to_string(),
+            volume_path: "kaspa.{currency}_24h_vol".to_string(),
+            change_path: "kaspa.{currency}_24h_change".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MarketSettings {
+    pub fiat_currency: String,
+    #[serde(default)]
+    pub provider: MarketDataProvider,
+    #[serde(default = "default_market_currencies")]
+    pub currencies: Vec<String>,
+    #[serde(default)]
+    pub json_provider: JsonMarketDataProviderSettings,
+    /// CoinMarketCap Pro API key. Note that the key is stored
+    /// in plaintext along with the rest of the application settings.
+    #[serde(default)]
+    pub coinmarketcap_api_key: String,
+}
+
+fn default_market_currencies() -> Vec<String> {
+    vec!["usd".to_string(), "btc".to_string()]
+}
+
+impl Default for MarketSettings {
+    fn default() -> Self {
+        Self {
+            fiat_currency: "usd".to_string(),
+            provider: MarketDataProvider::default(),
+            currencies: default_market_currencies(),
+            json_provider: JsonMarketDataProviderSettings::default(),
+            coinmarketcap_api_key: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ServerMonitorSettings {
+    /// Periodically probe public servers (latency, sync state, version)
+    pub enable: bool,
+    /// Switch to another public server if the current server
+    /// disconnects or stops advancing
+    pub automatic_failover: bool,
+}
+
+impl Default for ServerMonitorSettings {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            automatic_failover: true,
+        }
+    }
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PrometheusSettings {
+    /// Publish node metrics over HTTP in the Prometheus text format
+    pub enable: bool,
+    pub network_interface: NetworkInterfaceConfig,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PeerSettings {
+    /// IP addresses banned by the user, re-applied
+    /// to the node each time it is connected
+    pub banned: Vec<String>,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct DeveloperSettings {
@@ -601,6 +1152,18 @@ pub struct Settings {
     pub language_code: String,
     pub update_monitor: bool,
     pub market_monitor: bool,
+    #[serde(default)]
+    pub market: MarketSettings,
+    #[serde(default)]
+    pub alerts: AlertSettings,
+    #[serde(default)]
+    pub connection_profiles: Vec<ConnectionProfile>,
+    #[serde(default)]
+    pub server_monitor: ServerMonitorSettings,
+    #[serde(default)]
+    pub peers: PeerSettings,
+    #[serde(default)]
+    pub prometheus: PrometheusSettings,
 }
 
 impl Default for Settings {
@@ -618,6 +1181,12 @@ impl Default for Settings {
             language_code: "en".to_string(),
             update_monitor: true,
             market_monitor: true,
+            market: MarketSettings::default(),
+            alerts: AlertSettings::default(),
+            connection_profiles: vec![],
+            server_monitor: ServerMonitorSettings::default(),
+            peers: PeerSettings::default(),
+            prometheus: PrometheusSettings::default(),
         }
     }
 }
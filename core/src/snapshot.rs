@@ -0,0 +1,487 @@
+//!
+//! Node database snapshots.
+//!
+//! A snapshot is a gzip-compressed stream containing a magic header,
+//! a JSON manifest (network, format version, paths and sizes of all
+//! files) followed by the contents of the database files in the order
+//! listed in the manifest. The contents of each file are followed by
+//! their SHA-256 checksum, so that the database is read only once
+//! during the export.
+//!
+
+use crate::imports::*;
+use crate::storage::DATABASE_FOLDER;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+pub const SNAPSHOT_MAGIC: &[u8; 16] = b"KASPA-NG-DBSNAP\0";
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+pub const SNAPSHOT_FILE_EXTENSION: &str = "kaspa-snapshot";
+
+const BUFFER_SIZE: usize = 1024 * 1024;
+/// Maximum accepted size of the snapshot manifest
+pub const MAX_MANIFEST_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotFile {
+    /// Path relative to the database folder (`/`-separated)
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub network: Network,
+    pub kaspad_version: String,
+    pub application_version: String,
+    /// Snapshot creation time (unixtime in milliseconds)
+    pub created: u64,
+    pub files: Vec<SnapshotFile>,
+}
+
+impl SnapshotManifest {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotOperation {
+    Export,
+    Restore,
+}
+
+impl std::fmt::Display for SnapshotOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotOperation::Export => write!(f, "{}", i18n("Snapshot export")),
+            SnapshotOperation::Restore => write!(f, "{}", i18n("Snapshot restore")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SnapshotState {
+    StoppingNode {
+        operation: SnapshotOperation,
+    },
+    Running {
+        operation: SnapshotOperation,
+        stage: &'static str,
+        processed: u64,
+        total: u64,
+    },
+    Completed {
+        operation: SnapshotOperation,
+        path: PathBuf,
+    },
+    Failed {
+        operation: SnapshotOperation,
+        error: String,
+    },
+}
+
+impl Storage {
+    pub fn snapshot_state(&self) -> Option<SnapshotState> {
+        self.snapshot.lock().unwrap().clone()
+    }
+
+    pub fn is_snapshot_running(&self) -> bool {
+        matches!(
+            self.snapshot_state(),
+            Some(SnapshotState::StoppingNode { .. } | SnapshotState::Running { .. })
+        )
+    }
+
+    pub fn clear_snapshot_state(&self) {
+        if !self.is_snapshot_running() {
+            self.snapshot.lock().unwrap().take();
+        }
+    }
+
+    fn set_snapshot_state(&self, state: SnapshotState) {
+        self.snapshot.lock().unwrap().replace(state);
+        runtime().request_repaint();
+    }
+
+    pub fn network_database_folder(&self, network: Network) -> PathBuf {
+        self.storage_root()
+            .join(format!("kaspa-{network}"))
+            .join(DATABASE_FOLDER)
+    }
+
+    /// Archive the database of the given network into `target`.
+    /// If the local node is running, it is stopped for the duration
+    /// of the export and restarted using `node_settings` afterwards.
+    pub fn export_snapshot(
+        &self,
+        node_settings: &NodeSettings,
+        network: Network,
+        target: PathBuf,
+    ) -> Result<()> {
+        let source = self.network_database_folder(network);
+        if !source.exists() {
+            return Err(Error::custom(format!(
+                "No database found for {}",
+                network.to_string().to_uppercase()
+            )));
+        }
+
+        self.run_snapshot_task(
+            SnapshotOperation::Export,
+            node_settings.clone(),
+            move |this| {
+                this.write_snapshot(network, &source, &target)
+                    .map(|_| target)
+            },
+        )
+    }
+
+    /// Restore a database snapshot for the given network from `source`.
+    /// The snapshot network and format version are verified before the
+    /// existing database is replaced.
+    pub fn restore_snapshot(
+        &self,
+        node_settings: &NodeSettings,
+        network: Network,
+        source: PathBuf,
+    ) -> Result<()> {
+        // fail early if the snapshot is not valid for this network
+        let manifest = read_snapshot_manifest(&source)?;
+        verify_manifest(&manifest, network)?;
+
+        let target = self.network_database_folder(network);
+        self.run_snapshot_task(
+            SnapshotOperation::Restore,
+            node_settings.clone(),
+            move |this| {
+                this.read_snapshot(network, &source, &target)
+                    .map(|_| target)
+            },
+        )
+    }
+
+    fn run_snapshot_task<F>(
+        &self,
+        operation: SnapshotOperation,
+        node_settings: NodeSettings,
+        task: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&Storage) -> Result<PathBuf> + Send + 'static,
+    {
+        if self.is_snapshot_running() || self.is_relocating() {
+            return Err(Error::custom("Another storage operation is in progress"));
+        }
+
+        self.set_snapshot_state(SnapshotState::StoppingNode { operation });
+
+        let this = self.clone();
+        std::thread::Builder::new()
+            .name("snapshot".to_string())
+            .spawn(move || {
+                let kaspa_service = runtime().kaspa_service().clone();
+                let restart = kaspa_service.is_local_node_running();
+
+                let result = if restart {
+                    futures::executor::block_on(kaspa_service.stop_local_node())
+                } else {
+                    Ok(())
+                };
+
+                let state = match result.and_then(|_| task(&this)) {
+                    Ok(path) => SnapshotState::Completed { operation, path },
+                    Err(err) => SnapshotState::Failed {
+                        operation,
+                        error: err.to_string(),
+                    },
+                };
+
+                if restart {
+                    kaspa_service.update_services(&node_settings, None);
+                }
+
+                this.set_snapshot_state(state);
+                this.update(None);
+            })
+            .map_err(|err| Error::custom(format!("Unable to start snapshot thread: {err}")))?;
+
+        Ok(())
+    }
+
+    fn update_snapshot_progress(
+        &self,
+        operation: SnapshotOperation,
+        stage: &'static str,
+        processed: u64,
+        total: u64,
+    ) {
+        self.set_snapshot_state(SnapshotState::Running {
+            operation,
+            stage,
+            processed,
+            total,
+        });
+    }
+
+    fn write_snapshot(&self, network: Network, source: &Path, target: &Path) -> Result<()> {
+        let operation = SnapshotOperation::Export;
+
+        let entries = walkdir::WalkDir::new(source)
+            .sort_by_file_name()
+            .into_iter()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| Error::custom(err.to_string()))?
+            .into_iter()
+            .filter(|entry| entry.file_type().is_file())
+            .collect::<Vec<_>>();
+
+        let mut files = Vec::with_capacity(entries.len());
+        for entry in entries.iter() {
+            let relative = entry
+                .path()
+                .strip_prefix(source)
+                .map_err(|err| Error::custom(err.to_string()))?;
+            let path = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let size = entry
+                .metadata()
+                .map_err(|err| Error::custom(err.to_string()))?
+                .len();
+            files.push(SnapshotFile { path, size });
+        }
+
+        let manifest = SnapshotManifest {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            network,
+            kaspad_version: kaspa_core::kaspad_env::version().to_string(),
+            application_version: crate::app::VERSION.to_string(),
+            created: unixtime_as_millis_f64() as u64,
+            files,
+        };
+        let total = manifest.total_size();
+
+        let partial = target.with_extension("partial");
+        let result = (|| -> Result<()> {
+            let file = File::create(&partial)?;
+            let mut encoder = GzEncoder::new(
+                BufWriter::with_capacity(BUFFER_SIZE, file),
+                Compression::default(),
+            );
+            let manifest_json = serde_json::to_vec(&manifest)?;
+            encoder.write_all(SNAPSHOT_MAGIC)?;
+            encoder.write_all(&(manifest_json.len() as u32).to_le_bytes())?;
+            encoder.write_all(&manifest_json)?;
+
+            let mut processed = 0;
+            let mut buffer = vec![0u8; BUFFER_SIZE];
+            for file in manifest.files.iter() {
+                let mut reader = File::open(source.join(&file.path))?.take(file.size);
+                let mut hasher = Sha256::new();
+                let mut written = 0;
+                loop {
+                    let len = reader.read(&mut buffer)?;
+                    if len == 0 {
+                        break;
+                    }
+                    hasher.update(&buffer[..len]);
+                    encoder.write_all(&buffer[..len])?;
+                    written += len as u64;
+                    processed += len as u64;
+                    self.update_snapshot_progress(operation, i18n("Compressing"), processed, total);
+                }
+                if written != file.size {
+                    return Err(Error::custom(format!(
+                        "File '{}' has changed during the export",
+                        file.path
+                    )));
+                }
+                encoder.write_all(&hasher.finalize())?;
+            }
+
+            encoder.finish()?.flush()?;
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => {
+                std::fs::rename(&partial, target)?;
+                Ok(())
+            }
+            Err(err) => {
+                std::fs::remove_file(&partial).ok();
+                Err(err)
+            }
+        }
+    }
+
+    fn read_snapshot(&self, network: Network, source: &Path, target: &Path) -> Result<()> {
+        let operation = SnapshotOperation::Restore;
+
+        let mut decoder = open_snapshot(source)?;
+        let manifest = read_manifest(&mut decoder)?;
+        verify_manifest(&manifest, network)?;
+
+        let total = manifest.total_size();
+        let staging = target.with_extension("restore");
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)?;
+        }
+
+        let result = (|| -> Result<()> {
+            let mut processed = 0;
+            let mut buffer = vec![0u8; BUFFER_SIZE];
+            for file in manifest.files.iter() {
+                // file paths are validated by `verify_manifest()`
+                let path = staging.join(&file.path);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+
+                let mut writer = BufWriter::with_capacity(BUFFER_SIZE, File::create(&path)?);
+                let mut reader = (&mut decoder).take(file.size);
+                let mut hasher = Sha256::new();
+                let mut written = 0;
+                loop {
+                    let len = reader.read(&mut buffer)?;
+                    if len == 0 {
+                        break;
+                    }
+                    hasher.update(&buffer[..len]);
+                    writer.write_all(&buffer[..len])?;
+                    written += len as u64;
+                    processed += len as u64;
+                    self.update_snapshot_progress(operation, i18n("Extracting"), processed, total);
+                }
+                writer.flush()?;
+
+                if written != file.size {
+                    return Err(Error::custom("Snapshot file is truncated"));
+                }
+                let mut checksum = [0u8; 32];
+                decoder
+                    .read_exact(&mut checksum)
+                    .map_err(|_| Error::custom("Snapshot file is truncated"))?;
+                if hasher.finalize().as_slice() != checksum {
+                    return Err(Error::custom(format!(
+                        "Checksum mismatch for '{}'",
+                        file.path
+                    )));
+                }
+            }
+
+            Ok(())
+        })();
+
+        if let Err(err) = result {
+            std::fs::remove_dir_all(&staging).ok();
+            return Err(err);
+        }
+
+        // keep the current database until the restored one is in place
+        let backup = target.with_extension("bak");
+        if backup.exists() {
+            std::fs::remove_dir_all(&backup)?;
+        }
+        let has_backup = target.exists();
+        if has_backup {
+            if let Err(err) = std::fs::rename(target, &backup) {
+                std::fs::remove_dir_all(&staging).ok();
+                return Err(err.into());
+            }
+        }
+
+        if let Err(err) = std::fs::rename(&staging, target) {
+            if has_backup {
+                std::fs::rename(&backup, target).ok();
+            }
+            std::fs::remove_dir_all(&staging).ok();
+            return Err(err.into());
+        }
+
+        if has_backup {
+            if let Err(err) = std::fs::remove_dir_all(&backup) {
+                log_warn!(
+                    "Unable to remove database backup {}: {err}",
+                    backup.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn open_snapshot(path: &Path) -> Result<GzDecoder<BufReader<File>>> {
+    let file = File::open(path)?;
+    let mut decoder = GzDecoder::new(BufReader::with_capacity(BUFFER_SIZE, file));
+    let mut magic = [0u8; 16];
+    decoder
+        .read_exact(&mut magic)
+        .map_err(|_| Error::custom("Not a Kaspa NG database snapshot"))?;
+    if &magic != SNAPSHOT_MAGIC {
+        return Err(Error::custom("Not a Kaspa NG database snapshot"));
+    }
+    Ok(decoder)
+}
+
+/// Read the snapshot manifest following the magic header.
+pub fn read_manifest(decoder: &mut impl Read) -> Result<SnapshotManifest> {
+    let mut len = [0u8; 4];
+    decoder.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_MANIFEST_SIZE {
+        return Err(Error::custom(format!(
+            "Snapshot manifest is too large ({len} bytes)"
+        )));
+    }
+    let mut manifest = vec![0u8; len];
+    decoder.read_exact(&mut manifest)?;
+    Ok(serde_json::from_slice(&manifest)?)
+}
+
+pub fn read_snapshot_manifest(path: &Path) -> Result<SnapshotManifest> {
+    let mut decoder = open_snapshot(path)?;
+    read_manifest(&mut decoder)
+}
+
+pub fn verify_manifest(manifest: &SnapshotManifest, network: Network) -> Result<()> {
+    if manifest.format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(Error::custom(format!(
+            "Unsupported snapshot format version {} (expected {})",
+            manifest.format_version, SNAPSHOT_FORMAT_VERSION
+        )));
+    }
+
+    if manifest.network != network {
+        return Err(Error::custom(format!(
+            "Snapshot network {} does not match the selected network {}",
+            manifest.network.to_string().to_uppercase(),
+            network.to_string().to_uppercase()
+        )));
+    }
+
+    // file paths must be relative to the database folder
+    for file in manifest.files.iter() {
+        if file.path.is_empty()
+            || Path::new(&file.path)
+                .components()
+                .any(|component| !matches!(component, std::path::Component::Normal(_)))
+        {
+            return Err(Error::custom(format!(
+                "Invalid file path in snapshot: '{}'",
+                file.path
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -194,6 +194,59 @@ impl<'core> Status<'core> {
         }
     }
 
+    fn render_profile_selector(&mut self, ui: &mut Ui) {
+        use egui_phosphor::light::CHECK;
+
+        if self.settings().connection_profiles.is_empty() || self.core.module().modal() {
+            return;
+        }
+
+        let active = self
+            .settings()
+            .connection_profiles
+            .iter()
+            .find(|profile| profile.matches(&self.settings().node))
+            .map(|profile| profile.name.clone());
+
+        ui.separator();
+
+        let response = ui.add(
+            Label::new(RichText::new(
+                active.as_deref().unwrap_or(i18n("Profiles")).to_string(),
+            ))
+            .sense(Sense::click()),
+        );
+        let id = PopupPanel::id(ui, "connection_profile_selector_popup");
+        PopupPanel::new(
+            id,
+            |_ui| response,
+            |ui, close| {
+                set_menu_style(ui.style_mut());
+
+                let mut selected = None;
+                for profile in self.settings().connection_profiles.iter() {
+                    let name = if Some(&profile.name) == active.as_ref() {
+                        format!("{} {CHECK}", profile.name)
+                    } else {
+                        profile.name.clone()
+                    };
+
+                    if ui.button(name).on_hover_text(profile.describe()).clicked() {
+                        *close = true;
+                        selected = Some(profile.clone());
+                    }
+                }
+
+                if let Some(profile) = selected {
+                    self.core.apply_connection_profile(&profile);
+                }
+            },
+        )
+        .with_min_width(140.0)
+        .with_above_or_below(AboveOrBelow::Above)
+        .build(ui);
+    }
+
     fn render_network_selector(&mut self, ui: &mut Ui) {
         use egui_phosphor::light::CHECK;
 
@@ -265,11 +318,27 @@ impl<'core> Status<'core> {
                         match settings.node.node_kind {
                             KaspadNodeKind::Remote => match settings.node.connection_config_kind {
                                 NodeConnectionConfigKind::Custom => {
-                                    match KaspaRpcClient::parse_url(
-                                        settings.node.wrpc_url.clone(),
-                                        settings.node.wrpc_encoding,
-                                        settings.node.network.into(),
-                                    ) {
+                                    let (url, result) = match settings.node.rpc_kind {
+                                        #[cfg(not(target_arch = "wasm32"))]
+                                        RpcKind::Grpc => (
+                                            &settings.node.grpc_url,
+                                            crate::runtime::services::kaspa::grpc::parse_grpc_url(
+                                                &settings.node.grpc_url,
+                                                settings.node.network,
+                                            ),
+                                        ),
+                                        _ => (
+                                            &settings.node.wrpc_url,
+                                            KaspaRpcClient::parse_url(
+                                                settings.node.wrpc_url.clone(),
+                                                settings.node.wrpc_encoding,
+                                                settings.node.network.into(),
+                                            )
+                                            .map_err(Error::from),
+                                        ),
+                                    };
+
+                                    match result {
                                         Ok(url) => {
                                             ui.label(format!(
                                                 "{} {} ...",
@@ -282,7 +351,7 @@ impl<'core> Status<'core> {
                                                 RichText::new(format!(
                                                     "{} {}: {err}",
                                                     i18n("Error connecting to"),
-                                                    settings.node.wrpc_url
+                                                    url
                                                 ))
                                                 .color(theme_color().warning_color),
                                             );
@@ -366,6 +435,8 @@ impl<'core> Status<'core> {
                     }
                 }
 
+                self.render_profile_selector(ui);
+
                 if !self.device().single_pane() {
                     module.status_bar(self.core, ui);
                 }
@@ -401,6 +472,7 @@ impl<'core> Status<'core> {
                 }
                 ui.separator();
                 self.render_connection_selector(ui);
+                self.render_profile_selector(ui);
                 ui.separator();
                 self.render_network_selector(ui);
 
@@ -434,6 +506,7 @@ impl<'core> Status<'core> {
 
                         ui.separator();
                         self.render_connection_selector(ui);
+                        self.render_profile_selector(ui);
                         ui.separator();
                         self.render_network_selector(ui);
 
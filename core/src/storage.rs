@@ -1,5 +1,20 @@
 use crate::imports::*;
 
+/// Free disk space below which the user is warned that
+/// the node may run out of storage.
+pub const LOW_DISK_SPACE_THRESHOLD: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Name of the database folder inside of the network data folder.
+pub const DATABASE_FOLDER: &str = "datadir";
+
+/// Action pending user confirmation for a network data folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageFolderAction {
+    DeleteDatabase,
+    DeleteFolder,
+    Resync,
+}
+
 #[derive(PartialEq, Eq)]
 pub struct StorageFolder {
     pub path: PathBuf,
@@ -7,7 +22,42 @@ pub struct StorageFolder {
     pub name: String,
     pub folder_size: u64,
     pub folder_size_string: String,
-    pub confirm_deletion: bool,
+    pub confirm_action: Option<StorageFolderAction>,
+}
+
+impl StorageFolder {
+    pub fn database_path(&self) -> PathBuf {
+        self.path.join(DATABASE_FOLDER)
+    }
+}
+
+/// Free space of the disk hosting the storage root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    pub available: u64,
+    pub total: u64,
+}
+
+impl DiskSpace {
+    pub fn is_low(&self) -> bool {
+        self.available < LOW_DISK_SPACE_THRESHOLD
+    }
+}
+
+/// Snapshot operation requested from the storage settings panel.
+#[cfg(not(target_arch = "wasm32"))]
+enum SnapshotAction {
+    Export(PathBuf),
+    Restore(PathBuf),
+}
+
+/// State of the data directory relocation. Once the relocation
+/// completes, the state is cleared and [`Events::StorageRelocated`]
+/// is posted to the application.
+#[derive(Debug, Clone)]
+pub enum StorageRelocation {
+    Running { copied: u64, total: u64 },
+    Failed { error: String },
 }
 
 impl Ord for StorageFolder {
@@ -50,6 +100,13 @@ impl StorageUpdateOptions {
 pub struct Storage {
     pub folders: Arc<Mutex<Vec<StorageFolder>>>,
     pub storage_root: Arc<Mutex<Option<PathBuf>>>,
+    pub disk_space: Arc<Mutex<Option<DiskSpace>>>,
+    pub relocation: Arc<Mutex<Option<StorageRelocation>>>,
+    pub relocation_target: Arc<Mutex<String>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub snapshot: Arc<Mutex<Option<crate::snapshot::SnapshotState>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub pending_restore: Arc<Mutex<Option<(PathBuf, crate::snapshot::SnapshotManifest)>>>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -115,18 +172,55 @@ impl Storage {
                 }
             }
 
+            this.update_disk_space();
+
             runtime().request_repaint();
 
             Ok(())
         });
     }
 
+    fn update_disk_space(&self) {
+        use sysinfo::{DiskExt, System, SystemExt};
+
+        let storage_root = self.storage_root();
+        let storage_root = storage_root.canonicalize().unwrap_or(storage_root);
+
+        let mut system = System::new();
+        system.refresh_disks_list();
+        // select the disk with the longest mount point containing the storage root
+        let disk_space = system
+            .disks()
+            .iter()
+            .filter(|disk| storage_root.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map(|disk| DiskSpace {
+                available: disk.available_space(),
+                total: disk.total_space(),
+            });
+
+        *self.disk_space.lock().unwrap() = disk_space;
+    }
+
+    pub fn disk_space(&self) -> Option<DiskSpace> {
+        *self.disk_space.lock().unwrap()
+    }
+
+    pub fn relocation(&self) -> Option<StorageRelocation> {
+        self.relocation.lock().unwrap().clone()
+    }
+
+    pub fn is_relocating(&self) -> bool {
+        matches!(self.relocation(), Some(StorageRelocation::Running { .. }))
+    }
+
     fn update_folder_size(&self, network: Network, folder_size: u64, path: PathBuf) {
         use kaspa_metrics_core::data::as_data_size;
         let folder_size_string = as_data_size(folder_size as f64, true);
 
         let mut folders = self.folders.lock().unwrap();
         if let Some(folder) = folders.iter_mut().find(|folder| folder.network == network) {
+            folder.path = path;
             folder.folder_size = folder_size;
             folder.folder_size_string = folder_size_string;
         } else {
@@ -136,7 +230,7 @@ impl Storage {
                 network,
                 folder_size,
                 folder_size_string,
-                confirm_deletion: false,
+                confirm_action: None,
             });
 
             folders.sort();
@@ -180,6 +274,359 @@ impl Storage {
         });
     }
 
+    /// Remove the network database while preserving the logs.
+    pub fn remove_database(&self, network: Network) {
+        let this = self.clone();
+        spawn(async move {
+            if let Some(path) = this.folder(network) {
+                let path = path.join(DATABASE_FOLDER);
+                if path.exists() {
+                    if let Err(e) = std::fs::remove_dir_all(&path) {
+                        runtime().error(format!("Error removing database folder: {:?}", e));
+                    } else {
+                        runtime().notify(UserNotification::success(format!(
+                            "{} {}",
+                            network.to_string().to_uppercase(),
+                            i18n("database has been removed")
+                        )));
+                    }
+                    this.update(None);
+                } else {
+                    runtime().error(format!("Database not found: {}", path.display()));
+                }
+            }
+            Ok(())
+        });
+    }
+
+    /// Move all network data folders into the `target` directory.
+    /// The node must not be running while the data is relocated.
+    pub fn relocate(&self, target: PathBuf) -> Result<()> {
+        if self.is_relocating() {
+            return Err(Error::custom("Data relocation is already in progress"));
+        }
+        if self.is_snapshot_running() {
+            return Err(Error::custom("Another storage operation is in progress"));
+        }
+
+        let source = self.storage_root();
+        if target.starts_with(&source) || source.starts_with(&target) {
+            return Err(Error::custom(
+                "The target folder must not overlap with the current data folder",
+            ));
+        }
+
+        let folders = self
+            .folders
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|folder| (folder.path.clone(), folder.folder_size))
+            .collect::<Vec<_>>();
+
+        for (path, _) in folders.iter() {
+            if let Some(name) = path.file_name() {
+                if target.join(name).exists() {
+                    return Err(Error::custom(format!(
+                        "Target folder already contains '{}'",
+                        name.to_string_lossy()
+                    )));
+                }
+            }
+        }
+
+        let total = folders.iter().map(|(_, size)| *size).sum::<u64>();
+        self.relocation
+            .lock()
+            .unwrap()
+            .replace(StorageRelocation::Running { copied: 0, total });
+
+        let this = self.clone();
+        std::thread::Builder::new()
+            .name("storage-relocation".to_string())
+            .spawn(move || {
+                match this.relocate_folders(&target, &folders, total) {
+                    Ok(()) => {
+                        this.relocation.lock().unwrap().take();
+                        runtime()
+                            .try_send(Events::StorageRelocated { target })
+                            .unwrap_or_else(|err| log_error!("{err}"));
+                    }
+                    Err(err) => {
+                        this.relocation
+                            .lock()
+                            .unwrap()
+                            .replace(StorageRelocation::Failed {
+                                error: err.to_string(),
+                            });
+                    }
+                }
+                runtime().request_repaint();
+            })
+            .map_err(|err| Error::custom(format!("Unable to start data relocation: {err}")))?;
+
+        Ok(())
+    }
+
+    /// Move `folders` into `target`. Folders on a different filesystem are
+    /// copied and their sources removed only once all folders have been
+    /// moved, so that a failure can restore the original data folder.
+    fn relocate_folders(
+        &self,
+        target: &Path,
+        folders: &[(PathBuf, u64)],
+        total: u64,
+    ) -> Result<()> {
+        std::fs::create_dir_all(target)?;
+
+        let mut copied = 0;
+        // moved folders and whether they were copied (rather than renamed)
+        let mut moved: Vec<(&Path, PathBuf, bool)> = vec![];
+        for (source, size) in folders.iter() {
+            let Some(name) = source.file_name() else {
+                continue;
+            };
+            let destination = target.join(name);
+
+            // fast path if source and destination share the filesystem
+            if std::fs::rename(source, &destination).is_ok() {
+                copied += size;
+                self.update_relocation_progress(copied, total);
+                moved.push((source, destination, false));
+                continue;
+            }
+
+            if let Err(err) = self.copy_folder(source, &destination, &mut copied, total) {
+                if let Err(err) = std::fs::remove_dir_all(&destination) {
+                    log_warn!("Unable to remove {}: {err}", destination.display());
+                }
+                Self::restore_folders(&moved);
+                return Err(err);
+            }
+            moved.push((source, destination, true));
+        }
+
+        for (source, _, _) in moved.iter().filter(|(_, _, is_copy)| *is_copy) {
+            if let Err(err) = std::fs::remove_dir_all(source) {
+                log_warn!("Unable to remove {}: {err}", source.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn copy_folder(
+        &self,
+        source: &Path,
+        destination: &Path,
+        copied: &mut u64,
+        total: u64,
+    ) -> Result<()> {
+        for entry in walkdir::WalkDir::new(source) {
+            let entry = entry.map_err(|err| Error::custom(err.to_string()))?;
+            let relative = entry
+                .path()
+                .strip_prefix(source)
+                .map_err(|err| Error::custom(err.to_string()))?;
+            let path = destination.join(relative);
+            if entry.file_type().is_dir() {
+                std::fs::create_dir_all(&path)?;
+            } else {
+                *copied += std::fs::copy(entry.path(), &path)?;
+                self.update_relocation_progress(*copied, total);
+            }
+        }
+        Ok(())
+    }
+
+    /// Undo a partial relocation: renamed folders are moved back
+    /// and copies are removed (their sources are still in place).
+    fn restore_folders(moved: &[(&Path, PathBuf, bool)]) {
+        for (source, destination, is_copy) in moved.iter().rev() {
+            let result = if *is_copy {
+                std::fs::remove_dir_all(destination)
+            } else {
+                std::fs::rename(destination, source)
+            };
+            if let Err(err) = result {
+                log_error!(
+                    "Unable to restore {} from {}: {err}",
+                    source.display(),
+                    destination.display()
+                );
+            }
+        }
+    }
+
+    fn update_relocation_progress(&self, copied: u64, total: u64) {
+        self.relocation
+            .lock()
+            .unwrap()
+            .replace(StorageRelocation::Running { copied, total });
+        runtime().request_repaint();
+    }
+
+    fn render_snapshot_settings(&self, core: &mut Core, ui: &mut Ui) -> Option<SnapshotAction> {
+        use crate::snapshot::*;
+        use kaspa_metrics_core::data::as_data_size;
+
+        let network = core.settings.node.network;
+        let network_name = network.to_string().to_uppercase();
+
+        match self.snapshot_state() {
+            Some(SnapshotState::StoppingNode { operation }) => {
+                ui.label(operation.to_string());
+                ui.horizontal(|ui| {
+                    ui.add(egui::Spinner::new());
+                    ui.label(i18n("Stopping the node..."));
+                });
+            }
+            Some(SnapshotState::Running {
+                operation,
+                stage,
+                processed,
+                total,
+            }) => {
+                ui.label(format!("{operation}: {stage}"));
+                let progress = if total > 0 {
+                    processed as f32 / total as f32
+                } else {
+                    0.0
+                };
+                ui.add(ProgressBar::new(progress).show_percentage());
+                ui.label(format!(
+                    "{} / {}",
+                    as_data_size(processed as f64, true),
+                    as_data_size(total as f64, true)
+                ));
+            }
+            Some(SnapshotState::Completed { operation, path }) => {
+                self.clear_snapshot_state();
+                let message = match operation {
+                    SnapshotOperation::Export => {
+                        format!("{} {}", i18n("Snapshot saved to"), path.display())
+                    }
+                    SnapshotOperation::Restore => {
+                        format!("{network_name} {}", i18n("database has been restored"))
+                    }
+                };
+                runtime().notify(UserNotification::success(message));
+            }
+            Some(SnapshotState::Failed { operation, error }) => {
+                ui.colored_label(
+                    theme_color().error_color,
+                    format!("{operation} {}: {error}", i18n("failed")),
+                );
+                if ui.medium_button(i18n("Dismiss")).clicked() {
+                    self.clear_snapshot_state();
+                }
+            }
+            None => {
+                let mut pending_restore = self.pending_restore.lock().unwrap();
+                if let Some((path, manifest)) = pending_restore.as_ref() {
+                    let created = chrono::DateTime::<chrono::Local>::from(
+                        std::time::UNIX_EPOCH + Duration::from_millis(manifest.created),
+                    );
+                    ui.label(format!("{} {}", i18n("Snapshot:"), path.display()));
+                    ui.label(format!(
+                        "{} {} · {} · kaspad {}",
+                        i18n("Created"),
+                        created.format("%Y-%m-%d %H:%M:%S"),
+                        as_data_size(manifest.total_size() as f64, true),
+                        manifest.kaspad_version
+                    ));
+                    ui.label(i18n(
+                        "The node will be stopped and its database replaced with the snapshot",
+                    ));
+                    ui.label("");
+                    ui.colored_label(theme_color().alert_color, i18n("Please Confirm Restore"));
+                    if let Some(response) = ui.confirm_medium_apply_cancel(Align::Min) {
+                        let (path, _) = pending_restore.take().unwrap();
+                        if matches!(response, Confirm::Ack) {
+                            return Some(SnapshotAction::Restore(path));
+                        }
+                    }
+                    return None;
+                }
+
+                ui.label(format!(
+                    "{} {network_name} {}",
+                    i18n("Export or restore the"),
+                    i18n("node database. The node is stopped during the operation.")
+                ));
+
+                // `folders` is locked by the caller
+                let database_exists = self.network_database_folder(network).exists();
+
+                let mut action = None;
+                ui.horizontal_wrapped(|ui| {
+                    if ui
+                        .medium_button_enabled(
+                            database_exists && !self.is_relocating(),
+                            i18n("Export Snapshot"),
+                        )
+                        .clicked()
+                    {
+                        let file_name = format!(
+                            "kaspa-{network}-{}.{SNAPSHOT_FILE_EXTENSION}",
+                            chrono::Local::now().format("%Y%m%d-%H%M%S")
+                        );
+                        if let Some(target) = rfd::FileDialog::new()
+                            .set_file_name(file_name)
+                            .add_filter(i18n("Kaspa Snapshot"), &[SNAPSHOT_FILE_EXTENSION])
+                            .save_file()
+                        {
+                            action = Some(SnapshotAction::Export(target));
+                        }
+                    }
+
+                    if ui
+                        .medium_button_enabled(!self.is_relocating(), i18n("Restore Snapshot"))
+                        .clicked()
+                    {
+                        if let Some(source) = rfd::FileDialog::new()
+                            .add_filter(i18n("Kaspa Snapshot"), &[SNAPSHOT_FILE_EXTENSION])
+                            .pick_file()
+                        {
+                            match read_snapshot_manifest(&source).and_then(|manifest| {
+                                verify_manifest(&manifest, network).map(|_| manifest)
+                            }) {
+                                Ok(manifest) => {
+                                    pending_restore.replace((source, manifest));
+                                }
+                                Err(err) => {
+                                    runtime().error(format!("{err}"));
+                                }
+                            }
+                        }
+                    }
+                });
+
+                return action;
+            }
+        }
+
+        None
+    }
+
+    fn render_disk_space_warning(&self, ui: &mut Ui) {
+        use kaspa_metrics_core::data::as_data_size;
+
+        if let Some(disk_space) = self.disk_space() {
+            if disk_space.is_low() {
+                ui.colored_label(
+                    theme_color().warning_color,
+                    format!(
+                        "{} {} {}",
+                        egui_phosphor::light::WARNING,
+                        i18n("Low disk space:"),
+                        as_data_size(disk_space.available as f64, true)
+                    ),
+                );
+            }
+        }
+    }
+
     pub fn render(&self, ui: &mut Ui) {
         let folders = self.folders.lock().unwrap();
         if !folders.is_empty() {
@@ -199,6 +646,7 @@ impl Storage {
                                     network.to_string().to_uppercase()
                                 ));
                             }
+                            self.render_disk_space_warning(ui);
                         });
                     });
             });
@@ -208,34 +656,64 @@ impl Storage {
     pub fn clear_settings(&self) {
         let mut folders = self.folders.lock().unwrap();
         for folder in folders.iter_mut() {
-            folder.confirm_deletion = false;
+            folder.confirm_action = None;
         }
     }
 
+    /// Render the node data management panel.
     pub fn render_settings(&self, core: &mut Core, ui: &mut Ui) {
+        use kaspa_metrics_core::data::as_data_size;
+
+        let mut relocate = None;
+        let mut snapshot_action = None;
+
+        let node_is_local = core.settings.node.node_kind.is_local();
+        // data folders must not be modified during a relocation or a snapshot export or restore
+        let is_busy = self.is_relocating() || self.is_snapshot_running();
+
+        // the panel is shown even if there are no data folders
+        // so that a database snapshot can be restored
         let mut folders = self.folders.lock().unwrap();
-        if !folders.is_empty() {
+        {
             ui.vertical_centered(|ui| {
                 CollapsingHeader::new(i18n("Storage"))
                 .default_open(false)
                 .show(ui, |ui| {
                     ui.vertical(|ui| {
+
+                        if let Some(disk_space) = self.disk_space() {
+                            ui.label(format!("{} {} / {}",
+                                i18n("Free disk space:"),
+                                as_data_size(disk_space.available as f64, true),
+                                as_data_size(disk_space.total as f64, true),
+                            ));
+                        }
+                        self.render_disk_space_warning(ui);
+
                         for folder in folders.iter_mut() {
-                            let StorageFolder { network, folder_size_string, path, confirm_deletion, .. } = folder;
+                            let database_exists = folder.database_path().exists();
+                            let StorageFolder { network, folder_size_string, path, confirm_action, .. } = folder;
 
                             CollapsingHeader::new(format!("{}: {folder_size_string}", network.to_string().to_uppercase()))
                             .default_open(false)
                             .show(ui, |ui| {
-                                let is_running = core.settings.node.network == *network && core.settings.node.node_kind.is_local();
+                                let is_running = core.settings.node.network == *network && node_is_local;
+                                let enabled = confirm_action.is_none() && !is_busy;
 
-                                ui.horizontal(|ui|{
+                                ui.horizontal_wrapped(|ui|{
                                     if ui.medium_button(i18n("Open Data Folder")).clicked() {
-                                        if let Err(err) = open::that(path) {
+                                        if let Err(err) = open::that(&path) {
                                             runtime().error(format!("Error opening folder: {:?}", err));
                                         }
                                     }
-                                    if ui.medium_button_enabled(!is_running && !*confirm_deletion, i18n("Delete Data Folder")).clicked() {
-                                        *confirm_deletion = true;
+                                    if ui.medium_button_enabled(enabled && !is_running && database_exists, i18n("Delete Database")).clicked() {
+                                        *confirm_action = Some(StorageFolderAction::DeleteDatabase);
+                                    }
+                                    if ui.medium_button_enabled(enabled && !is_running, i18n("Delete Data Folder")).clicked() {
+                                        *confirm_action = Some(StorageFolderAction::DeleteFolder);
+                                    }
+                                    if ui.medium_button_enabled(enabled && is_running, i18n("Resync")).clicked() {
+                                        *confirm_action = Some(StorageFolderAction::Resync);
                                     }
                                 });
 
@@ -244,19 +722,38 @@ impl Storage {
                                     ui.label(i18n("Please set node to 'Disabled' to delete the data folder"));
                                 }
 
-                                if *confirm_deletion {
+                                if let Some(action) = *confirm_action {
                                     ui.add_sized(vec2(260.,4.), Separator::default());
-                                    ui.label(i18n("This action will erase Kaspa database and logs"));
+                                    match action {
+                                        StorageFolderAction::DeleteDatabase => {
+                                            ui.label(i18n("This action will erase the Kaspa database; the node will resync on the next start"));
+                                        }
+                                        StorageFolderAction::DeleteFolder => {
+                                            ui.label(i18n("This action will erase Kaspa database and logs"));
+                                        }
+                                        StorageFolderAction::Resync => {
+                                            ui.label(i18n("The node will restart, erase its database and resync from the network"));
+                                        }
+                                    }
                                     ui.label("");
-                                    ui.colored_label(theme_color().alert_color, i18n("Please Confirm Deletion"));
+                                    let caption = if action == StorageFolderAction::Resync { i18n("Please Confirm Resync") } else { i18n("Please Confirm Deletion") };
+                                    ui.colored_label(theme_color().alert_color, caption);
                                     if let Some(response) = ui.confirm_medium_apply_cancel(Align::Min) {
                                         match response {
                                             Confirm::Ack => {
-                                                *confirm_deletion = false;
-                                                self.remove(*network);
+                                                *confirm_action = None;
+                                                match action {
+                                                    StorageFolderAction::DeleteDatabase => self.remove_database(*network),
+                                                    StorageFolderAction::DeleteFolder => self.remove(*network),
+                                                    StorageFolderAction::Resync => {
+                                                        if let Err(err) = runtime().kaspa_service().resync(&core.settings.node) {
+                                                            runtime().error(format!("Unable to resync: {err}"));
+                                                        }
+                                                    }
+                                                }
                                             },
                                             Confirm::Nack => {
-                                                *confirm_deletion = false;
+                                                *confirm_action = None;
                                             }
                                         }
                                     }
@@ -264,9 +761,73 @@ impl Storage {
                                 }
                             });
                         }
+
+                        CollapsingHeader::new(i18n("Move Data Folder"))
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                ui.label(format!("{} {}", i18n("Current location:"), self.storage_root().display()));
+
+                                match self.relocation() {
+                                    Some(StorageRelocation::Running { copied, total }) => {
+                                        let progress = if total > 0 { copied as f32 / total as f32 } else { 0.0 };
+                                        ui.add(ProgressBar::new(progress).show_percentage());
+                                        ui.label(format!("{} / {}", as_data_size(copied as f64, true), as_data_size(total as f64, true)));
+                                    }
+                                    Some(StorageRelocation::Failed { error }) => {
+                                        ui.colored_label(theme_color().error_color, format!("{} {}", i18n("Unable to move data folder:"), error));
+                                        if ui.medium_button(i18n("Dismiss")).clicked() {
+                                            self.relocation.lock().unwrap().take();
+                                        }
+                                    }
+                                    None => {
+                                        let mut target = self.relocation_target.lock().unwrap();
+                                        ui.horizontal(|ui| {
+                                            ui.label(i18n("New location:"));
+                                            ui.add(TextEdit::singleline(&mut *target));
+                                        });
+
+                                        if node_is_local {
+                                            ui.label(i18n("Please set node to 'Disabled' to move the data folder"));
+                                        }
+
+                                        let target_path = target.trim();
+                                        if ui.medium_button_enabled(!node_is_local && !target_path.is_empty() && !is_busy, i18n("Move Data Folder")).clicked() {
+                                            relocate = Some(PathBuf::from(target_path));
+                                        }
+                                    }
+                                }
+                            });
+
+                        CollapsingHeader::new(i18n("Database Snapshots"))
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                snapshot_action = self.render_snapshot_settings(core, ui);
+                            });
                     });
                 });
             });
         }
+        drop(folders);
+
+        if let Some(target) = relocate {
+            if let Err(err) = self.relocate(target) {
+                runtime().error(format!("{err}"));
+            }
+        }
+
+        if let Some(action) = snapshot_action {
+            let network = core.settings.node.network;
+            let result = match action {
+                SnapshotAction::Export(target) => {
+                    self.export_snapshot(&core.settings.node, network, target)
+                }
+                SnapshotAction::Restore(source) => {
+                    self.restore_snapshot(&core.settings.node, network, source)
+                }
+            };
+            if let Err(err) = result {
+                runtime().error(format!("{err}"));
+            }
+        }
     }
 }
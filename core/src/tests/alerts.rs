@@ -0,0 +1,139 @@
+use crate::alerts::*;
+use crate::imports::*;
+use kaspa_metrics_core::{Metric, MetricsSnapshot};
+
+fn settings(rules: Vec<AlertRule>) -> AlertSettings {
+    AlertSettings {
+        rules,
+        ..Default::default()
+    }
+}
+
+fn snapshot(seconds: f64, cpu_usage: f64, daa_score: f64) -> MetricsSnapshot {
+    MetricsSnapshot {
+        unixtime_millis: 1_700_006_400_000.0 + seconds * 1000.0,
+        node_cpu_usage: cpu_usage,
+        network_virtual_daa_score: daa_score,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_metric_alert_duration_and_rearm() {
+    let settings = settings(vec![AlertRule::MetricAbove {
+        metric: Metric::NodeCpuUsage,
+        threshold: 50.0,
+        duration: 10,
+    }]);
+    let mut monitor = MetricAlertMonitor::default();
+
+    // the condition must hold for the rule duration
+    assert!(monitor
+        .evaluate(&settings, &snapshot(0.0, 80.0, 0.0))
+        .is_empty());
+    assert!(monitor
+        .evaluate(&settings, &snapshot(5.0, 80.0, 0.0))
+        .is_empty());
+    assert_eq!(
+        monitor
+            .evaluate(&settings, &snapshot(10.0, 80.0, 0.0))
+            .len(),
+        1
+    );
+
+    // the alert triggers only once while the condition holds
+    assert!(monitor
+        .evaluate(&settings, &snapshot(11.0, 80.0, 0.0))
+        .is_empty());
+
+    // the rule re-arms once the condition clears
+    assert!(monitor
+        .evaluate(&settings, &snapshot(12.0, 20.0, 0.0))
+        .is_empty());
+    assert!(monitor
+        .evaluate(&settings, &snapshot(13.0, 80.0, 0.0))
+        .is_empty());
+    assert!(monitor
+        .evaluate(&settings, &snapshot(22.0, 80.0, 0.0))
+        .is_empty());
+    assert_eq!(
+        monitor
+            .evaluate(&settings, &snapshot(23.0, 80.0, 0.0))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_metric_alert_sync_stalled() {
+    let settings = settings(vec![AlertRule::SyncStalled { duration: 60 }]);
+    let mut monitor = MetricAlertMonitor::default();
+
+    assert!(monitor
+        .evaluate(&settings, &snapshot(0.0, 0.0, 100.0))
+        .is_empty());
+    assert!(monitor
+        .evaluate(&settings, &snapshot(30.0, 0.0, 100.0))
+        .is_empty());
+    assert_eq!(
+        monitor
+            .evaluate(&settings, &snapshot(60.0, 0.0, 100.0))
+            .len(),
+        1
+    );
+    assert!(monitor
+        .evaluate(&settings, &snapshot(90.0, 0.0, 100.0))
+        .is_empty());
+
+    // the stall is measured from the last DAA score change
+    assert!(monitor
+        .evaluate(&settings, &snapshot(91.0, 0.0, 101.0))
+        .is_empty());
+    assert!(monitor
+        .evaluate(&settings, &snapshot(150.0, 0.0, 101.0))
+        .is_empty());
+    assert_eq!(
+        monitor
+            .evaluate(&settings, &snapshot(151.0, 0.0, 101.0))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_metric_alert_settings_changes() {
+    let rule = AlertRule::MetricBelow {
+        metric: Metric::NodeCpuUsage,
+        threshold: 10.0,
+        duration: 10,
+    };
+    let mut monitor = MetricAlertMonitor::default();
+
+    // rule state is retained when other rules are added
+    let active = settings(vec![rule.clone()]);
+    assert!(monitor
+        .evaluate(&active, &snapshot(0.0, 5.0, 0.0))
+        .is_empty());
+    let extended = settings(vec![
+        AlertRule::SyncStalled { duration: 3600 },
+        rule.clone(),
+    ]);
+    assert_eq!(
+        monitor.evaluate(&extended, &snapshot(10.0, 5.0, 0.0)).len(),
+        1
+    );
+
+    // disabling alerts discards the rule state
+    let mut disabled = extended.clone();
+    disabled.enable = false;
+    assert!(monitor
+        .evaluate(&disabled, &snapshot(20.0, 5.0, 0.0))
+        .is_empty());
+    assert!(monitor
+        .evaluate(&active, &snapshot(25.0, 5.0, 0.0))
+        .is_empty());
+    assert_eq!(
+        monitor.evaluate(&active, &snapshot(35.0, 5.0, 0.0)).len(),
+        1
+    );
+}
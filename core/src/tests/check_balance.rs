@@ -0,0 +1,86 @@
+use crate::imports::*;
+use crate::modules::tools::check_balance::{export_csv, parse_addresses, AddressBalance};
+use kaspa_addresses::{Prefix, Version};
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId, TransactionOutpoint, UtxoEntry};
+use kaspa_rpc_core::RpcUtxosByAddressesEntry;
+
+fn address(prefix: Prefix, byte: u8) -> Address {
+    Address::new(prefix, Version::PubKey, &[byte; 32])
+}
+
+fn utxo(address: &Address, index: u32, amount: u64) -> RpcUtxosByAddressesEntry {
+    RpcUtxosByAddressesEntry {
+        address: Some(address.clone()),
+        outpoint: TransactionOutpoint::new(TransactionId::from_u64_word(1), index),
+        utxo_entry: UtxoEntry::new(amount, ScriptPublicKey::default(), 0, false),
+    }
+}
+
+#[test]
+fn test_parse_addresses() {
+    let first = address(Prefix::Mainnet, 1);
+    let second = address(Prefix::Mainnet, 2);
+    let text = format!("{first}, {second};\n\"{first}\"\t'{second}'\n\n");
+
+    let (addresses, invalid) = parse_addresses(&text, NetworkType::Mainnet);
+    assert_eq!(addresses, vec![first, second]);
+    assert!(invalid.is_empty());
+
+    let (addresses, invalid) = parse_addresses(" ,;\n", NetworkType::Mainnet);
+    assert!(addresses.is_empty());
+    assert!(invalid.is_empty());
+}
+
+#[test]
+fn test_parse_addresses_invalid_entries() {
+    let mainnet = address(Prefix::Mainnet, 1);
+    let testnet = address(Prefix::Testnet, 1);
+    let text = format!("{mainnet} kaspa:invalid {testnet}");
+
+    let (addresses, invalid) = parse_addresses(&text, NetworkType::Mainnet);
+    assert_eq!(addresses, vec![mainnet.clone()]);
+    assert_eq!(invalid.len(), 2);
+    assert_eq!(invalid[0], "kaspa:invalid");
+    assert!(invalid[1].starts_with(&format!("{testnet} (")));
+
+    // the same text parsed for testnet only accepts the testnet address
+    let (addresses, invalid) = parse_addresses(&text, NetworkType::Testnet);
+    assert_eq!(addresses, vec![testnet]);
+    assert_eq!(invalid.len(), 2);
+    assert!(invalid[1].starts_with(&format!("{mainnet} (")));
+}
+
+#[test]
+fn test_check_balance_export_csv() {
+    let funded = address(Prefix::Mainnet, 1);
+    let failed = address(Prefix::Mainnet, 2);
+    let results = vec![
+        AddressBalance {
+            address: funded.clone(),
+            balance: 150_000_001,
+            utxos: vec![utxo(&funded, 0, 100_000_000), utxo(&funded, 1, 50_000_001)],
+            error: None,
+        },
+        AddressBalance {
+            address: failed.clone(),
+            balance: 0,
+            utxos: vec![],
+            error: Some("RPC \"timeout\"".to_string()),
+        },
+    ];
+
+    let target =
+        std::env::temp_dir().join(format!("kaspa-ng-check-balance-{}.csv", std::process::id()));
+    export_csv(&results, &target).unwrap();
+    let csv = std::fs::read_to_string(&target).unwrap();
+    std::fs::remove_file(&target).unwrap();
+
+    assert_eq!(
+        csv,
+        format!(
+            "address,balance,utxo_count,largest_utxo,error\n\
+             {funded},1.50000001,2,1.00000000,\n\
+             {failed},0.00000000,0,,\"RPC \"\"timeout\"\"\"\n"
+        )
+    );
+}
@@ -0,0 +1,83 @@
+use crate::imports::*;
+
+#[test]
+fn test_kaspad_config_default_is_valid() {
+    assert!(KaspadConfig::default().validate().is_ok());
+}
+
+#[test]
+fn test_kaspad_config_peer_limits() {
+    let config = KaspadConfig {
+        outbound_target: KaspadConfig::MAX_PEERS,
+        inbound_limit: KaspadConfig::MAX_PEERS,
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+
+    let config = KaspadConfig {
+        outbound_target: KaspadConfig::MAX_PEERS + 1,
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+
+    let config = KaspadConfig {
+        inbound_limit: KaspadConfig::MAX_PEERS + 1,
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_kaspad_config_rpc_client_limits() {
+    for (rpc_max_clients, valid) in [
+        (0, false),
+        (1, true),
+        (KaspadConfig::MAX_RPC_CLIENTS, true),
+        (KaspadConfig::MAX_RPC_CLIENTS + 1, false),
+    ] {
+        let config = KaspadConfig {
+            rpc_max_clients,
+            ..Default::default()
+        };
+        assert_eq!(config.validate().is_ok(), valid, "{rpc_max_clients}");
+    }
+}
+
+#[test]
+fn test_kaspad_config_peer_addresses() {
+    let config = KaspadConfig {
+        connect_peers: vec!["192.168.1.1:16111".to_string()],
+        add_peers: vec!["10.0.0.1".to_string()],
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+
+    let config = KaspadConfig {
+        add_peers: vec!["not an address".to_string()],
+        ..Default::default()
+    };
+    let err = config.validate().unwrap_err();
+    assert!(err.contains("not an address"));
+
+    let config = KaspadConfig {
+        connect_peers: vec!["192.168.1.1:16111:1".to_string()],
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_kaspad_config_connect_peers_require_outbound_target() {
+    let config = KaspadConfig {
+        outbound_target: 0,
+        connect_peers: vec!["192.168.1.1:16111".to_string()],
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+
+    let config = KaspadConfig {
+        outbound_target: 0,
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+}
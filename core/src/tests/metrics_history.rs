@@ -0,0 +1,150 @@
+use crate::imports::*;
+use crate::runtime::services::metrics_monitor::history::{
+    read_segment, segments, MetricsTier, TierWriter,
+};
+use crate::runtime::services::metrics_monitor::MetricsHistoryData;
+use kaspa_metrics_core::Metric;
+
+// 2023-11-15 00:00:00 UTC, aligned to the segment size of every tier
+const BASE: u64 = 1_700_006_400;
+
+fn folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!(
+        "kaspa-ng-metrics-history-{}-{name}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&folder);
+    folder
+}
+
+fn values(value: f64) -> Vec<f64> {
+    vec![value; Metric::list().len()]
+}
+
+fn read(folder: &Path, tier: MetricsTier, from: u64) -> MetricsHistoryData {
+    let mut data = MetricsHistoryData::default();
+    let mut segments = segments(&folder.join(tier.as_str())).unwrap();
+    segments.sort_by_key(|(segment_start, _)| *segment_start);
+    for (_, path) in segments {
+        read_segment(&path, from, &mut data).unwrap();
+    }
+    data
+}
+
+fn points(data: &MetricsHistoryData) -> Vec<(f64, f64)> {
+    let metric = Metric::list()[0];
+    data.get(&metric)
+        .unwrap()
+        .iter()
+        .map(|point| (point.x, point.y))
+        .collect()
+}
+
+#[test]
+fn test_metrics_history_round_trip() {
+    let folder = folder("round-trip");
+    let mut writer = TierWriter::try_new(MetricsTier::Second, &folder).unwrap();
+    for (offset, value) in [(0, 1.0), (1, 2.0), (2, 3.0)] {
+        writer.ingest(BASE + offset, &values(value)).unwrap();
+    }
+    writer.flush().unwrap();
+
+    let data = read(&folder, MetricsTier::Second, 0);
+    assert_eq!(data.len(), Metric::list().len());
+    assert_eq!(
+        points(&data),
+        vec![
+            (BASE as f64 * 1000.0, 1.0),
+            ((BASE + 1) as f64 * 1000.0, 2.0),
+            ((BASE + 2) as f64 * 1000.0, 3.0),
+        ]
+    );
+
+    // samples recorded before `from` are skipped
+    let data = read(&folder, MetricsTier::Second, BASE + 1);
+    assert_eq!(points(&data).len(), 2);
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn test_metrics_history_tier_bucketing() {
+    let folder = folder("bucketing");
+    let mut writer = TierWriter::try_new(MetricsTier::Minute, &folder).unwrap();
+    writer.ingest(BASE, &values(1.0)).unwrap();
+    writer.ingest(BASE + 30, &values(3.0)).unwrap();
+    writer.ingest(BASE + 59, &values(5.0)).unwrap();
+    writer.ingest(BASE + 61, &values(7.0)).unwrap();
+    // a new segment is started once the bucket crosses the segment boundary
+    writer
+        .ingest(BASE + MetricsTier::Minute.segment(), &values(9.0))
+        .unwrap();
+    writer.flush().unwrap();
+
+    assert_eq!(MetricsTier::Minute.resolution(), 60);
+    let segments = segments(&folder.join(MetricsTier::Minute.as_str())).unwrap();
+    assert_eq!(segments.len(), 2);
+
+    let data = read(&folder, MetricsTier::Minute, 0);
+    assert_eq!(
+        points(&data),
+        vec![
+            (BASE as f64 * 1000.0, 3.0),
+            ((BASE + 60) as f64 * 1000.0, 7.0),
+            ((BASE + MetricsTier::Minute.segment()) as f64 * 1000.0, 9.0),
+        ]
+    );
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn test_metrics_history_reopen_merges_flushed_bucket() {
+    let folder = folder("reopen");
+    let mut writer = TierWriter::try_new(MetricsTier::Minute, &folder).unwrap();
+    writer.ingest(BASE, &values(1.0)).unwrap();
+    writer.flush().unwrap();
+    drop(writer);
+
+    // the remainder of the bucket is recorded after reopening the history
+    // the halves hold a different number of samples, so the merged
+    // bucket is weighted: (1 * 1.0 + 3 * 3.0) / 4 = 2.5
+    let mut writer = TierWriter::try_new(MetricsTier::Minute, &folder).unwrap();
+    writer.ingest(BASE + 10, &values(2.0)).unwrap();
+    writer.ingest(BASE + 20, &values(3.0)).unwrap();
+    writer.ingest(BASE + 30, &values(4.0)).unwrap();
+    writer.ingest(BASE + 60, &values(5.0)).unwrap();
+    writer.flush().unwrap();
+
+    let data = read(&folder, MetricsTier::Minute, 0);
+    assert_eq!(
+        points(&data),
+        vec![
+            (BASE as f64 * 1000.0, 2.5),
+            ((BASE + 60) as f64 * 1000.0, 5.0),
+        ]
+    );
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn test_metrics_history_replaces_segment_of_other_format() {
+    let folder = folder("format");
+    let tier_folder = folder.join(MetricsTier::Minute.as_str());
+    std::fs::create_dir_all(&tier_folder).unwrap();
+    // header of a previous format version without any metrics
+    let mut header = b"KNGM".to_vec();
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&0u16.to_le_bytes());
+    std::fs::write(tier_folder.join(format!("{BASE}.bin")), header).unwrap();
+
+    let mut writer = TierWriter::try_new(MetricsTier::Minute, &folder).unwrap();
+    writer.ingest(BASE, &values(1.0)).unwrap();
+    writer.flush().unwrap();
+
+    let data = read(&folder, MetricsTier::Minute, 0);
+    assert_eq!(points(&data), vec![(BASE as f64 * 1000.0, 1.0)]);
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
@@ -1 +1,15 @@
+mod alerts;
+#[cfg(not(target_arch = "wasm32"))]
+mod check_balance;
+mod kaspad_config;
+#[cfg(not(target_arch = "wasm32"))]
+mod metrics_history;
+#[cfg(not(target_arch = "wasm32"))]
+mod node_logs;
+#[cfg(not(target_arch = "wasm32"))]
+mod prometheus;
+mod server_health;
+mod servers;
+#[cfg(not(target_arch = "wasm32"))]
+mod snapshot;
 mod transactions;
@@ -0,0 +1,86 @@
+use crate::imports::*;
+use crate::runtime::services::kaspa::logs::LogFile;
+
+// every test line ("line 00") is 8 bytes long including the newline
+const LINE_SIZE: u64 = 8;
+
+fn folder(name: &str) -> PathBuf {
+    let folder =
+        std::env::temp_dir().join(format!("kaspa-ng-node-logs-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&folder);
+    folder
+}
+
+fn read(folder: &Path, file: &str) -> Option<String> {
+    std::fs::read_to_string(folder.join(file)).ok()
+}
+
+fn lines(range: std::ops::Range<usize>) -> String {
+    range.map(|index| format!("line {index:02}\n")).collect()
+}
+
+#[test]
+fn test_log_file_rotation() {
+    let folder = folder("rotation");
+    let mut log_file = LogFile::try_new_with_folder(folder.clone(), LINE_SIZE * 4).unwrap();
+    for index in 0..10 {
+        log_file.write(&format!("line {index:02}")).unwrap();
+    }
+    log_file.flush().unwrap();
+
+    assert_eq!(read(&folder, "kaspad.log").unwrap(), lines(8..10));
+    assert_eq!(read(&folder, "kaspad.1.log").unwrap(), lines(4..8));
+    assert_eq!(read(&folder, "kaspad.2.log").unwrap(), lines(0..4));
+    assert!(read(&folder, "kaspad.3.log").is_none());
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn test_log_file_rotation_history_limit() {
+    let folder = folder("history");
+    let mut log_file = LogFile::try_new_with_folder(folder.clone(), LINE_SIZE).unwrap();
+    for index in 0..8 {
+        log_file.write(&format!("line {index:02}")).unwrap();
+    }
+    log_file.flush().unwrap();
+
+    // the current file and 5 previous files are retained
+    assert_eq!(read(&folder, "kaspad.log").unwrap(), lines(7..8));
+    assert_eq!(read(&folder, "kaspad.5.log").unwrap(), lines(2..3));
+    assert!(read(&folder, "kaspad.6.log").is_none());
+
+    // rotated out files are no longer available for export
+    let target = folder.join("export.log");
+    log_file.export_session(&target).unwrap();
+    assert_eq!(std::fs::read_to_string(&target).unwrap(), lines(2..8));
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn test_log_file_export_session() {
+    let folder = folder("export");
+
+    let mut log_file = LogFile::try_new_with_folder(folder.clone(), LINE_SIZE * 4).unwrap();
+    log_file.write("previous").unwrap();
+    drop(log_file);
+
+    // a new session starts with a new file
+    let mut log_file = LogFile::try_new_with_folder(folder.clone(), LINE_SIZE * 4).unwrap();
+    assert_eq!(read(&folder, "kaspad.log").unwrap(), "");
+    assert_eq!(read(&folder, "kaspad.1.log").unwrap(), "previous\n");
+
+    for index in 0..10 {
+        log_file.write(&format!("line {index:02}")).unwrap();
+    }
+
+    // logs of the current session are exported in order and
+    // without logs of the previous session
+    let target = folder.join("export.log");
+    log_file.export_session(&target).unwrap();
+    assert_eq!(std::fs::read_to_string(&target).unwrap(), lines(0..10));
+    assert_eq!(read(&folder, "kaspad.3.log").unwrap(), "previous\n");
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
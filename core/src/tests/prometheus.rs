@@ -0,0 +1,55 @@
+use crate::imports::*;
+use crate::runtime::services::metrics_monitor::prometheus::{metric_name, render_metrics};
+use kaspa_metrics_core::{Metric, MetricsSnapshot};
+
+#[test]
+fn test_prometheus_metric_name() {
+    assert_eq!(metric_name(&Metric::NodeCpuUsage), "kaspa_node_cpu_usage");
+    assert_eq!(
+        metric_name(&Metric::NodeResidentSetSizeBytes),
+        "kaspa_node_resident_set_size_bytes"
+    );
+
+    for metric in Metric::list() {
+        let name = metric_name(&metric);
+        assert!(
+            name.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+            "invalid metric name '{name}'"
+        );
+    }
+}
+
+#[test]
+fn test_prometheus_render_disconnected() {
+    let text = render_metrics(None, Network::Testnet11);
+    assert_eq!(
+        text,
+        "# HELP kaspa_node_connected Node connection state\n\
+         # TYPE kaspa_node_connected gauge\n\
+         kaspa_node_connected{network=\"testnet-11\"} 0\n"
+    );
+}
+
+#[test]
+fn test_prometheus_render_metrics() {
+    let snapshot = MetricsSnapshot {
+        node_cpu_usage: 12.5,
+        ..Default::default()
+    };
+    let text = render_metrics(Some(&snapshot), Network::Mainnet);
+    let lines = text.lines().collect::<Vec<_>>();
+
+    assert!(lines.contains(&"kaspa_node_connected{network=\"mainnet\"} 1"));
+    assert!(lines.contains(&"# TYPE kaspa_node_cpu_usage gauge"));
+    assert!(lines.contains(&"kaspa_node_cpu_usage{network=\"mainnet\"} 12.5"));
+
+    // every metric is exported with its help, type and sample lines
+    let samples = lines.iter().filter(|line| !line.starts_with('#')).count();
+    assert_eq!(samples, Metric::list().len() + 1);
+    let help = lines
+        .iter()
+        .filter(|line| line.starts_with("# HELP"))
+        .count();
+    assert_eq!(help, samples);
+}
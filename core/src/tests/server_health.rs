@@ -0,0 +1,89 @@
+use crate::imports::*;
+use crate::runtime::services::server_monitor::{ServerHealth, ServerHealthTable};
+
+fn server(address: &str, bias: Option<f32>) -> Server {
+    Server {
+        name: None,
+        ident: None,
+        location: None,
+        protocol: WrpcEncoding::Borsh,
+        address: address.to_string(),
+        enable: None,
+        link: None,
+        network: Network::Mainnet,
+        bias,
+        manual: None,
+        version: None,
+    }
+}
+
+fn healthy(server: Server, latency_millis: u64) -> ServerHealth {
+    ServerHealth {
+        server,
+        latency: Some(Duration::from_millis(latency_millis)),
+        server_version: Some("0.13.4".to_string()),
+        is_synced: Some(true),
+        has_utxo_index: Some(true),
+        virtual_daa_score: Some(1_000_000),
+        error: None,
+    }
+}
+
+#[test]
+fn test_server_health_score() {
+    let health = healthy(server("wss://a.example.com", None), 200);
+    assert_eq!(health.score(), Some(0.2));
+
+    // a higher bias lowers (improves) the score
+    let health = healthy(server("wss://a.example.com", Some(2.0)), 200);
+    assert_eq!(health.score(), Some(0.1));
+
+    // bias is clamped to avoid division by zero
+    let health = healthy(server("wss://a.example.com", Some(0.0)), 200);
+    assert!(health.score().unwrap().is_finite());
+}
+
+#[test]
+fn test_server_health_score_requires_healthy_server() {
+    let mut health = healthy(server("wss://a.example.com", None), 200);
+    health.is_synced = Some(false);
+    assert_eq!(health.score(), None);
+
+    let mut health = healthy(server("wss://a.example.com", None), 200);
+    health.has_utxo_index = None;
+    assert_eq!(health.score(), None);
+
+    let mut health = healthy(server("wss://a.example.com", None), 200);
+    health.error = Some("timeout".to_string());
+    assert_eq!(health.score(), None);
+}
+
+#[test]
+fn test_server_health_table_best_server() {
+    let a = server("wss://a.example.com", None);
+    let b = server("wss://b.example.com", Some(4.0));
+    let c = server("wss://c.example.com", None);
+
+    let mut unhealthy = healthy(c.clone(), 10);
+    unhealthy.is_synced = Some(false);
+
+    let table = ServerHealthTable {
+        network: Some(Network::Mainnet),
+        timestamp: None,
+        servers: vec![healthy(a.clone(), 100), healthy(b.clone(), 200), unhealthy],
+    };
+
+    let candidates = [a.clone(), b.clone(), c.clone()];
+    // b scores 0.05 against 0.1 for a; c is not synced
+    assert_eq!(table.best_server(&Network::Mainnet, &candidates), Some(b));
+
+    // only servers listed as candidates are considered
+    assert_eq!(
+        table.best_server(&Network::Mainnet, &[a.clone(), c.clone()]),
+        Some(a)
+    );
+    assert_eq!(table.best_server(&Network::Mainnet, &[c]), None);
+
+    // probe results of a different network are ignored
+    assert_eq!(table.best_server(&Network::Testnet10, &candidates), None);
+}
@@ -0,0 +1,112 @@
+use crate::imports::*;
+use crate::servers::{merge_imported_servers, merge_servers, ServerConfig};
+
+fn server(address: &str, network: Network, enable: Option<bool>) -> Server {
+    Server {
+        name: Some(format!("{address} server")),
+        ident: None,
+        location: Some("Europe".to_string()),
+        protocol: WrpcEncoding::Borsh,
+        address: address.to_string(),
+        enable,
+        link: None,
+        network,
+        bias: Some(1.5),
+        manual: Some(true),
+        version: None,
+    }
+}
+
+fn addresses(servers: &[Server]) -> Vec<&str> {
+    servers
+        .iter()
+        .map(|server| server.address.as_str())
+        .collect()
+}
+
+#[test]
+fn test_user_servers_export_import_round_trip() {
+    let servers = vec![
+        server("wss://a.example.com", Network::Mainnet, None),
+        server("wss://b.example.com", Network::Testnet10, Some(false)),
+    ];
+
+    let toml = ServerConfig::new(servers.clone()).to_toml().unwrap();
+    let parsed = ServerConfig::try_parse(&toml).unwrap();
+
+    // disabled entries are retained
+    assert_eq!(addresses(&parsed), addresses(&servers));
+    for (parsed, server) in parsed.iter().zip(servers.iter()) {
+        assert_eq!(parsed.name, server.name);
+        assert_eq!(parsed.location, server.location);
+        assert_eq!(parsed.network, server.network);
+        assert_eq!(parsed.enable, server.enable);
+        assert_eq!(parsed.bias, server.bias);
+        assert_eq!(parsed.manual, server.manual);
+    }
+}
+
+#[test]
+fn test_user_servers_import_replaces_by_address() {
+    let existing = vec![
+        server("wss://a.example.com", Network::Mainnet, None),
+        server("wss://b.example.com", Network::Mainnet, None),
+    ];
+    let mut replacement = server("wss://b.example.com", Network::Mainnet, Some(false));
+    replacement.name = Some("replacement".to_string());
+    let imported = vec![
+        replacement,
+        server("wss://c.example.com", Network::Mainnet, None),
+    ];
+
+    let servers = merge_imported_servers(existing, imported);
+    assert_eq!(
+        addresses(&servers),
+        vec![
+            "wss://a.example.com",
+            "wss://b.example.com",
+            "wss://c.example.com"
+        ]
+    );
+    assert_eq!(servers[1].name.as_deref(), Some("replacement"));
+    assert_eq!(servers[1].enable, Some(false));
+}
+
+#[test]
+fn test_user_servers_merge_into_defaults() {
+    let mut defaults = HashMap::new();
+    defaults.insert(
+        Network::Mainnet,
+        vec![
+            server("wss://a.example.com", Network::Mainnet, None),
+            server("wss://b.example.com", Network::Mainnet, None),
+        ],
+    );
+
+    let mut user = server("wss://a.example.com", Network::Mainnet, None);
+    user.name = Some("user".to_string());
+    let user_servers = vec![
+        user,
+        server("wss://c.example.com", Network::Mainnet, Some(false)),
+        server("wss://d.example.com", Network::Testnet11, None),
+    ];
+
+    let servers = merge_servers(&defaults, &user_servers);
+
+    // the user server replaces the default one; disabled servers are skipped
+    let mainnet = servers.get(&Network::Mainnet).unwrap();
+    assert_eq!(
+        addresses(mainnet),
+        vec!["wss://b.example.com", "wss://a.example.com"]
+    );
+    assert_eq!(mainnet[1].name.as_deref(), Some("user"));
+
+    let testnet = servers.get(&Network::Testnet11).unwrap();
+    assert_eq!(addresses(testnet), vec!["wss://d.example.com"]);
+
+    // defaults are left untouched
+    assert_eq!(
+        defaults.get(&Network::Mainnet).unwrap()[0].name.as_deref(),
+        Some("wss://a.example.com server")
+    );
+}
@@ -0,0 +1,76 @@
+use crate::imports::*;
+use crate::snapshot::*;
+
+fn manifest(paths: &[&str]) -> SnapshotManifest {
+    SnapshotManifest {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        network: Network::Mainnet,
+        kaspad_version: "0.13.4".to_string(),
+        application_version: "0.2.5".to_string(),
+        created: 1_700_006_400_000,
+        files: paths
+            .iter()
+            .map(|path| SnapshotFile {
+                path: path.to_string(),
+                size: 1024,
+            })
+            .collect(),
+    }
+}
+
+fn encode(manifest: &SnapshotManifest) -> Vec<u8> {
+    let json = serde_json::to_vec(manifest).unwrap();
+    let mut data = (json.len() as u32).to_le_bytes().to_vec();
+    data.extend_from_slice(&json);
+    data
+}
+
+#[test]
+fn test_snapshot_manifest_round_trip() {
+    let manifest = manifest(&["consensus/000001.sst", "meta/CURRENT"]);
+    let data = encode(&manifest);
+    let decoded = read_manifest(&mut data.as_slice()).unwrap();
+
+    assert_eq!(decoded.network, Network::Mainnet);
+    assert_eq!(decoded.files.len(), 2);
+    assert_eq!(decoded.files[0].path, "consensus/000001.sst");
+    assert_eq!(decoded.total_size(), 2048);
+    assert!(verify_manifest(&decoded, Network::Mainnet).is_ok());
+}
+
+#[test]
+fn test_snapshot_manifest_size_limit() {
+    let data = ((MAX_MANIFEST_SIZE + 1) as u32).to_le_bytes();
+    assert!(read_manifest(&mut data.as_slice()).is_err());
+
+    // truncated manifest
+    let mut data = encode(&manifest(&["meta/CURRENT"]));
+    data.truncate(data.len() - 1);
+    assert!(read_manifest(&mut data.as_slice()).is_err());
+}
+
+#[test]
+fn test_snapshot_manifest_verification() {
+    let mut unsupported = manifest(&["meta/CURRENT"]);
+    unsupported.format_version = SNAPSHOT_FORMAT_VERSION + 1;
+    assert!(verify_manifest(&unsupported, Network::Mainnet).is_err());
+
+    assert!(verify_manifest(&manifest(&["meta/CURRENT"]), Network::Testnet11).is_err());
+}
+
+#[test]
+fn test_snapshot_manifest_rejects_path_traversal() {
+    for path in [
+        "",
+        "..",
+        "../kaspa.key",
+        "meta/../../kaspa.key",
+        "./meta/CURRENT",
+        "/etc/passwd",
+    ] {
+        assert!(
+            verify_manifest(&manifest(&["meta/CURRENT", path]), Network::Mainnet).is_err(),
+            "path '{path}' should be rejected"
+        );
+    }
+}